@@ -1,14 +1,49 @@
+use flate2::write::GzEncoder;
+use flate2::Compression;
 use lazy_static::lazy_static;
 use regex::Regex;
-use rusty_chess_core::game::{Game, Piece, PieceType, Position, UserInput, UserOutput};
+use rusty_chess_core::analysis::{find_puzzles, review, MoveClass, PuzzleOptions};
+use rusty_chess_core::engine::{
+    find_mate, generate_data, self_play, DataGen, Engine, PvLine, SearchLimits, SelfPlay,
+    SkillLevel,
+};
+use rusty_chess_core::game::{
+    BoardFormatter, BotRng, Color, Game, GameStatus, Handicap, Move, PgnTags, Piece, PieceType,
+    Position, SeedableRng, SvgRenderer, UserInput,
+};
+use rusty_chess_core::player::RandomAggressive;
+use rusty_chess_core::tournament::{Sprt, SprtDecision, Tournament};
 use std::io;
 use std::io::BufRead;
+use std::io::{BufWriter, Write};
 use std::process::exit;
+use std::time::Duration;
+
+/// Lines shown by the "analyze" command.
+const ANALYSIS_LINES: usize = 3;
+const ANALYSIS_TIME: Duration = Duration::from_secs(2);
+/// Time the "review" command searches every position of the game.
+const REVIEW_TIME: Duration = Duration::from_millis(200);
+/// Longest mate the "mate" command looks for, in moves of the side to move.
+const MATE_MOVES: u8 = 3;
+/// Time the engine thinks about its moves when it plays Black.
+const ENGINE_MOVETIME: Duration = Duration::from_secs(1);
+/// Time of every move of the games of `--selfplay`, short enough for hundreds
+/// of games.
+const SELFPLAY_MOVETIME: Duration = Duration::from_millis(20);
+/// Positions the engine searches for every move of `--datagen`, the same
+/// quality of the scores on every machine.
+const DATAGEN_NODES: u64 = 5_000;
+/// Depth every position is searched to by `--puzzles`, deep enough for short
+/// combinations.
+const PUZZLE_DEPTH: u8 = 4;
+/// Random games for `--random-puzzles` are drawn after this many plies.
+const RANDOM_GAME_PLIES: usize = 200;
 
 fn parse_input_move(std_input: &str) -> Result<UserInput, String> {
     lazy_static! {
         static ref RE: Regex = Regex::new(
-            r"(?:\s*)?([a-zA-Z])(?:\s*)?(\d)(?:\s*)?(?:-|->)?(?:\s*)?([a-zA-Z])(?:\s*)?(\d)(?:\s*)?"
+            r"(?:\s*)?([a-zA-Z])(?:\s*)?(\d)(?:\s*)?(?:-|->)?(?:\s*)?([a-zA-Z])(?:\s*)?(\d)(?:\s*)?=?([qrbnQRBN])?"
         )
         .unwrap();
     }
@@ -16,6 +51,8 @@ fn parse_input_move(std_input: &str) -> Result<UserInput, String> {
         None => {
             if std_input.contains("Resign") || std_input.contains("resign") {
                 Ok(UserInput::Resign)
+            } else if std_input.contains("Claim") || std_input.contains("claim") {
+                Ok(UserInput::ClaimDraw)
             } else if std_input.contains("Draw") || std_input.contains("draw") {
                 Ok(UserInput::Draw)
             } else {
@@ -23,122 +60,574 @@ fn parse_input_move(std_input: &str) -> Result<UserInput, String> {
             }
         }
         Some(cap) => {
-            let from = Position(
-                cap[1].chars().next().unwrap(),
-                cap[2].chars().next().unwrap(),
-            );
-            let to = Position(
-                cap[3].chars().next().unwrap(),
-                cap[4].chars().next().unwrap(),
+            let parse_position = |file: &str, rank: &str| {
+                let file = file.to_lowercase().chars().next().unwrap();
+                let rank = rank.chars().next().unwrap();
+                Position::new(file, rank).ok_or_else(|| format!("{file}{rank} is not a square."))
+            };
+            let from = parse_position(&cap[1], &cap[2])?;
+            let to = parse_position(&cap[3], &cap[4])?;
+            let promotion = cap.get(5).map(|piece| match piece.as_str() {
+                "q" | "Q" => PieceType::Queen,
+                "r" | "R" => PieceType::Rook,
+                "b" | "B" => PieceType::Bishop,
+                _ => PieceType::Knight,
+            });
+            match promotion {
+                Some(piece_type) => Ok(UserInput::MoveWithPromotion(from, to, piece_type)),
+                None => Ok(UserInput::Move(from, to)),
+            }
+        }
+    }
+}
+
+fn read_promotion_piece(stdin: &io::Stdin, color: Color) -> Piece {
+    loop {
+        println!("To what piece do you want to promote your pawn (Queen, Rook, Knight, Bishop)?");
+        let promotion_str = stdin.lock().lines().next().unwrap().unwrap().to_lowercase();
+        let piece_type = if promotion_str.contains("queen") {
+            PieceType::Queen
+        } else if promotion_str.contains("rook") {
+            PieceType::Rook
+        } else if promotion_str.contains("knight") {
+            PieceType::Knight
+        } else if promotion_str.contains("bishop") {
+            PieceType::Bishop
+        } else {
+            println!("Invalid choice. Please choose between Queen, Rook, Bishop, Knight.");
+            continue;
+        };
+        return Piece::new(piece_type, color);
+    }
+}
+
+/// Asks the side to move whether it accepts the draw offer of the other side.
+fn answer_draw_offer(game: &mut Game, stdin: &io::Stdin) {
+    println!(
+        "{:?} offers a draw, does {:?} accept it? [y/N]",
+        game.turn.invert(),
+        game.turn
+    );
+    let input = stdin.lock().lines().next().unwrap().unwrap();
+    let answer = if input.contains('y') {
+        UserInput::AcceptDraw
+    } else {
+        UserInput::DeclineDraw
+    };
+    match game.process_input(&answer) {
+        Ok(GameStatus::Draw(_)) => {
+            println!("It is a draw!");
+            exit(0)
+        }
+        _ => println!("Draw has been refused!"),
+    }
+}
+
+fn parse_handicap(handicap: &str) -> Option<Handicap> {
+    match handicap.to_lowercase().as_str() {
+        "pawn" => Some(Handicap::PawnAndMove),
+        "knight" => Some(Handicap::KnightOdds),
+        "rook" => Some(Handicap::RookOdds),
+        "queen" => Some(Handicap::QueenOdds),
+        "move" => Some(Handicap::FirstMove),
+        _ => None,
+    }
+}
+
+/// Score from the view of White in pawns, or the moves until mate.
+fn format_score(line: &PvLine, turn: Color) -> String {
+    let sign = if turn == Color::White { 1 } else { -1 };
+    match line.mate_in() {
+        Some(moves) => format!("#{}", sign * moves),
+        None => format!("{:+.2}", f64::from(sign * line.score) / 100.0),
+    }
+}
+
+fn format_line(game: &Game, moves: &[Move]) -> String {
+    let mut game = game.clone();
+    let mut san = Vec::new();
+    for mv in moves {
+        san.push(game.san(mv));
+        let input = match mv.promotion {
+            Some(piece_type) => UserInput::MoveWithPromotion(mv.from, mv.to, piece_type),
+            None => UserInput::Move(mv.from, mv.to),
+        };
+        if game.process_input(&input).is_err() {
+            break;
+        }
+    }
+    san.join(" ")
+}
+
+fn print_analysis(game: &Game) {
+    let limits = SearchLimits::movetime(ANALYSIS_TIME);
+    let result = Engine::new()
+        .multi_pv(ANALYSIS_LINES)
+        .search_with_info(game, &limits, |info| {
+            println!(
+                "depth {:>2} {:>6} {:>8} positions/s  {}",
+                info.depth,
+                format_score(&info.lines[0], game.turn),
+                info.nps,
+                format_line(game, info.pv)
             );
-            Ok(UserInput::Move(from, to))
+        });
+    if result.lines.is_empty() {
+        println!("The game is over, there is nothing to analyze.");
+        return;
+    }
+    println!("Depth {}, {} positions:", result.depth, result.nodes);
+    for (i, line) in result.lines.iter().enumerate() {
+        println!(
+            "{}. {:>6}  {}",
+            i + 1,
+            format_score(line, game.turn),
+            format_line(game, &line.moves)
+        );
+    }
+}
+
+fn print_mate(game: &Game) {
+    match find_mate(game, 2 * MATE_MOVES - 1) {
+        Some(line) => println!(
+            "Mate in {}: {}",
+            line.len().div_ceil(2),
+            format_line(game, &line)
+        ),
+        None => println!("There is no forced mate in {MATE_MOVES} moves."),
+    }
+}
+
+/// The inaccuracies, mistakes and blunders of the game so far and how well
+/// each side played.
+fn print_review(game: &Game) {
+    let report = review(game, &SearchLimits::movetime(REVIEW_TIME));
+    let mut position = game.clone();
+    position.goto_ply(0);
+    for reviewed in &report.moves {
+        let number = position.fullmove_number();
+        let dots = if position.turn == Color::White {
+            "."
+        } else {
+            "..."
+        };
+        let best = reviewed.best_move.map(|best| position.san(&best));
+        position.redo();
+        if reviewed.class < MoveClass::Inaccuracy {
+            continue;
         }
+        println!(
+            "{number}{dots} {}: {:?}, loses {:.2}{}",
+            reviewed.san,
+            reviewed.class,
+            f64::from(reviewed.loss) / 100.0,
+            best.map(|best| format!(", {best} was best"))
+                .unwrap_or_default()
+        );
     }
+    for color in [Color::White, Color::Black] {
+        println!(
+            "{color:?}: {} inaccuracies, {} mistakes, {} blunders, {:.2} pawns lost per move",
+            report.count(color, MoveClass::Inaccuracy),
+            report.count(color, MoveClass::Mistake),
+            report.count(color, MoveClass::Blunder),
+            f64::from(report.average_loss(color)) / 100.0
+        );
+    }
+}
+
+/// The move of the engine in the same format a player types it.
+fn engine_move(engine: &Engine, game: &Game) -> String {
+    let mv = engine
+        .search(game, &SearchLimits::movetime(ENGINE_MOVETIME))
+        .best_move
+        .expect("The game goes on, so there is a move to play.");
+    println!("{:?} plays {}.", game.turn, game.san(&mv));
+    mv.to_uci()
 }
 
-fn headless_chess() {
+/// `engine` plays Black if it is given, otherwise both sides are played on
+/// the command line.
+fn headless_chess(handicap: Option<Handicap>, formatter: BoardFormatter, engine: Option<Engine>) {
     println!("Hello to rusty chess. Let's start a game:\n");
-    let mut game = Game::new();
+    let mut game = handicap.map_or_else(Game::new, Game::with_handicap);
     let stdin = io::stdin();
     let mut previous_loop_turn = game.turn.invert();
     loop {
+        let engine_turn = engine.is_some() && game.turn == Color::Black;
         if previous_loop_turn != game.turn {
-            println!("{game}");
+            println!("{}", formatter.format(&game));
+        }
+        if previous_loop_turn != game.turn && !engine_turn {
             println!(
-                "{:?}'s turn. Please input a move (e.g. \"e2e4\" moves piece from e2 to e4)",
+                "{:?}'s turn. Please input a move (e.g. \"e2e4\" moves piece from e2 to e4, \"e7e8q\" promotes to a queen)",
                 game.turn
             );
+            if let Some(reason) = game.claimable_draw() {
+                println!("You can claim a draw ({reason:?}) with \"claim\".");
+            }
+            if game.draw_offer() == Some(game.turn.invert()) {
+                answer_draw_offer(&mut game, &stdin);
+            }
         }
         previous_loop_turn = game.turn;
-        let input_move = stdin.lock().lines().next().unwrap().unwrap();
+        let input_move = match &engine {
+            Some(engine) if engine_turn => engine_move(engine, &game),
+            _ => stdin.lock().lines().next().unwrap().unwrap(),
+        };
+        if input_move.trim().eq_ignore_ascii_case("fen") {
+            println!("{}", game.to_fen());
+            continue;
+        }
+        if input_move.trim().eq_ignore_ascii_case("pgn") {
+            println!("{}", game.to_pgn());
+            continue;
+        }
+        if input_move.trim().eq_ignore_ascii_case("svg") {
+            println!("{}", SvgRenderer::new().render(&game));
+            continue;
+        }
+        if input_move.trim().eq_ignore_ascii_case("analyze") {
+            print_analysis(&game);
+            continue;
+        }
+        if input_move.trim().eq_ignore_ascii_case("review") {
+            print_review(&game);
+            continue;
+        }
+        if input_move.trim().eq_ignore_ascii_case("mate") {
+            print_mate(&game);
+            continue;
+        }
+        if input_move.trim().eq_ignore_ascii_case("undo") {
+            if game.undo().is_none() {
+                println!("There is no move to take back.");
+            }
+            // take back the engine's answer as well, so that it is the player's turn again
+            if engine.is_some() && game.turn == Color::Black {
+                game.undo();
+            }
+            previous_loop_turn = game.turn.invert();
+            continue;
+        }
+        if input_move.trim().eq_ignore_ascii_case("redo") {
+            if game.redo().is_none() {
+                println!("There is no move to play again.");
+            }
+            previous_loop_turn = game.turn.invert();
+            continue;
+        }
         match parse_input_move(&input_move) {
             Err(e) => println!("{e}"),
-            Ok(UserInput::Move(from, to)) => {
-                let user_output = game.process_input(&UserInput::Move(from, to));
-                if user_output.is_some() {
-                    match user_output.unwrap() {
-                        UserOutput::InvalidMove => {
-                            println!("Not a valid move please repeat a move.");
-                        }
-                        UserOutput::Draw => {
-                            println!("{game}");
-                            println!("It is a draw!");
-                            exit(0)
-                        }
-                        UserOutput::CheckMate => {
-                            println!("{game}");
-                            println!("{:?} has won!", game.turn.invert());
-                            exit(0)
-                        }
-                        UserOutput::StaleMate => {
-                            println!("{game}");
-                            println!("It is a draw stalemate!");
-                            exit(0)
-                        }
-                        UserOutput::Promotion(pos) => {
-                            println!("{game}");
-                            println!("To what piece do you want to promote your pawn (Queen, Rook, Knight, Bishop)?");
-                            let promotion_str = stdin.lock().lines().next().unwrap().unwrap();
-                            let color = game.turn;
-                            if promotion_str.contains("Queen") || promotion_str.contains("queen") {
-                                game.process_input(&UserInput::Promotion(
-                                    Piece::new(PieceType::Queen, color),
-                                    pos,
-                                ));
-                            } else if promotion_str.contains("Rook")
-                                || promotion_str.contains("rook")
-                            {
-                                game.process_input(&UserInput::Promotion(
-                                    Piece::new(PieceType::Rook, color),
-                                    pos,
-                                ));
-                            } else if promotion_str.contains("Knight")
-                                || promotion_str.contains("knight")
-                            {
-                                game.process_input(&UserInput::Promotion(
-                                    Piece::new(PieceType::Knight, color),
-                                    pos,
-                                ));
-                            } else if promotion_str.contains("Bishop")
-                                || promotion_str.contains("Bishop")
-                            {
-                                game.process_input(&UserInput::Promotion(
-                                    Piece::new(PieceType::Bishop, color),
-                                    pos,
-                                ));
-                            } else {
-                                println!("Invalid choice. Please choose between Queen, Rook, Bishop, Knight.");
-                                continue;
-                            }
-                        }
+            Ok(input @ (UserInput::Move(_, _) | UserInput::MoveWithPromotion(_, _, _))) => {
+                let status = match game.process_input(&input) {
+                    Err(e) => {
+                        println!("Not a valid move ({e}), please repeat a move.");
+                        continue;
+                    }
+                    Ok(GameStatus::AwaitingPromotion(pos)) => {
+                        println!("{}", formatter.format(&game));
+                        let piece = read_promotion_piece(&stdin, game.turn);
+                        game.process_input(&UserInput::Promotion(piece, pos))
+                            .expect("Promotion is always valid after a pawn reached the last rank.")
+                    }
+                    Ok(status) => status,
+                };
+                match status {
+                    GameStatus::Draw(_) => {
+                        println!("{}", formatter.format(&game));
+                        println!("It is a draw!");
+                        exit(0)
+                    }
+                    GameStatus::Checkmate(winner) | GameStatus::VariantWin(winner) => {
+                        println!("{}", formatter.format(&game));
+                        println!("{winner:?} has won!");
+                        exit(0)
                     }
+                    GameStatus::Stalemate => {
+                        println!("{}", formatter.format(&game));
+                        println!("It is a draw stalemate!");
+                        exit(0)
+                    }
+                    _ => {}
                 }
             }
+            Ok(UserInput::ClaimDraw) => match game.process_input(&UserInput::ClaimDraw) {
+                Ok(_) => {
+                    println!("{:?} claims a draw. It is a draw!", game.turn);
+                    exit(0)
+                }
+                Err(e) => println!("{e}"),
+            },
             Ok(UserInput::Resign) => {
-                println!("{:?} resigns!", game.turn);
-                exit(0)
+                if let Ok(GameStatus::Resignation(winner)) = game.process_input(&UserInput::Resign)
+                {
+                    println!("{:?} resigns! {winner:?} has won!", game.turn);
+                    exit(0)
+                }
             }
             Ok(UserInput::Draw) => {
+                game.process_input(&UserInput::Draw)
+                    .expect("The side to move can always offer a draw.");
                 println!(
-                    "{:?} offers a draw does {:?} accept it? [y/N]",
+                    "{:?} offers a draw, {:?} answers it after your move.",
                     game.turn,
                     game.turn.invert()
                 );
-                let input_move = stdin.lock().lines().next().unwrap().unwrap();
-                if input_move.contains('y') {
-                    println!("It is a draw!");
-                    exit(0)
-                } else {
-                    println!("Draw has been refused!");
-                }
             }
-            Ok(UserInput::Promotion(_, _)) => {
+            Ok(
+                UserInput::Promotion(_, _)
+                | UserInput::AcceptDraw
+                | UserInput::DeclineDraw
+                | UserInput::Timeout,
+            ) => {
                 unreachable!("Should not be an output of parsing.")
             }
         }
     }
 }
 
+/// Plays the engine against `opponent` and prints the score of the engine,
+/// at most `games` games or until `sprt` decides.
+fn print_self_play(games: u32, opponent: &Engine, sprt: Option<Sprt>) {
+    let options = SelfPlay {
+        seed: time_seed(),
+        sprt,
+        ..SelfPlay::new(games, SearchLimits::movetime(SELFPLAY_MOVETIME))
+    };
+    let score = self_play(&Engine::new(), opponent, &options, |score| {
+        eprint!("\rGame {}/{games}: {score}", score.games());
+        if let Some(sprt) = sprt {
+            let (lower, upper) = sprt.bounds();
+            eprint!(" LLR {:.2} ({lower:.2}, {upper:.2})", sprt.llr(score));
+        }
+    });
+    eprintln!();
+    println!("Score: {:.1}% ({score})", 100.0 * score.score());
+    println!(
+        "Elo difference: {:.1} +/- {:.1}",
+        score.elo_difference(),
+        score.elo_error()
+    );
+    println!("Draw rate: {:.1}%", 100.0 * score.draw_rate());
+    if let Some(sprt) = sprt {
+        let decision = match sprt.decision(&score) {
+            Some(SprtDecision::Pass) => "passed",
+            Some(SprtDecision::Fail) => "failed",
+            None => "undecided",
+        };
+        println!("SPRT [{}, {}]: {decision}", sprt.elo0, sprt.elo1);
+    }
+}
+
+/// Plays `games` games of the engine against itself and writes them to
+/// `<prefix>.pgn.gz` and the scored positions to `<prefix>.txt`. `noise` is
+/// the chance of a random move instead of the one of the engine.
+fn write_data(engine: &Engine, games: u32, prefix: &str, noise: f64) -> io::Result<()> {
+    let create = |extension: &str| {
+        let path = format!("{prefix}.{extension}");
+        std::fs::File::create(&path)
+            .map(BufWriter::new)
+            .map_err(|e| io::Error::new(e.kind(), format!("{path}: {e}")))
+    };
+    let mut pgn = GzEncoder::new(create("pgn.gz")?, Compression::default());
+    let mut records = create("txt")?;
+    let options = DataGen {
+        noise,
+        seed: time_seed(),
+        ..DataGen::new(games, SearchLimits::nodes(DATAGEN_NODES))
+    };
+    let mut written = Ok(());
+    let mut positions = 0;
+    let mut round = 0;
+    generate_data(engine, &options, |played| {
+        round += 1;
+        positions += played.positions.len();
+        let tags = PgnTags {
+            event: String::from("rusty-chess self-play"),
+            round: round.to_string(),
+            white: String::from("rusty-chess"),
+            black: String::from("rusty-chess"),
+            ..PgnTags::default()
+        };
+        if written.is_ok() {
+            written = writeln!(pgn, "{}", played.game.to_pgn_with_tags(&tags))
+                .and_then(|()| records.write_all(played.to_records().as_bytes()));
+        }
+        eprint!("\rGame {round}/{games}: {positions} positions");
+    });
+    eprintln!();
+    written?;
+    pgn.finish()?.flush()?;
+    records.flush()
+}
+
+/// Prints the positions of the games with a single winning move as EPD, one
+/// line each.
+fn print_puzzles(games: Vec<Game>) {
+    let options = PuzzleOptions::new(SearchLimits::depth(PUZZLE_DEPTH));
+    let mut found = 0;
+    for (index, game) in games.iter().enumerate() {
+        for puzzle in find_puzzles(game, &options) {
+            println!("{}", puzzle.to_epd());
+            found += 1;
+        }
+        eprint!("\rGame {}/{}: {found} puzzles", index + 1, games.len());
+    }
+    eprintln!();
+}
+
+/// The games of a PGN file, exits if it can not be read.
+fn read_pgn_games(path: &str) -> Vec<Game> {
+    let text = std::fs::read_to_string(path).unwrap_or_else(|e| {
+        eprintln!("Can not read {path}: {e}");
+        exit(1)
+    });
+    let games: Vec<Game> = Game::from_pgn_games(&text)
+        .into_iter()
+        .filter_map(Result::ok)
+        .collect();
+    if games.is_empty() {
+        eprintln!("No games in {path}.");
+        exit(1)
+    }
+    games
+}
+
+/// `count` games of two bots playing random moves, preferring captures.
+fn random_games(count: u32) -> Vec<Game> {
+    let seed = time_seed();
+    let result = Tournament::new()
+        .player("Random", RandomAggressive::new(BotRng::seed_from_u64(seed)))
+        .player(
+            "Random",
+            RandomAggressive::new(BotRng::seed_from_u64(seed.wrapping_add(1))),
+        )
+        .rounds(count.div_ceil(2))
+        .max_plies(RANDOM_GAME_PLIES)
+        .run(|_| {});
+    result
+        .games
+        .into_iter()
+        .take(count as usize)
+        .map(|played| played.game)
+        .collect()
+}
+
+fn time_seed() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |time| time.as_secs())
+}
+
 fn main() {
-    headless_chess();
+    let usage = || {
+        eprintln!(
+            "Usage: rusty-chess-cli [--handicap pawn|knight|rook|queen|move] [--ascii] [--level 0-20] [--selfplay GAMES [--sprt ELO0 ELO1]] [--puzzles PGN | --random-puzzles GAMES] [--datagen GAMES PREFIX [--noise PERCENT]]"
+        );
+        exit(1)
+    };
+    let mut handicap = None;
+    let mut formatter = BoardFormatter::new();
+    let mut engine = None;
+    let mut selfplay_games = None;
+    let mut sprt = None;
+    let mut puzzle_games = None;
+    let mut datagen = None;
+    let mut noise = 0.0;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--handicap" => {
+                let Some(name) = args.next() else { usage() };
+                let Some(parsed) = parse_handicap(&name) else {
+                    eprintln!(
+                        "Unknown handicap \"{name}\", choose pawn, knight, rook, queen or move."
+                    );
+                    exit(1)
+                };
+                handicap = Some(parsed);
+            }
+            // for terminals that can not show the chess glyphs
+            "--ascii" => formatter = formatter.unicode(false),
+            "--level" => {
+                let Some(level) = args.next() else { usage() };
+                let Some(level) = level
+                    .parse()
+                    .ok()
+                    .filter(|&level| level <= SkillLevel::MAX.level())
+                else {
+                    eprintln!(
+                        "Unknown level \"{level}\", choose a number from {} to {}.",
+                        SkillLevel::MIN.level(),
+                        SkillLevel::MAX.level()
+                    );
+                    exit(1)
+                };
+                engine = Some(
+                    Engine::new()
+                        .skill_level(SkillLevel::new(level))
+                        .seed(time_seed()),
+                );
+            }
+            "--selfplay" => {
+                let Some(games) = args.next().and_then(|games| games.parse().ok()) else {
+                    usage()
+                };
+                selfplay_games = Some(games);
+            }
+            "--sprt" => {
+                let mut elo = || args.next().and_then(|elo| elo.parse().ok());
+                let (Some(elo0), Some(elo1)) = (elo(), elo()) else {
+                    usage()
+                };
+                sprt = Some(Sprt::new(elo0, elo1));
+            }
+            "--puzzles" => {
+                let Some(path) = args.next() else { usage() };
+                puzzle_games = Some(read_pgn_games(&path));
+            }
+            "--random-puzzles" => {
+                let Some(games) = args.next().and_then(|games| games.parse().ok()) else {
+                    usage()
+                };
+                puzzle_games = Some(random_games(games));
+            }
+            "--datagen" => {
+                let games = args.next().and_then(|games| games.parse::<u32>().ok());
+                let (Some(games), Some(prefix)) = (games, args.next()) else {
+                    usage()
+                };
+                datagen = Some((games, prefix));
+            }
+            "--noise" => {
+                let Some(percent) = args
+                    .next()
+                    .and_then(|percent| percent.parse::<f64>().ok())
+                    .filter(|percent| (0.0..=100.0).contains(percent))
+                else {
+                    usage()
+                };
+                noise = percent / 100.0;
+            }
+            _ => usage(),
+        }
+    }
+    if let Some((games, prefix)) = datagen {
+        if let Err(e) = write_data(&engine.unwrap_or_default(), games, &prefix, noise) {
+            eprintln!("Can not write the games: {e}");
+            exit(1)
+        }
+        return;
+    }
+    if let Some(games) = puzzle_games {
+        print_puzzles(games);
+        return;
+    }
+    if let Some(games) = selfplay_games {
+        // against itself unless a level is given
+        print_self_play(games, &engine.unwrap_or_default(), sprt);
+        return;
+    }
+    headless_chess(handicap, formatter, engine);
 }
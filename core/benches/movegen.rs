@@ -0,0 +1,82 @@
+//! Run with `cargo bench -p rusty-chess-core`, compare against a saved run with
+//! `cargo bench -p rusty-chess-core -- --save-baseline main` and
+//! `-- --baseline main`.
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use rusty_chess_core::bots;
+use rusty_chess_core::game::{
+    BotRng, Game, Position, SeedableRng, UserInput, STARTING_POSITION_FEN,
+};
+
+const KIWIPETE_FEN: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+const MIDDLEGAME_FEN: &str =
+    "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10";
+const ENDGAME_FEN: &str = "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1";
+
+const POSITIONS: [(&str, &str); 4] = [
+    ("start", STARTING_POSITION_FEN),
+    ("kiwipete", KIWIPETE_FEN),
+    ("middlegame", MIDDLEGAME_FEN),
+    ("endgame", ENDGAME_FEN),
+];
+
+/// Legal moves of a position that did not generate them yet.
+fn move_generation(c: &mut Criterion) {
+    let mut group = c.benchmark_group("legal_moves");
+    for (name, fen) in POSITIONS {
+        let game = Game::from_fen(fen).unwrap();
+        group.bench_function(name, |b| {
+            b.iter_batched(
+                || game.clone(),
+                |game| game.legal_moves().len(),
+                BatchSize::SmallInput,
+            );
+        });
+    }
+    group.finish();
+}
+
+fn process_input(c: &mut Criterion) {
+    let game = Game::new();
+    let input = UserInput::Move(Position('e', '2'), Position('e', '4'));
+    c.bench_function("process_input", |b| {
+        b.iter_batched(
+            || game.clone(),
+            |mut game| game.process_input(&input).unwrap(),
+            BatchSize::SmallInput,
+        );
+    });
+}
+
+fn perft(c: &mut Criterion) {
+    let mut group = c.benchmark_group("perft");
+    group.sample_size(10);
+    let start = Game::new();
+    group.bench_function("start_4", |b| b.iter(|| start.perft(4)));
+    let kiwipete = Game::from_fen(KIWIPETE_FEN).unwrap();
+    group.bench_function("kiwipete_3", |b| b.iter(|| kiwipete.perft(3)));
+    let endgame = Game::from_fen(ENDGAME_FEN).unwrap();
+    group.bench_function("endgame_5", |b| b.iter(|| endgame.perft(5)));
+    group.finish();
+}
+
+/// Move choice of the aggressive bot of the frontends: the first capture,
+/// otherwise a random legal move.
+fn bot_move(c: &mut Criterion) {
+    let mut group = c.benchmark_group("bot_move");
+    let mut rng = BotRng::seed_from_u64(0);
+    for (name, fen) in POSITIONS {
+        let game = Game::from_fen(fen).unwrap();
+        group.bench_function(name, |b| {
+            b.iter_batched(
+                || game.clone(),
+                |game| bots::randomly_aggressive_move(&game, &mut rng),
+                BatchSize::SmallInput,
+            );
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, move_generation, process_input, perft, bot_move);
+criterion_main!(benches);
@@ -0,0 +1,7 @@
+mod motifs;
+mod puzzle;
+mod review;
+
+pub use motifs::{tactical_motifs, Motif};
+pub use puzzle::{find_puzzles, Puzzle, PuzzleOptions};
+pub use review::{review, MoveClass, Review, ReviewedMove};
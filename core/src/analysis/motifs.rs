@@ -0,0 +1,204 @@
+use crate::game::{Color, Game, Piece, PieceType, Position, BOARD_SIZE};
+use alloc::vec::Vec;
+
+/// Lines of the sliding pieces, the straight ones first.
+const DIRECTIONS: [(i8, i8); 8] = [
+    (0, 1),
+    (0, -1),
+    (1, 0),
+    (-1, 0),
+    (1, 1),
+    (1, -1),
+    (-1, 1),
+    (-1, -1),
+];
+
+/// A tactical pattern on the board. The pieces are given with the squares they
+/// stand on, the attacking side is the color of the first piece.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Motif {
+    /// `attacker` attacks two or more pieces at once, each of them the king,
+    /// more valuable than the attacker or not protected.
+    Fork {
+        attacker: (Piece, Position),
+        targets: Vec<(Piece, Position)>,
+    },
+    /// `pinned` can not move away without exposing the more valuable `behind`
+    /// to the sliding `attacker`. The pin is absolute if `behind` is the king.
+    Pin {
+        attacker: (Piece, Position),
+        pinned: (Piece, Position),
+        behind: (Piece, Position),
+    },
+    /// The sliding `attacker` attacks `front`, which has to move away and
+    /// leaves the less valuable `behind` to be taken.
+    Skewer {
+        attacker: (Piece, Position),
+        front: (Piece, Position),
+        behind: (Piece, Position),
+    },
+    /// Moving `piece` out of the way uncovers an attack of `slider` of the same
+    /// color on `target`.
+    DiscoveredAttack {
+        piece: (Piece, Position),
+        slider: (Piece, Position),
+        target: (Piece, Position),
+    },
+    /// `piece` is attacked and either not protected or attacked by a less
+    /// valuable piece.
+    Hanging {
+        piece: (Piece, Position),
+        attackers: Vec<(Piece, Position)>,
+    },
+}
+
+/// Forks, pins, skewers, discovered attacks and hanging pieces of both sides
+/// in the position, e.g. for a frontend to point out "your knight is forked".
+/// Only the geometry is checked, whether a motif wins material depends on the
+/// rest of the position.
+///
+/// ```
+/// use rusty_chess_core::analysis::{tactical_motifs, Motif};
+/// use rusty_chess_core::game::{Game, PieceType, Position};
+///
+/// let game = Game::from_fen("8/2k1q3/8/3N4/8/8/8/4K3 b - - 0 1").unwrap();
+/// let fork = tactical_motifs(&game)
+///     .into_iter()
+///     .find_map(|motif| match motif {
+///         Motif::Fork { attacker, targets } => Some((attacker, targets)),
+///         _ => None,
+///     })
+///     .unwrap();
+/// assert_eq!(fork.0 .1, Position('d', '5'));
+/// let targets: Vec<_> = fork.1.iter().map(|(piece, _)| piece.piece_type).collect();
+/// assert_eq!(targets, [PieceType::King, PieceType::Queen]);
+/// ```
+#[must_use]
+pub fn tactical_motifs(game: &Game) -> Vec<Motif> {
+    let mut motifs = Vec::new();
+    for color in [Color::White, Color::Black] {
+        forks(game, color, &mut motifs);
+        lines(game, color, &mut motifs);
+        hanging(game, color, &mut motifs);
+    }
+    motifs
+}
+
+/// Forks by the pieces of `color`.
+fn forks(game: &Game, color: Color, motifs: &mut Vec<Motif>) {
+    for (pos, piece) in game.pieces_of(color) {
+        let targets: Vec<(Piece, Position)> = game
+            .pieces_of(color.invert())
+            .filter(|(target_pos, target)| {
+                worth_attacking(game, *target, *target_pos, piece)
+                    && game
+                        .attackers_of(*target_pos, color)
+                        .iter()
+                        .any(|(_, from)| *from == pos)
+            })
+            .map(|(target_pos, target)| (target, target_pos))
+            .collect();
+        if targets.len() >= 2 {
+            motifs.push(Motif::Fork {
+                attacker: (piece, pos),
+                targets,
+            });
+        }
+    }
+}
+
+/// Pins, skewers and discovered attacks along the lines of the sliding
+/// pieces of `color`.
+fn lines(game: &Game, color: Color, motifs: &mut Vec<Motif>) {
+    for (pos, piece) in game.pieces_of(color) {
+        let directions: &[(i8, i8)] = match piece.piece_type {
+            PieceType::Bishop => &DIRECTIONS[4..],
+            PieceType::Rook => &DIRECTIONS[..4],
+            PieceType::Queen => &DIRECTIONS,
+            _ => continue,
+        };
+        for &direction in directions {
+            let mut pieces = ray(game, pos, direction);
+            let (Some(first), Some(second)) = (pieces.next(), pieces.next()) else {
+                continue;
+            };
+            let slider = (piece, pos);
+            if first.0.color == color {
+                if second.0.color != color && worth_attacking(game, second.0, second.1, piece) {
+                    motifs.push(Motif::DiscoveredAttack {
+                        piece: first,
+                        slider,
+                        target: second,
+                    });
+                }
+            } else if second.0.color != color {
+                if value(first.0) > value(second.0) {
+                    motifs.push(Motif::Skewer {
+                        attacker: slider,
+                        front: first,
+                        behind: second,
+                    });
+                } else if value(first.0) < value(second.0) {
+                    motifs.push(Motif::Pin {
+                        attacker: slider,
+                        pinned: first,
+                        behind: second,
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Pieces of `color` that can be taken.
+fn hanging(game: &Game, color: Color, motifs: &mut Vec<Motif>) {
+    for (pos, piece) in game.pieces_of(color) {
+        if piece.piece_type == PieceType::King {
+            continue;
+        }
+        let attackers = game.attackers_of(pos, color.invert());
+        let protected = !game.attackers_of(pos, color).is_empty();
+        let cheaper_attacker = attackers.iter().any(|(attacker, _)| {
+            attacker.piece_type != PieceType::King && value(*attacker) < value(piece)
+        });
+        if !attackers.is_empty() && (!protected || cheaper_attacker) {
+            motifs.push(Motif::Hanging {
+                piece: (piece, pos),
+                attackers,
+            });
+        }
+    }
+}
+
+/// Attacking `target` with `attacker` threatens something: it is the king,
+/// more valuable than the attacker or not protected.
+fn worth_attacking(game: &Game, target: Piece, pos: Position, attacker: Piece) -> bool {
+    target.piece_type == PieceType::King
+        || value(target) > value(attacker)
+        || game.attackers_of(pos, target.color).is_empty()
+}
+
+/// The king is worth more than everything else.
+const fn value(piece: Piece) -> u8 {
+    match piece.piece_type {
+        PieceType::King => u8::MAX,
+        piece_type => piece_type.value(),
+    }
+}
+
+/// The pieces from `from` in `direction`, nearest first.
+fn ray(
+    game: &Game,
+    from: Position,
+    direction: (i8, i8),
+) -> impl Iterator<Item = (Piece, Position)> + '_ {
+    let file = (from.0 as u8 - b'a') as i8;
+    let rank = (from.1 as u8 - b'1') as i8;
+    (1..BOARD_SIZE as i8)
+        .map(move |distance| (file + direction.0 * distance, rank + direction.1 * distance))
+        .take_while(|(file, rank)| {
+            (0..BOARD_SIZE as i8).contains(file) && (0..BOARD_SIZE as i8).contains(rank)
+        })
+        .map(|(file, rank)| Position((b'a' + file as u8) as char, (b'1' + rank as u8) as char))
+        .filter_map(|pos| game.board[pos.as_index()].map(|piece| (piece, pos)))
+}
@@ -0,0 +1,129 @@
+use crate::engine::{Engine, SearchLimits};
+use crate::game::{Game, Move};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// A position of a game with a single winning move, found by
+/// [`find_puzzles`].
+#[derive(Debug, Clone)]
+pub struct Puzzle {
+    /// The position, the side to move has to find `best_move`.
+    pub game: Game,
+    pub best_move: Move,
+    /// `best_move` in standard algebraic notation.
+    pub san: String,
+    /// Centipawns after the best move from the view of the side to move.
+    pub score: i32,
+    /// Centipawns after the second best move.
+    pub second_score: i32,
+    /// Moves until the side to move mates, if the best move mates.
+    pub mate_in: Option<i32>,
+}
+
+/// When a position is a puzzle for [`find_puzzles`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PuzzleOptions {
+    /// Search of every position, deep enough to see the tactic through.
+    pub limits: SearchLimits,
+    /// Centipawns the best move has to win at least.
+    pub min_score: i32,
+    /// Centipawns every other move may win at most, so the best move is the
+    /// only one that wins.
+    pub max_second_score: i32,
+}
+
+impl PuzzleOptions {
+    /// The best move wins at least three pawns and no other move more than
+    /// one pawn.
+    #[must_use]
+    pub const fn new(limits: SearchLimits) -> Self {
+        PuzzleOptions {
+            limits,
+            min_score: 300,
+            max_second_score: 100,
+        }
+    }
+}
+
+impl Puzzle {
+    /// The puzzle as EPD line with the best move as `bm` opcode and the score
+    /// as `ce`, or the moves to mate as `dm` if it mates.
+    ///
+    /// ```
+    /// use rusty_chess_core::analysis::{find_puzzles, PuzzleOptions};
+    /// use rusty_chess_core::engine::SearchLimits;
+    /// use rusty_chess_core::game::Game;
+    ///
+    /// let game = Game::from_fen("6k1/3ppppp/8/8/8/3r4/5PPP/R5K1 w - - 0 1").unwrap();
+    /// let puzzles = find_puzzles(&game, &PuzzleOptions::new(SearchLimits::depth(2)));
+    /// assert_eq!(puzzles[0].to_epd(), "6k1/3ppppp/8/8/8/3r4/5PPP/R5K1 w - - bm Ra8#; dm 1;");
+    /// ```
+    #[must_use]
+    pub fn to_epd(&self) -> String {
+        let fen = self.game.to_fen();
+        let position: Vec<&str> = fen.split_whitespace().take(4).collect();
+        let evaluation = match self.mate_in {
+            Some(moves) => format!("dm {moves};"),
+            None => format!("ce {};", self.score),
+        };
+        format!("{} bm {}; {evaluation}", position.join(" "), self.san)
+    }
+}
+
+/// The positions of the game, including the one it starts from, in which
+/// exactly one move wins as [`PuzzleOptions`] asks for, e.g. to build
+/// puzzles out of finished games. Every position is searched for its two best
+/// moves, so this takes a while for long games.
+///
+/// ```
+/// use rusty_chess_core::analysis::{find_puzzles, PuzzleOptions};
+/// use rusty_chess_core::engine::SearchLimits;
+/// use rusty_chess_core::game::Game;
+///
+/// // 3. Qxf7# mates
+/// let game = Game::from_pgn("1. e4 e5 2. Qh5 Nc6 3. Bc4 Nf6 4. Qxf7#").unwrap();
+/// let puzzles = find_puzzles(&game, &PuzzleOptions::new(SearchLimits::depth(2)));
+/// assert_eq!(puzzles.len(), 1);
+/// assert_eq!(puzzles[0].san, "Qxf7#");
+/// assert_eq!(puzzles[0].mate_in, Some(1));
+/// ```
+#[must_use]
+pub fn find_puzzles(game: &Game, options: &PuzzleOptions) -> Vec<Puzzle> {
+    let engine = Engine::new().multi_pv(2);
+    let mut position = game.clone();
+    position.goto_ply(0);
+    let mut puzzles = Vec::new();
+    loop {
+        if let Some(puzzle) = puzzle(&engine, &position, options) {
+            puzzles.push(puzzle);
+        }
+        if position.redo().is_none() {
+            break;
+        }
+    }
+    puzzles
+}
+
+fn puzzle(engine: &Engine, game: &Game, options: &PuzzleOptions) -> Option<Puzzle> {
+    // a single legal move is not a puzzle
+    if game.status().is_finished() || game.legal_moves().len() < 2 {
+        return None;
+    }
+    let result = engine.search(game, &options.limits);
+    let [best, second] = result.lines.get(..2)? else {
+        return None;
+    };
+    if best.score < options.min_score || second.score > options.max_second_score {
+        return None;
+    }
+    let best_move = best.moves[0];
+    Some(Puzzle {
+        game: game.clone(),
+        best_move,
+        san: game.san(&best_move),
+        score: best.score,
+        second_score: second.score,
+        mate_in: best.mate_in(),
+    })
+}
@@ -0,0 +1,146 @@
+use crate::engine::{Engine, SearchLimits};
+use crate::game::{Color, Game, Move};
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// Scores are capped for the loss of a move, so missing a mate counts as
+/// losing this many centipawns at most.
+const SCORE_CAP: i32 = 2_000;
+
+/// How much worse a played move is than the best one of the engine, by its
+/// loss in centipawns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MoveClass {
+    /// The move of the engine or one just as good.
+    Best,
+    /// Loses less than 50 centipawns.
+    Good,
+    /// Loses less than 100 centipawns.
+    Inaccuracy,
+    /// Loses less than 300 centipawns.
+    Mistake,
+    /// Loses 300 centipawns or more.
+    Blunder,
+}
+
+impl MoveClass {
+    const fn from_loss(loss: i32) -> Self {
+        match loss {
+            ..=0 => MoveClass::Best,
+            1..50 => MoveClass::Good,
+            50..100 => MoveClass::Inaccuracy,
+            100..300 => MoveClass::Mistake,
+            _ => MoveClass::Blunder,
+        }
+    }
+}
+
+/// A played move of [`review`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReviewedMove {
+    pub played: Move,
+    /// The played move in standard algebraic notation.
+    pub san: String,
+    /// The move the engine would have played.
+    pub best_move: Option<Move>,
+    /// Centipawns before the move from the view of the side that played it.
+    pub score: i32,
+    /// Centipawns the move gives away compared to the best move, at least 0.
+    pub loss: i32,
+    pub class: MoveClass,
+}
+
+/// Outcome of [`review`], one entry for every played move.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Review {
+    pub moves: Vec<ReviewedMove>,
+}
+
+impl Review {
+    /// The moves of `color`.
+    pub fn moves_of(&self, color: Color) -> impl Iterator<Item = &ReviewedMove> {
+        self.moves
+            .iter()
+            .filter(move |reviewed| reviewed.played.piece.color == color)
+    }
+
+    /// Average loss of the moves of `color` in centipawns, 0 if it did not
+    /// move.
+    #[must_use]
+    pub fn average_loss(&self, color: Color) -> i32 {
+        let (sum, count) = self.moves_of(color).fold((0, 0), |(sum, count), reviewed| {
+            (sum + reviewed.loss, count + 1)
+        });
+        if count == 0 {
+            0
+        } else {
+            sum / count
+        }
+    }
+
+    /// Number of moves of `color` in `class`.
+    #[must_use]
+    pub fn count(&self, color: Color, class: MoveClass) -> usize {
+        self.moves_of(color)
+            .filter(|reviewed| reviewed.class == class)
+            .count()
+    }
+}
+
+/// Searches every position of the game within `limits` and classifies the
+/// played moves by how much worse they are than the best move of the engine,
+/// e.g. to show a report after the game.
+///
+/// ```
+/// use rusty_chess_core::analysis::{review, MoveClass};
+/// use rusty_chess_core::engine::SearchLimits;
+/// use rusty_chess_core::game::{Color, Game};
+///
+/// let game = Game::from_pgn("1. e4 e5 2. Qh5 Ke7 3. Qxe5#").unwrap();
+/// let review = review(&game, &SearchLimits::depth(3));
+/// assert_eq!(review.moves.len(), 5);
+/// assert_eq!(review.moves[3].class, MoveClass::Blunder);
+/// assert_eq!(review.moves[4].class, MoveClass::Best);
+/// assert!(review.average_loss(Color::Black) > review.average_loss(Color::White));
+/// ```
+#[must_use]
+pub fn review(game: &Game, limits: &SearchLimits) -> Review {
+    let engine = Engine::new();
+    let played: Vec<(Move, String)> = game
+        .history()
+        .iter()
+        .map(|played| (played.mv, played.san.clone()))
+        .collect();
+    // the positions are searched with the moves before them, so that
+    // repetitions are seen
+    let mut position = game.clone();
+    position.goto_ply(0);
+    let mut before = engine.search(&position, limits);
+    let mut moves = Vec::with_capacity(played.len());
+    for (mv, san) in played {
+        if position.redo().is_none() {
+            break;
+        }
+        let after = engine.search(&position, limits);
+        let same_move = before.best_move.is_some_and(|best| {
+            best.from == mv.from && best.to == mv.to && best.promotion == mv.promotion
+        });
+        // the score after the move is from the view of the other side
+        let loss = if same_move {
+            0
+        } else {
+            (before.score.clamp(-SCORE_CAP, SCORE_CAP) + after.score.clamp(-SCORE_CAP, SCORE_CAP))
+                .max(0)
+        };
+        moves.push(ReviewedMove {
+            played: mv,
+            san,
+            best_move: before.best_move,
+            score: before.score,
+            loss,
+            class: MoveClass::from_loss(loss),
+        });
+        before = after;
+    }
+    Review { moves }
+}
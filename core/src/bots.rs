@@ -0,0 +1,77 @@
+//! Simple bots of the frontends, which play without searching. They take any
+//! random number generator, use [`BotRng`](crate::game::BotRng) with a fixed
+//! seed to make them play the same moves again.
+//!
+//! ```
+//! use rusty_chess_core::bots;
+//! use rusty_chess_core::game::{BotRng, Game, SeedableRng};
+//!
+//! let mut game = Game::new();
+//! let mut rng = BotRng::seed_from_u64(42);
+//! let status = bots::play_randomly_aggressive(&mut game, &mut rng);
+//! assert!(status.is_some());
+//! assert_eq!(game.history().len(), 1);
+//! ```
+
+use crate::game::{Game, GameStatus, Move, PieceType, Rng, UserInput};
+
+/// Mates if it can, otherwise gives check, captures or plays a random move, in
+/// this order. `None` if there is no legal move.
+#[must_use]
+pub fn attacking_king_move<R: Rng + ?Sized>(game: &Game, rng: &mut R) -> Option<Move> {
+    let moves = game.legal_moves();
+    let snapshot = game.snapshot();
+    moves
+        .iter()
+        .find(|mv| {
+            let mut game = snapshot.clone();
+            match game.process_input(&bot_input(**mv)) {
+                Ok(GameStatus::Checkmate(_)) => true,
+                _ => game.check(game.turn),
+            }
+        })
+        .or_else(|| moves.iter().find(|mv| mv.is_capture()))
+        .copied()
+        .or_else(|| game.random_move(rng))
+}
+
+/// Captures whenever it can, otherwise plays a random move. `None` if there is
+/// no legal move.
+#[must_use]
+pub fn randomly_aggressive_move<R: Rng + ?Sized>(game: &Game, rng: &mut R) -> Option<Move> {
+    game.capture_moves(game.turn)
+        .first()
+        .copied()
+        .or_else(|| game.random_move(rng))
+}
+
+/// Plays the move of [`attacking_king_move`], `None` if the game is over.
+pub fn play_attacking_king<R: Rng + ?Sized>(game: &mut Game, rng: &mut R) -> Option<GameStatus> {
+    let mv = attacking_king_move(game, rng)?;
+    Some(play(game, mv))
+}
+
+/// Plays the move of [`randomly_aggressive_move`], `None` if the game is over.
+pub fn play_randomly_aggressive<R: Rng + ?Sized>(
+    game: &mut Game,
+    rng: &mut R,
+) -> Option<GameStatus> {
+    let mv = randomly_aggressive_move(game, rng)?;
+    Some(play(game, mv))
+}
+
+fn play(game: &mut Game, mv: Move) -> GameStatus {
+    match game.process_input(&bot_input(mv)) {
+        Ok(status) => status,
+        Err(e) => unreachable!("Legal move {mv} is not valid: {e}"),
+    }
+}
+
+/// Bots promote to a queen.
+pub(crate) fn bot_input(mv: Move) -> UserInput {
+    if mv.is_promotion() {
+        UserInput::MoveWithPromotion(mv.from, mv.to, PieceType::Queen)
+    } else {
+        UserInput::Move(mv.from, mv.to)
+    }
+}
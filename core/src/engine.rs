@@ -0,0 +1,615 @@
+#[cfg(feature = "nnue")]
+use crate::eval::Network;
+use crate::game::{
+    BotRng, Color, Game, GameStatus, Move, PieceType, SeedableRng, UserInput, Variant,
+};
+#[cfg(feature = "nnue")]
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::cmp::Reverse;
+use core::sync::atomic::AtomicBool;
+
+#[cfg(feature = "std")]
+mod datagen;
+mod evaluator;
+#[cfg(feature = "std")]
+mod handle;
+mod info;
+mod limits;
+mod mate;
+#[cfg(feature = "std")]
+mod selfplay;
+mod skill;
+
+#[cfg(feature = "std")]
+pub use datagen::{generate_data, DataGame, DataGen, ScoredPosition};
+use evaluator::Evaluator;
+#[cfg(feature = "std")]
+pub use handle::EngineHandle;
+pub use info::SearchInfo;
+use limits::Budget;
+pub use limits::{SearchLimits, MAX_DEPTH};
+pub use mate::find_mate;
+#[cfg(feature = "std")]
+pub use selfplay::{self_play, MatchScore, SelfPlay};
+pub use skill::SkillLevel;
+
+/// Score of a checkmate on the board. Mates further away score one less for
+/// every ply, so the engine prefers the fastest mate.
+pub const MATE_SCORE: i32 = 100_000;
+
+/// Scores beyond this are mates, see [`SearchResult::mate_in`].
+const MATE_THRESHOLD: i32 = MATE_SCORE - 1_000;
+
+/// Plies the search after a null move is shallower than after a real move.
+const NULL_MOVE_REDUCTION: u8 = 2;
+
+/// Null moves are only tried if there are enough plies left to gain from the
+/// reduction.
+const NULL_MOVE_MIN_DEPTH: u8 = NULL_MOVE_REDUCTION + 2;
+
+/// Moves that are searched at full depth before late moves are reduced, the
+/// ordering puts the good ones first.
+const LMR_MIN_MOVES: usize = 3;
+
+/// Late moves are reduced by one ply if at least this many are left.
+const LMR_MIN_DEPTH: u8 = 3;
+
+/// Above the score of any position that is not a mate.
+const INFINITY: i32 = MATE_SCORE + 1;
+
+/// Plays chess by searching the tree of legal moves with negamax and
+/// alpha-beta pruning, followed by a search of the captures so that it does
+/// not stop in the middle of an exchange. Positions where even passing the turn
+/// keeps the side to move ahead are searched less deep, see
+/// [`Game::make_null_move`], and so are quiet moves late in the move order
+/// unless they turn out to be better than expected. It searches one ply deeper
+/// at a time until the [`SearchLimits`] are reached, trying the best move of
+/// the previous depth first.
+///
+/// ```
+/// use rusty_chess_core::engine::{Engine, SearchLimits};
+/// use rusty_chess_core::game::Game;
+///
+/// let game = Game::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1").unwrap();
+/// let result = Engine::new().search(&game, &SearchLimits::depth(2));
+/// assert_eq!(result.best_move.unwrap().to_uci(), "a1a8");
+/// assert_eq!(result.mate_in(), Some(1));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Engine {
+    multi_pv: usize,
+    skill: SkillLevel,
+    seed: u64,
+    contempt: i32,
+    #[cfg(feature = "nnue")]
+    network: Option<Arc<Network>>,
+}
+
+/// Outcome of [`Engine::search`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchResult {
+    /// `None` if the game is over. Promotions have their piece set.
+    pub best_move: Option<Move>,
+    /// Centipawns from the view of the side to move, positive if it is better.
+    pub score: i32,
+    /// Deepest completely searched depth in plies the move and score are from.
+    pub depth: u8,
+    /// Positions visited.
+    pub nodes: u64,
+    /// The best lines, as many as [`Engine::multi_pv`] asks for if there are
+    /// enough legal moves, best first. The first line starts with `best_move`
+    /// unless a weaker [`SkillLevel`] picked another one.
+    pub lines: Vec<PvLine>,
+}
+
+/// A principal variation, the moves both sides are expected to play.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PvLine {
+    /// Centipawns from the view of the side to move at the start of the line.
+    pub score: i32,
+    /// Starts with a move of the side to move, at least one move long.
+    pub moves: Vec<Move>,
+}
+
+impl SearchResult {
+    /// Moves until the side to move mates, negative if it gets mated. `None`
+    /// if the search found no mate.
+    #[must_use]
+    pub const fn mate_in(&self) -> Option<i32> {
+        mate_in(self.score)
+    }
+}
+
+impl PvLine {
+    /// Like [`SearchResult::mate_in`] for the score of the line.
+    #[must_use]
+    pub const fn mate_in(&self) -> Option<i32> {
+        mate_in(self.score)
+    }
+}
+
+const fn mate_in(score: i32) -> Option<i32> {
+    if score > MATE_THRESHOLD {
+        Some((MATE_SCORE - score + 1) / 2)
+    } else if score < -MATE_THRESHOLD {
+        Some(-(MATE_SCORE + score) / 2)
+    } else {
+        None
+    }
+}
+
+impl Default for Engine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Engine {
+    #[must_use]
+    pub const fn new() -> Self {
+        Engine {
+            multi_pv: 1,
+            skill: SkillLevel::MAX,
+            seed: 0,
+            contempt: 0,
+            #[cfg(feature = "nnue")]
+            network: None,
+        }
+    }
+
+    /// Number of best lines to find, at least 1. More lines make the search
+    /// slower as each of them has to be searched exactly.
+    #[must_use]
+    pub const fn multi_pv(mut self, lines: usize) -> Self {
+        self.multi_pv = if lines == 0 { 1 } else { lines };
+        self
+    }
+
+    /// Plays weaker than [`SkillLevel::MAX`], the default.
+    #[must_use]
+    pub const fn skill_level(mut self, skill: SkillLevel) -> Self {
+        self.skill = skill;
+        self
+    }
+
+    /// Seed of the randomness of a weaker [`SkillLevel`]. The same seed plays
+    /// the same moves in the same positions.
+    #[must_use]
+    pub const fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Centipawns a draw is worse than an equal position for the side to move
+    /// at the start of the search, 0 by default. A positive contempt avoids
+    /// draws by repetition against weaker opponents, a negative one seeks them
+    /// against stronger ones.
+    #[must_use]
+    pub const fn contempt(mut self, centipawns: i32) -> Self {
+        self.contempt = centipawns;
+        self
+    }
+
+    /// Evaluates positions of standard chess with `network` instead of
+    /// [`eval::evaluate`](crate::eval::evaluate). The accumulators of the
+    /// network are updated with every move of the search.
+    #[cfg(feature = "nnue")]
+    #[must_use]
+    pub fn network(mut self, network: Arc<Network>) -> Self {
+        self.network = Some(network);
+        self
+    }
+
+    /// Best move for the side to move and its score, searched within `limits`.
+    #[must_use]
+    pub fn search(&self, game: &Game, limits: &SearchLimits) -> SearchResult {
+        self.search_with_info(game, limits, |_| {})
+    }
+
+    /// Like [`Engine::search`] but calls `on_info` after every completed depth,
+    /// e.g. to show the progress of a long search.
+    ///
+    /// ```
+    /// use rusty_chess_core::engine::{Engine, SearchLimits};
+    /// use rusty_chess_core::game::Game;
+    ///
+    /// let mut depths = Vec::new();
+    /// Engine::new().search_with_info(&Game::new(), &SearchLimits::depth(3), |info| {
+    ///     depths.push(info.depth);
+    /// });
+    /// assert_eq!(depths, [1, 2, 3]);
+    /// ```
+    pub fn search_with_info(
+        &self,
+        game: &Game,
+        limits: &SearchLimits,
+        on_info: impl FnMut(&SearchInfo),
+    ) -> SearchResult {
+        self.run(game, limits, None, on_info)
+    }
+
+    /// The search, which also ends once `stop` is set.
+    fn run(
+        &self,
+        game: &Game,
+        limits: &SearchLimits,
+        stop: Option<&AtomicBool>,
+        mut on_info: impl FnMut(&SearchInfo),
+    ) -> SearchResult {
+        let root = game.snapshot();
+        let mut search = Search::new(game, limits, stop);
+        search.skill = self.skill;
+        search.seed = self.seed;
+        search.contempt = self.contempt;
+        search.evaluator = Evaluator::new(self, &root);
+        let mut result = SearchResult {
+            best_move: None,
+            score: 0,
+            depth: 0,
+            nodes: 0,
+            lines: Vec::new(),
+        };
+        if let Some(score) = search.final_score(&root, 0) {
+            result.score = score;
+            return result;
+        }
+        let mut root_moves: Vec<(Move, Game)> = children(&root, ordered_moves(&root)).collect();
+        // a weaker skill level needs more lines to pick its move from
+        let line_count = self.multi_pv.max(self.skill.candidate_lines());
+        let max_depth = limits.max_depth().min(self.skill.max_depth());
+        for depth in 1..=max_depth {
+            let mut lines: Vec<PvLine> = Vec::with_capacity(line_count + 1);
+            for (mv, child) in &root_moves {
+                // only scores above the last of the lines are needed exactly
+                let alpha = if lines.len() < line_count {
+                    -INFINITY
+                } else {
+                    lines[line_count - 1].score
+                };
+                let mut pv = Vec::new();
+                search.evaluator.push(&root, child);
+                let score = -search.negamax(child, depth - 1, 1, -INFINITY, -alpha, true, &mut pv);
+                search.evaluator.pop();
+                if search.stopped {
+                    break;
+                }
+                if score > alpha {
+                    pv.insert(0, *mv);
+                    let rank = lines.partition_point(|line| line.score >= score);
+                    lines.insert(rank, PvLine { score, moves: pv });
+                    lines.truncate(line_count);
+                }
+            }
+            if search.stopped {
+                // the moves after the stop were not searched
+                break;
+            }
+            // the best lines go first in the next depth
+            root_moves.sort_by_key(|(mv, _)| {
+                lines
+                    .iter()
+                    .position(|line| line.moves[0] == *mv)
+                    .unwrap_or(lines.len())
+            });
+            result.best_move = Some(lines[0].moves[0]);
+            result.score = lines[0].score;
+            result.depth = depth;
+            result.lines = lines;
+            let time = search.budget.elapsed();
+            on_info(&SearchInfo {
+                depth,
+                score: result.score,
+                nodes: search.nodes,
+                nps: info::nodes_per_second(search.nodes, time),
+                time,
+                pv: &result.lines[0].moves,
+                lines: &result.lines[..self.multi_pv.min(result.lines.len())],
+            });
+            // a deeper search cannot find a faster mate
+            if result.lines.iter().all(|line| line.mate_in().is_some())
+                || !search.budget.may_deepen()
+            {
+                break;
+            }
+            search.may_stop = true;
+        }
+        if !result.lines.is_empty() {
+            let scores: Vec<i32> = result.lines.iter().map(|line| line.score).collect();
+            let mut rng = BotRng::seed_from_u64(game.zobrist_key() ^ self.seed);
+            let picked = &result.lines[self.skill.pick(&scores, &mut rng)];
+            result.best_move = Some(picked.moves[0]);
+            result.score = picked.score;
+            result.lines.truncate(self.multi_pv);
+        }
+        result.nodes = search.nodes;
+        result
+    }
+}
+
+struct Search<'a> {
+    nodes: u64,
+    budget: Budget<'a>,
+    /// Set once the first depth is completed.
+    may_stop: bool,
+    /// The budget ran out, the scores of the unfinished depth are worthless.
+    stopped: bool,
+    /// Zobrist keys of the positions of the game and of the current line, to
+    /// score repetitions as draw.
+    keys: Vec<u64>,
+    skill: SkillLevel,
+    seed: u64,
+    /// See [`Engine::contempt`].
+    contempt: i32,
+    /// The side to move at the root, the one with the contempt.
+    root_turn: Color,
+    evaluator: Evaluator,
+}
+
+impl<'a> Search<'a> {
+    fn new(game: &Game, limits: &SearchLimits, stop: Option<&'a AtomicBool>) -> Self {
+        let mut keys: Vec<u64> = game.history().iter().map(|played| played.key).collect();
+        keys.push(game.zobrist_key());
+        Search {
+            nodes: 0,
+            budget: Budget::new(limits, game.turn, stop),
+            may_stop: false,
+            stopped: false,
+            keys,
+            skill: SkillLevel::MAX,
+            seed: 0,
+            contempt: 0,
+            root_turn: game.turn,
+            evaluator: Evaluator::new(&Engine::new(), game),
+        }
+    }
+
+    /// Counts the position and checks whether the search has to stop.
+    fn visit(&mut self) -> bool {
+        self.nodes += 1;
+        if self.may_stop && !self.stopped {
+            self.stopped = self.budget.exhausted(self.nodes);
+        }
+        self.stopped
+    }
+
+    /// `null_move` allows to pass the turn to prune this position, it is not
+    /// allowed twice in a row. The best line from this position is written to
+    /// `pv` if its score is between `alpha` and `beta`.
+    #[allow(clippy::too_many_arguments)]
+    fn negamax(
+        &mut self,
+        game: &Game,
+        depth: u8,
+        ply: i32,
+        mut alpha: i32,
+        beta: i32,
+        null_move: bool,
+        pv: &mut Vec<Move>,
+    ) -> i32 {
+        pv.clear();
+        if self.visit() {
+            return 0;
+        }
+        if let Some(score) = self.final_score(game, ply) {
+            return score;
+        }
+        if depth == 0 {
+            return self.quiescence(game, ply, alpha, beta);
+        }
+        let mut child_pv = Vec::new();
+        if null_move
+            && depth >= NULL_MOVE_MIN_DEPTH
+            && null_move_allowed(game, beta)
+            && self.evaluator.evaluate(game) >= beta
+        {
+            let mut child = game.clone();
+            if child.make_null_move().is_ok() {
+                let reduced = depth - 1 - NULL_MOVE_REDUCTION;
+                self.keys.push(game.zobrist_key());
+                let score = -self.negamax(
+                    &child,
+                    reduced,
+                    ply + 1,
+                    -beta,
+                    1 - beta,
+                    false,
+                    &mut child_pv,
+                );
+                self.keys.pop();
+                // if even passing keeps the score above beta, a real move will
+                // too unless the side is in zugzwang, which a reduced search of
+                // the real moves rules out
+                if score >= beta
+                    && !self.stopped
+                    && self.negamax(game, reduced, ply, beta - 1, beta, false, &mut child_pv)
+                        >= beta
+                {
+                    return beta;
+                }
+                if self.stopped {
+                    return 0;
+                }
+            }
+        }
+        self.keys.push(game.zobrist_key());
+        let in_check = game.is_check();
+        for (i, (mv, child)) in children(game, ordered_moves(game)).enumerate() {
+            let reduce = depth >= LMR_MIN_DEPTH
+                && i >= LMR_MIN_MOVES
+                && !in_check
+                && !mv.is_capture()
+                && mv.promotion.is_none()
+                && !child.is_check();
+            self.evaluator.push(game, &child);
+            // late quiet moves are rarely good, a shallower search with a null
+            // window proves it and only the surprises are searched again
+            let mut score = if reduce {
+                -self.negamax(
+                    &child,
+                    depth - 2,
+                    ply + 1,
+                    -alpha - 1,
+                    -alpha,
+                    true,
+                    &mut child_pv,
+                )
+            } else {
+                INFINITY
+            };
+            if score > alpha {
+                score = -self.negamax(
+                    &child,
+                    depth - 1,
+                    ply + 1,
+                    -beta,
+                    -alpha,
+                    true,
+                    &mut child_pv,
+                );
+            }
+            self.evaluator.pop();
+            if self.stopped {
+                break;
+            }
+            if score >= beta {
+                alpha = beta;
+                break;
+            }
+            if score > alpha {
+                alpha = score;
+                pv.clear();
+                pv.push(mv);
+                pv.extend_from_slice(&child_pv);
+            }
+        }
+        self.keys.pop();
+        alpha
+    }
+
+    /// Only plays captures and promotions until the position is quiet. The
+    /// side to move can always stand pat instead.
+    fn quiescence(&mut self, game: &Game, ply: i32, mut alpha: i32, beta: i32) -> i32 {
+        let stand_pat =
+            self.evaluator.evaluate(game) + self.skill.noise(game.zobrist_key(), self.seed);
+        if stand_pat >= beta {
+            return beta;
+        }
+        alpha = alpha.max(stand_pat);
+        let mut captures = game.capture_moves(game.turn);
+        sort_by_value(&mut captures);
+        for (_, child) in children(game, captures) {
+            if self.visit() {
+                return 0;
+            }
+            if let Some(score) = self.final_score(&child, ply + 1) {
+                alpha = alpha.max(-score);
+                continue;
+            }
+            self.evaluator.push(game, &child);
+            let score = -self.quiescence(&child, ply + 1, -beta, -alpha);
+            self.evaluator.pop();
+            if score >= beta {
+                return beta;
+            }
+            alpha = alpha.max(score);
+        }
+        alpha
+    }
+
+    /// Score of a finished game, or of a claimable draw or a repetition of an
+    /// earlier position below the root, `None` if the game goes on.
+    fn final_score(&self, game: &Game, ply: i32) -> Option<i32> {
+        match game.status() {
+            GameStatus::Checkmate(_) => Some(ply - MATE_SCORE),
+            GameStatus::Resignation(winner)
+            | GameStatus::Timeout(winner)
+            | GameStatus::VariantWin(winner) => Some(if winner == game.turn {
+                MATE_SCORE - ply
+            } else {
+                ply - MATE_SCORE
+            }),
+            GameStatus::Stalemate | GameStatus::Draw(_) => Some(self.draw_score(game)),
+            GameStatus::Ongoing | GameStatus::Check | GameStatus::AwaitingPromotion(_) => {
+                // at the root the side to move may still play on instead of claiming
+                let drawn = ply > 0
+                    && (self.keys.contains(&game.zobrist_key()) || game.claimable_draw().is_some());
+                drawn.then(|| self.draw_score(game))
+            }
+        }
+    }
+
+    /// A draw from the view of the side to move, the contempt of the root
+    /// side is an advantage for the other one.
+    fn draw_score(&self, game: &Game) -> i32 {
+        if game.turn == self.root_turn {
+            -self.contempt
+        } else {
+            self.contempt
+        }
+    }
+}
+
+/// Passing the turn is worth a try if the side to move is already above
+/// `beta`, which the caller checks with its evaluation. Endgames with only
+/// pawns are full of zugzwang and antichess is about being forced to move, so
+/// there are no null moves there.
+fn null_move_allowed(game: &Game, beta: i32) -> bool {
+    game.variant() == Variant::Standard
+        && beta.abs() < MATE_THRESHOLD
+        && game
+            .pieces_of(game.turn)
+            .any(|(_, piece)| !matches!(piece.piece_type, PieceType::Pawn | PieceType::King))
+}
+
+/// Legal moves with the most valuable captures by the least valuable pieces
+/// and promotions first.
+fn ordered_moves(game: &Game) -> Vec<Move> {
+    let mut moves = game.legal_moves().to_vec();
+    sort_by_value(&mut moves);
+    moves
+}
+
+fn sort_by_value(moves: &mut [Move]) {
+    moves.sort_by_key(|mv| {
+        let victim = mv
+            .captured_piece
+            .map_or(0, |piece| piece.piece_type.value());
+        let promotion = if mv.is_promotion() { 8 } else { 0 };
+        Reverse((victim + promotion, Reverse(mv.piece.piece_type.value())))
+    });
+}
+
+/// The moves played on a copy of `game`, promotions once for every piece the
+/// pawn can promote to.
+fn children(game: &Game, moves: Vec<Move>) -> impl Iterator<Item = (Move, Game)> + '_ {
+    moves.into_iter().flat_map(move |mv| {
+        let promotions: &[_] = if mv.is_promotion() {
+            game.promotion_piece_types()
+        } else {
+            &[]
+        };
+        let inputs: Vec<(Move, UserInput)> = if promotions.is_empty() {
+            alloc::vec![(mv, UserInput::Move(mv.from, mv.to))]
+        } else {
+            promotions
+                .iter()
+                .map(|piece_type| {
+                    let mut promotion = mv;
+                    promotion.promotion = Some(*piece_type);
+                    (
+                        promotion,
+                        UserInput::MoveWithPromotion(mv.from, mv.to, *piece_type),
+                    )
+                })
+                .collect()
+        };
+        inputs.into_iter().map(move |(mv, input)| {
+            let mut child = game.clone();
+            if let Err(e) = child.process_input(&input) {
+                unreachable!("Generated move {mv} is not valid: {e}");
+            }
+            (mv, child)
+        })
+    })
+}
@@ -0,0 +1,144 @@
+use super::selfplay::random_opening;
+use super::{Engine, SearchLimits};
+use crate::bots;
+use crate::game::{BotRng, Color, Game, GameResult, Rng, SeedableRng, UserInput};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// How [`generate_data`] plays its games.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DataGen {
+    pub games: u32,
+    /// Limits of every move, e.g. a node budget for the same quality on every
+    /// machine.
+    pub limits: SearchLimits,
+    /// Random moves at the start of every game.
+    pub opening_plies: u8,
+    /// Chance from 0 to 1 that a move of the engine is replaced by a random
+    /// one, so that the games reach positions the engine would avoid.
+    pub noise: f64,
+    /// Games still going on after this many plies are adjudicated as draw.
+    pub max_plies: usize,
+    /// Seed of the openings and the noise.
+    pub seed: u64,
+}
+
+impl DataGen {
+    /// Eight random plies at the start and no noise after them.
+    #[must_use]
+    pub const fn new(games: u32, limits: SearchLimits) -> Self {
+        DataGen {
+            games,
+            limits,
+            opening_plies: 8,
+            noise: 0.0,
+            max_plies: 400,
+            seed: 0,
+        }
+    }
+}
+
+/// A position of a [`DataGame`] before a move of the engine.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScoredPosition {
+    pub fen: String,
+    /// Centipawns of the search from the view of White.
+    pub score: i32,
+}
+
+/// A finished game of [`generate_data`].
+#[derive(Debug, Clone)]
+pub struct DataGame {
+    pub game: Game,
+    /// Every position the engine searched, in the order of the game.
+    pub positions: Vec<ScoredPosition>,
+    pub result: GameResult,
+}
+
+impl DataGame {
+    /// One `fen | score | result` line per position, the score in centipawns
+    /// and the result as 1.0, 0.5 or 0.0 for White, as read by most trainers
+    /// of evaluation networks.
+    #[must_use]
+    pub fn to_records(&self) -> String {
+        let result = match self.result {
+            GameResult::WhiteWins => "1.0",
+            GameResult::Draw => "0.5",
+            GameResult::BlackWins => "0.0",
+        };
+        self.positions
+            .iter()
+            .map(|position| format!("{} | {} | {result}\n", position.fen, position.score))
+            .collect()
+    }
+}
+
+/// Plays `engine` against itself and scores every position with its search,
+/// e.g. to train an evaluation network on the games. `on_game` gets every game
+/// when it is finished, so that it can be written out before the next one.
+///
+/// ```
+/// use rusty_chess_core::engine::{generate_data, DataGen, Engine, SearchLimits};
+///
+/// let options = DataGen {
+///     noise: 0.1,
+///     max_plies: 20,
+///     ..DataGen::new(2, SearchLimits::depth(1))
+/// };
+/// let mut records = String::new();
+/// generate_data(&Engine::new(), &options, |played| {
+///     records.push_str(&played.to_records());
+/// });
+/// assert!(records.lines().count() >= 2);
+/// println!("{records}");
+/// ```
+pub fn generate_data(engine: &Engine, options: &DataGen, mut on_game: impl FnMut(&DataGame)) {
+    let mut rng = BotRng::seed_from_u64(options.seed);
+    for _ in 0..options.games {
+        let mut game = random_opening(options.opening_plies, &mut rng);
+        let mut positions = Vec::new();
+        while !game.status().is_finished() {
+            if game.claimable_draw().is_some() {
+                process_valid(&mut game, &UserInput::ClaimDraw);
+                break;
+            }
+            if game.history().len() >= options.max_plies {
+                // the side to move offers a draw, the other side accepts on its turn
+                if game.draw_offer() == Some(game.turn.invert()) {
+                    process_valid(&mut game, &UserInput::AcceptDraw);
+                    break;
+                }
+                process_valid(&mut game, &UserInput::Draw);
+            }
+            let result = engine.search(&game, &options.limits);
+            let Some(best_move) = result.best_move else {
+                break;
+            };
+            positions.push(ScoredPosition {
+                fen: game.to_fen(),
+                score: match game.turn {
+                    Color::White => result.score,
+                    Color::Black => -result.score,
+                },
+            });
+            let mv = if rng.gen_bool(options.noise.clamp(0.0, 1.0)) {
+                game.random_move(&mut rng).unwrap_or(best_move)
+            } else {
+                best_move
+            };
+            process_valid(&mut game, &bots::bot_input(mv));
+        }
+        on_game(&DataGame {
+            result: game.result().unwrap_or(GameResult::Draw),
+            game,
+            positions,
+        });
+    }
+}
+
+fn process_valid(game: &mut Game, input: &UserInput) {
+    if let Err(e) = game.process_input(input) {
+        unreachable!("{input:?} is not valid: {e}");
+    }
+}
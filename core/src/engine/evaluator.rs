@@ -0,0 +1,106 @@
+use super::Engine;
+use crate::eval;
+use crate::game::{Color, Game};
+#[cfg(feature = "nnue")]
+use crate::{
+    eval::{Accumulator, Network},
+    game::Variant,
+};
+#[cfg(feature = "nnue")]
+use alloc::{sync::Arc, vec::Vec};
+
+/// The evaluation of the search. With a [`Network`] it keeps the accumulator
+/// of every position of the current line, [`Evaluator::push`] and
+/// [`Evaluator::pop`] have to follow the search into and out of a child.
+#[cfg(feature = "nnue")]
+pub(super) struct Evaluator {
+    /// Only for standard chess, the network knows nothing about variants.
+    network: Option<Arc<Network>>,
+    /// Reused between the lines, the accumulator of the current position is
+    /// the one at `depth`.
+    accumulators: Vec<Accumulator>,
+    depth: usize,
+}
+
+#[cfg(not(feature = "nnue"))]
+pub(super) struct Evaluator;
+
+#[cfg(feature = "nnue")]
+impl Evaluator {
+    pub(super) fn new(engine: &Engine, root: &Game) -> Self {
+        let network = engine
+            .network
+            .clone()
+            .filter(|_| root.variant() == Variant::Standard);
+        let accumulators = network
+            .iter()
+            .map(|network| network.accumulator(root))
+            .collect();
+        Evaluator {
+            network,
+            accumulators,
+            depth: 0,
+        }
+    }
+
+    /// The search moves from `parent`, the current position, to `child`.
+    pub(super) fn push(&mut self, parent: &Game, child: &Game) {
+        let Some(network) = &self.network else {
+            return;
+        };
+        let depth = self.depth + 1;
+        if depth == self.accumulators.len() {
+            self.accumulators.push(self.accumulators[depth - 1].clone());
+        } else {
+            let (lower, upper) = self.accumulators.split_at_mut(depth);
+            upper[0].clone_from(&lower[depth - 1]);
+        }
+        network.update(&mut self.accumulators[depth], parent, child);
+        self.depth = depth;
+    }
+
+    /// The search goes back to the parent of the current position.
+    pub(super) fn pop(&mut self) {
+        if self.network.is_some() {
+            self.depth -= 1;
+        }
+    }
+
+    /// Centipawns of the current position from the view of the side to move.
+    pub(super) fn evaluate(&self, game: &Game) -> i32 {
+        let score = match &self.network {
+            Some(network) => {
+                network.evaluate_accumulator(&self.accumulators[self.depth], game.turn)
+            }
+            None => eval::evaluate(game),
+        };
+        side_to_move(game, score)
+    }
+}
+
+#[cfg(not(feature = "nnue"))]
+impl Evaluator {
+    pub(super) const fn new(_engine: &Engine, _root: &Game) -> Self {
+        Evaluator
+    }
+
+    /// The search moves from `parent`, the current position, to `child`.
+    pub(super) const fn push(&mut self, _parent: &Game, _child: &Game) {}
+
+    /// The search goes back to the parent of the current position.
+    pub(super) const fn pop(&mut self) {}
+
+    /// Centipawns of the position from the view of the side to move.
+    pub(super) fn evaluate(&self, game: &Game) -> i32 {
+        side_to_move(game, eval::evaluate(game))
+    }
+}
+
+/// `score` from the view of White turned to the view of the side to move.
+fn side_to_move(game: &Game, score: i32) -> i32 {
+    if game.turn == Color::White {
+        score
+    } else {
+        -score
+    }
+}
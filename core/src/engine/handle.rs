@@ -0,0 +1,98 @@
+use super::{Engine, SearchInfo, SearchLimits, SearchResult};
+use crate::game::Game;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+
+/// A search running on its own thread, e.g. so that a user interface keeps
+/// drawing while the engine thinks. Dropping the handle stops the search.
+///
+/// ```
+/// use rusty_chess_core::engine::{EngineHandle, SearchLimits};
+/// use rusty_chess_core::game::Game;
+///
+/// let handle = EngineHandle::start_search(&Game::new(), SearchLimits::default());
+/// // ... later, when the move is needed
+/// let result = handle.stop();
+/// assert!(result.best_move.is_some());
+/// ```
+#[derive(Debug)]
+pub struct EngineHandle {
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<SearchResult>>,
+}
+
+impl EngineHandle {
+    /// Starts a search of [`Engine::new`], see [`Engine::start_search`].
+    #[must_use]
+    pub fn start_search(game: &Game, limits: SearchLimits) -> EngineHandle {
+        Engine::new().start_search(game, limits)
+    }
+
+    /// Sets the stop token and waits for the best move found so far. The
+    /// search completes the first depth in any case.
+    #[must_use]
+    pub fn stop(mut self) -> SearchResult {
+        self.stop.store(true, Ordering::Relaxed);
+        self.join()
+    }
+
+    /// Waits until the search reaches its limits. Never returns for a search
+    /// without limits, use [`EngineHandle::stop`] then.
+    #[must_use]
+    pub fn wait(mut self) -> SearchResult {
+        self.join()
+    }
+
+    /// The search reached its limits, so [`EngineHandle::wait`] returns
+    /// without waiting.
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.thread.as_ref().is_none_or(JoinHandle::is_finished)
+    }
+
+    fn join(&mut self) -> SearchResult {
+        let thread = self.thread.take().expect("The search is only joined once.");
+        match thread.join() {
+            Ok(result) => result,
+            Err(panic) => std::panic::resume_unwind(panic),
+        }
+    }
+}
+
+impl Drop for EngineHandle {
+    fn drop(&mut self) {
+        // the thread finishes on its own, nobody waits for its result anymore
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+impl Engine {
+    /// Like [`Engine::search`] but on a new thread, the returned handle stops
+    /// the search or waits for it.
+    #[must_use]
+    pub fn start_search(&self, game: &Game, limits: SearchLimits) -> EngineHandle {
+        self.start_search_with_info(game, limits, |_| {})
+    }
+
+    /// Like [`Engine::search_with_info`] but on a new thread, `on_info` is
+    /// called on the thread of the search, e.g. to print the progress while
+    /// waiting for input.
+    #[must_use]
+    pub fn start_search_with_info(
+        &self,
+        game: &Game,
+        limits: SearchLimits,
+        on_info: impl FnMut(&SearchInfo) + Send + 'static,
+    ) -> EngineHandle {
+        let stop = Arc::new(AtomicBool::new(false));
+        let engine = self.clone();
+        let game = game.clone();
+        let token = Arc::clone(&stop);
+        let thread = thread::spawn(move || engine.run(&game, &limits, Some(&token), on_info));
+        EngineHandle {
+            stop,
+            thread: Some(thread),
+        }
+    }
+}
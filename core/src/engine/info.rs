@@ -0,0 +1,33 @@
+use super::PvLine;
+use crate::game::Move;
+use core::time::Duration;
+
+/// Progress of a running search, reported after every completed depth by
+/// [`Engine::search_with_info`](super::Engine::search_with_info).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchInfo<'a> {
+    /// Depth in plies that was just completed.
+    pub depth: u8,
+    /// Centipawns from the view of the side to move, see
+    /// [`SearchResult::score`](super::SearchResult::score).
+    pub score: i32,
+    /// Positions visited so far.
+    pub nodes: u64,
+    /// Positions visited per second. Zero without the `std` feature as there
+    /// is no clock.
+    pub nps: u64,
+    /// Time since the search started, zero without the `std` feature.
+    pub time: Duration,
+    /// Principal variation of the best line.
+    pub pv: &'a [Move],
+    /// All lines, see [`Engine::multi_pv`](super::Engine::multi_pv).
+    pub lines: &'a [PvLine],
+}
+
+pub(super) fn nodes_per_second(nodes: u64, time: Duration) -> u64 {
+    let nanos = time.as_nanos();
+    if nanos == 0 {
+        return 0;
+    }
+    u64::try_from(u128::from(nodes) * 1_000_000_000 / nanos).unwrap_or(u64::MAX)
+}
@@ -0,0 +1,206 @@
+use crate::game::Color;
+use core::sync::atomic::{AtomicBool, Ordering};
+use core::time::Duration;
+
+/// Deepest search in plies, also when no depth is given.
+pub const MAX_DEPTH: u8 = 64;
+
+/// Moves the time left on the clock is split into if the number of moves to
+/// the next time control is not known.
+const DEFAULT_MOVES_TO_GO: u32 = 30;
+
+/// How often the clock is read, in visited positions.
+#[cfg(feature = "std")]
+const CLOCK_INTERVAL: u64 = 1024;
+
+/// When [`Engine::search`](super::Engine::search) stops. The search goes one
+/// ply deeper at a time until a limit is reached and returns the best move of
+/// the deepest depth it completed. The first ply is always searched completely,
+/// so there is a move even with a tiny budget.
+///
+/// ```
+/// use core::time::Duration;
+/// use rusty_chess_core::engine::SearchLimits;
+///
+/// let limits = SearchLimits {
+///     depth: Some(6),
+///     ..SearchLimits::movetime(Duration::from_secs(1))
+/// };
+///
+/// // a game with 5 minutes and 3 seconds increment
+/// let limits = SearchLimits {
+///     wtime: Some(Duration::from_secs(300)),
+///     btime: Some(Duration::from_secs(300)),
+///     winc: Some(Duration::from_secs(3)),
+///     binc: Some(Duration::from_secs(3)),
+///     ..SearchLimits::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SearchLimits {
+    /// Plies, at most [`MAX_DEPTH`].
+    pub depth: Option<u8>,
+    /// Positions visited.
+    pub nodes: Option<u64>,
+    /// Time to think. Needs the `std` feature to measure it, without it the
+    /// limit is ignored.
+    pub movetime: Option<Duration>,
+    /// Time left on the clock of White, the search takes a part of the clock
+    /// of the side to move, see [`SearchLimits::time_budget`].
+    pub wtime: Option<Duration>,
+    pub btime: Option<Duration>,
+    /// Time added to the clock of White after every move.
+    pub winc: Option<Duration>,
+    pub binc: Option<Duration>,
+    /// Moves until the next time control, if there is one.
+    pub moves_to_go: Option<u32>,
+}
+
+impl SearchLimits {
+    const UNLIMITED: SearchLimits = SearchLimits {
+        depth: None,
+        nodes: None,
+        movetime: None,
+        wtime: None,
+        btime: None,
+        winc: None,
+        binc: None,
+        moves_to_go: None,
+    };
+
+    #[must_use]
+    pub const fn depth(depth: u8) -> Self {
+        SearchLimits {
+            depth: Some(depth),
+            ..Self::UNLIMITED
+        }
+    }
+
+    #[must_use]
+    pub const fn nodes(nodes: u64) -> Self {
+        SearchLimits {
+            nodes: Some(nodes),
+            ..Self::UNLIMITED
+        }
+    }
+
+    #[must_use]
+    pub const fn movetime(movetime: Duration) -> Self {
+        SearchLimits {
+            movetime: Some(movetime),
+            ..Self::UNLIMITED
+        }
+    }
+
+    /// Time to think for the side to move: the `movetime`, or a share of its
+    /// clock with half of the increment, but never more than half of the
+    /// clock. The smaller one if both are given.
+    ///
+    /// ```
+    /// use core::time::Duration;
+    /// use rusty_chess_core::engine::SearchLimits;
+    /// use rusty_chess_core::game::Color;
+    ///
+    /// let limits = SearchLimits {
+    ///     btime: Some(Duration::from_secs(60)),
+    ///     binc: Some(Duration::from_secs(1)),
+    ///     moves_to_go: Some(20),
+    ///     ..SearchLimits::default()
+    /// };
+    /// assert_eq!(limits.time_budget(Color::Black), Some(Duration::from_millis(3500)));
+    /// assert_eq!(limits.time_budget(Color::White), None);
+    /// ```
+    #[must_use]
+    pub fn time_budget(&self, turn: Color) -> Option<Duration> {
+        let (time, increment) = match turn {
+            Color::White => (self.wtime, self.winc),
+            Color::Black => (self.btime, self.binc),
+        };
+        let clock = time.map(|time| {
+            let moves_to_go = self.moves_to_go.unwrap_or(DEFAULT_MOVES_TO_GO).max(1);
+            (time / moves_to_go + increment.unwrap_or_default() / 2).min(time / 2)
+        });
+        match (self.movetime, clock) {
+            (Some(movetime), Some(clock)) => Some(movetime.min(clock)),
+            (movetime, clock) => movetime.or(clock),
+        }
+    }
+
+    pub(super) fn max_depth(&self) -> u8 {
+        self.depth
+            .map_or(MAX_DEPTH, |depth| depth.clamp(1, MAX_DEPTH))
+    }
+}
+
+/// Tells the running search when its node or time budget is used up or it was
+/// stopped from outside.
+pub(super) struct Budget<'a> {
+    nodes: Option<u64>,
+    stop: Option<&'a AtomicBool>,
+    #[cfg(feature = "std")]
+    start: std::time::Instant,
+    #[cfg(feature = "std")]
+    deadline: Option<std::time::Instant>,
+    /// A new depth is not started after this, it would hardly finish in
+    /// time. Only for budgets from the clock, a `movetime` is used up.
+    #[cfg(feature = "std")]
+    soft_deadline: Option<std::time::Instant>,
+}
+
+impl<'a> Budget<'a> {
+    pub(super) fn new(limits: &SearchLimits, turn: Color, stop: Option<&'a AtomicBool>) -> Self {
+        #[cfg(feature = "std")]
+        let start = std::time::Instant::now();
+        #[cfg(feature = "std")]
+        let budget = limits.time_budget(turn);
+        #[cfg(not(feature = "std"))]
+        let _ = turn;
+        Budget {
+            nodes: limits.nodes,
+            stop,
+            #[cfg(feature = "std")]
+            start,
+            #[cfg(feature = "std")]
+            deadline: budget.map(|budget| start + budget),
+            #[cfg(feature = "std")]
+            soft_deadline: budget
+                .filter(|budget| limits.movetime != Some(*budget))
+                .map(|budget| start + budget / 2),
+        }
+    }
+
+    /// Time since the search started, always zero without the `std` feature.
+    pub(super) fn elapsed(&self) -> Duration {
+        #[cfg(feature = "std")]
+        return self.start.elapsed();
+        #[cfg(not(feature = "std"))]
+        Duration::ZERO
+    }
+
+    /// Whether there is enough time left to search one ply deeper.
+    pub(super) fn may_deepen(&self) -> bool {
+        #[cfg(feature = "std")]
+        return self
+            .soft_deadline
+            .is_none_or(|deadline| std::time::Instant::now() < deadline);
+        #[cfg(not(feature = "std"))]
+        true
+    }
+
+    pub(super) fn exhausted(&self, nodes: u64) -> bool {
+        if self.nodes.is_some_and(|limit| nodes >= limit)
+            || self.stop.is_some_and(|stop| stop.load(Ordering::Relaxed))
+        {
+            return true;
+        }
+        #[cfg(feature = "std")]
+        if nodes.is_multiple_of(CLOCK_INTERVAL)
+            && self
+                .deadline
+                .is_some_and(|deadline| std::time::Instant::now() >= deadline)
+        {
+            return true;
+        }
+        false
+    }
+}
@@ -0,0 +1,81 @@
+use super::{children, ordered_moves};
+use crate::game::{Game, GameStatus, Move};
+use alloc::vec::Vec;
+
+/// Shortest forced mate for the side to move within `max_plies`, with the
+/// longest defence of the other side. The line alternates between the moves
+/// of both sides and ends with the mating move. Unlike [`Engine::search`]
+/// this does not evaluate positions, it proves the mate against every
+/// defence, e.g. to check puzzles.
+///
+/// ```
+/// use rusty_chess_core::engine::find_mate;
+/// use rusty_chess_core::game::Game;
+///
+/// let game = Game::from_fen("kbK5/pp6/1P6/8/8/8/8/R7 w - - 0 1").unwrap();
+/// let line = find_mate(&game, 3).unwrap();
+/// assert_eq!(line.len(), 3);
+/// assert_eq!(line[0].to_uci(), "a1a6");
+/// assert_eq!(find_mate(&game, 2), None);
+/// ```
+///
+/// [`Engine::search`]: super::Engine::search
+#[must_use]
+pub fn find_mate(game: &Game, max_plies: u8) -> Option<Vec<Move>> {
+    // without the clock of the game, which would run in every line
+    mate_within(&game.snapshot(), max_plies)
+}
+
+fn mate_within(game: &Game, max_plies: u8) -> Option<Vec<Move>> {
+    // a mate of the side to move always takes an odd number of plies
+    (1..=max_plies)
+        .step_by(2)
+        .find_map(|plies| attack(game, plies))
+}
+
+/// A move of the side to move that mates within `plies`.
+fn attack(game: &Game, plies: u8) -> Option<Vec<Move>> {
+    if game.status().is_finished() {
+        return None;
+    }
+    let mut moves: Vec<(Move, Game)> = children(game, ordered_moves(game)).collect();
+    // checks are the most forcing moves
+    moves.sort_by_key(|(_, child)| !child.is_check());
+    for (mv, child) in moves {
+        if child.is_check() && matches!(child.status(), GameStatus::Checkmate(_)) {
+            return Some(alloc::vec![mv]);
+        }
+        if plies < 3 {
+            if child.is_check() {
+                continue;
+            }
+            // only the checks that came first can mate in one
+            break;
+        }
+        if let Some(mut line) = defend(&child, plies - 1) {
+            line.insert(0, mv);
+            return Some(line);
+        }
+    }
+    None
+}
+
+/// Every move of the side to move gets mated within `plies`, the line of the
+/// reply that delays it the longest.
+fn defend(game: &Game, plies: u8) -> Option<Vec<Move>> {
+    if game.status().is_finished() {
+        return None;
+    }
+    let mut longest: Option<Vec<Move>> = None;
+    for (mv, child) in children(game, ordered_moves(game)) {
+        let mut line = mate_within(&child, plies - 1)?;
+        if longest
+            .as_ref()
+            .is_none_or(|longest| line.len() + 1 > longest.len())
+        {
+            line.insert(0, mv);
+            longest = Some(line);
+        }
+    }
+    longest
+}
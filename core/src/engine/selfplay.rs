@@ -0,0 +1,199 @@
+use super::{Engine, SearchLimits};
+use crate::bots;
+use crate::game::{BotRng, Color, Game, GameResult, Rng, SeedableRng};
+use crate::player::EnginePlayer;
+use crate::tournament::{Sprt, Tournament};
+use core::fmt::{self, Formatter};
+
+/// Width of the error bars of [`MatchScore::elo_error`] in standard
+/// deviations, for a confidence of 95%.
+const CONFIDENCE_Z: f64 = 1.96;
+
+/// How [`self_play`] plays its games.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SelfPlay {
+    pub games: u32,
+    /// Limits of every move of both engines.
+    pub limits: SearchLimits,
+    /// Random moves at the start of every game, so that the games differ.
+    /// Each opening is played twice with swapped colors.
+    pub opening_plies: u8,
+    /// Games still going on after this many plies are adjudicated as draw.
+    pub max_plies: usize,
+    /// Seed of the random openings.
+    pub seed: u64,
+    /// Stops before all games are played once the test decides.
+    pub sprt: Option<Sprt>,
+}
+
+impl SelfPlay {
+    #[must_use]
+    pub const fn new(games: u32, limits: SearchLimits) -> Self {
+        SelfPlay {
+            games,
+            limits,
+            opening_plies: 8,
+            max_plies: 400,
+            seed: 0,
+            sprt: None,
+        }
+    }
+}
+
+/// Games won, drawn and lost by the first engine of [`self_play`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MatchScore {
+    pub wins: u32,
+    pub draws: u32,
+    pub losses: u32,
+}
+
+impl fmt::Display for MatchScore {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "+{} ={} -{}", self.wins, self.draws, self.losses)
+    }
+}
+
+impl MatchScore {
+    #[must_use]
+    pub const fn games(&self) -> u32 {
+        self.wins + self.draws + self.losses
+    }
+
+    /// Counts a game the first engine played with `color`.
+    pub(crate) fn record(&mut self, result: GameResult, color: Color) {
+        match result {
+            GameResult::Draw => self.draws += 1,
+            GameResult::WhiteWins if color == Color::White => self.wins += 1,
+            GameResult::BlackWins if color == Color::Black => self.wins += 1,
+            _ => self.losses += 1,
+        }
+    }
+
+    /// Points per game, a draw is half a point. 0.5 before the first game.
+    #[must_use]
+    pub fn score(&self) -> f64 {
+        if self.games() == 0 {
+            return 0.5;
+        }
+        (f64::from(self.wins) + f64::from(self.draws) / 2.0) / f64::from(self.games())
+    }
+
+    /// Share of the games that were drawn.
+    #[must_use]
+    pub fn draw_rate(&self) -> f64 {
+        if self.games() == 0 {
+            return 0.0;
+        }
+        f64::from(self.draws) / f64::from(self.games())
+    }
+
+    /// Elo the first engine is stronger than the second, infinite if one of
+    /// them won every game.
+    #[must_use]
+    pub fn elo_difference(&self) -> f64 {
+        elo(self.score())
+    }
+
+    /// Half the width of the 95% confidence interval of
+    /// [`MatchScore::elo_difference`], infinite before the first game and
+    /// while one engine won every game.
+    #[must_use]
+    pub fn elo_error(&self) -> f64 {
+        let games = f64::from(self.games());
+        if games == 0.0 || !self.elo_difference().is_finite() {
+            return f64::INFINITY;
+        }
+        let score = self.score();
+        let deviation = |points: f64, count: u32| f64::from(count) * (points - score).powi(2);
+        let variance =
+            (deviation(1.0, self.wins) + deviation(0.5, self.draws) + deviation(0.0, self.losses))
+                / games;
+        let margin = CONFIDENCE_Z * (variance / games).sqrt();
+        (elo(score + margin) - elo(score - margin)) / 2.0
+    }
+}
+
+/// Elo difference of a player scoring `score` points per game.
+fn elo(score: f64) -> f64 {
+    if score <= 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    if score >= 1.0 {
+        return f64::INFINITY;
+    }
+    -400.0 * (1.0 / score - 1.0).log10()
+}
+
+/// Plays `first` against `second` in a [`Tournament`] and counts the results
+/// of `first`, e.g. to check whether a change of the engine makes it stronger.
+/// `on_game` gets the score after every game to show the progress of a long
+/// match.
+///
+/// ```
+/// use rusty_chess_core::engine::{self_play, Engine, SearchLimits, SelfPlay, SkillLevel};
+///
+/// let weak = Engine::new().skill_level(SkillLevel::MIN);
+/// let options = SelfPlay {
+///     max_plies: 16,
+///     ..SelfPlay::new(2, SearchLimits::depth(1))
+/// };
+/// let score = self_play(&Engine::new(), &weak, &options, |_| {});
+/// assert_eq!(score.games(), 2);
+/// println!("{:.0} +/- {:.0} Elo", score.elo_difference(), score.elo_error());
+/// ```
+pub fn self_play(
+    first: &Engine,
+    second: &Engine,
+    options: &SelfPlay,
+    mut on_game: impl FnMut(&MatchScore),
+) -> MatchScore {
+    let mut score = MatchScore::default();
+    if options.games == 0 {
+        return score;
+    }
+    let mut rng = BotRng::seed_from_u64(options.seed);
+    // the tournament plays each opening with both colors
+    let openings = (0..options.games.div_ceil(2))
+        .map(|_| random_opening(options.opening_plies, &mut rng))
+        .collect();
+    Tournament::new()
+        .player("First", EnginePlayer::new(first.clone(), options.limits))
+        .player("Second", EnginePlayer::new(second.clone(), options.limits))
+        .openings(openings)
+        .max_plies(options.max_plies)
+        .run_while(|played| {
+            let first_color = if played.white == 0 {
+                Color::White
+            } else {
+                Color::Black
+            };
+            score.record(played.result, first_color);
+            on_game(&score);
+            score.games() < options.games
+                && options
+                    .sprt
+                    .is_none_or(|sprt| sprt.decision(&score).is_none())
+        });
+    score
+}
+
+/// `plies` random moves from the starting position that do not end the game.
+pub(super) fn random_opening<R: Rng>(plies: u8, rng: &mut R) -> Game {
+    loop {
+        let mut game = Game::new();
+        for _ in 0..plies {
+            let moves = game.legal_moves();
+            if moves.is_empty() {
+                break;
+            }
+            let input = bots::bot_input(moves[rng.gen_range(0..moves.len())]);
+            if let Err(e) = game.process_input(&input) {
+                unreachable!("Generated input {input:?} is not valid: {e}");
+            }
+        }
+        if !game.status().is_finished() {
+            return game;
+        }
+    }
+}
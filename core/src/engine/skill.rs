@@ -0,0 +1,118 @@
+use super::MAX_DEPTH;
+use crate::game::{BotRng, Rng};
+
+/// Centipawns the evaluation is off by at most, for every level below
+/// [`SkillLevel::MAX`].
+const NOISE_PER_LEVEL: i32 = 5;
+
+/// Centipawns a picked move may be worse than the best one, for every level
+/// below [`SkillLevel::MAX`].
+const MARGIN_PER_LEVEL: i32 = 15;
+
+/// Lines searched below [`SkillLevel::MAX`] to have moves to pick from.
+const CANDIDATE_LINES: usize = 4;
+
+/// Strength of the [`Engine`](super::Engine) from 0 to 20, so that beginners
+/// have a chance. Below the full strength of [`SkillLevel::MAX`] the search is
+/// shallower, the evaluation is off by some centipawns and the engine sometimes
+/// picks a slightly worse move than the best one. Mates it has found are always
+/// played.
+///
+/// ```
+/// use rusty_chess_core::engine::{Engine, SearchLimits, SkillLevel};
+/// use rusty_chess_core::game::Game;
+///
+/// let engine = Engine::new().skill_level(SkillLevel::new(3));
+/// let result = engine.search(&Game::new(), &SearchLimits::default());
+/// assert!(result.best_move.is_some());
+/// assert!(result.depth <= SkillLevel::new(3).max_depth());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SkillLevel(u8);
+
+impl Default for SkillLevel {
+    fn default() -> Self {
+        SkillLevel::MAX
+    }
+}
+
+impl SkillLevel {
+    pub const MIN: SkillLevel = SkillLevel(0);
+    pub const MAX: SkillLevel = SkillLevel(20);
+
+    /// Levels above [`SkillLevel::MAX`] are full strength.
+    #[must_use]
+    pub const fn new(level: u8) -> Self {
+        if level > SkillLevel::MAX.0 {
+            SkillLevel::MAX
+        } else {
+            SkillLevel(level)
+        }
+    }
+
+    #[must_use]
+    pub const fn level(self) -> u8 {
+        self.0
+    }
+
+    /// Deepest search in plies at this level, from 1 at the lowest level up
+    /// to [`MAX_DEPTH`].
+    #[must_use]
+    pub const fn max_depth(self) -> u8 {
+        if self.0 == SkillLevel::MAX.0 {
+            MAX_DEPTH
+        } else {
+            1 + self.0 / 2
+        }
+    }
+
+    const fn weakness(self) -> i32 {
+        (SkillLevel::MAX.0 - self.0) as i32
+    }
+
+    /// Lines the search needs to pick a move from.
+    pub(super) const fn candidate_lines(self) -> usize {
+        if self.0 == SkillLevel::MAX.0 {
+            1
+        } else {
+            CANDIDATE_LINES
+        }
+    }
+
+    /// Error of the evaluation of the position with the Zobrist `key`, the
+    /// same for the same position and `seed`.
+    pub(super) fn noise(self, key: u64, seed: u64) -> i32 {
+        let amplitude = NOISE_PER_LEVEL * self.weakness();
+        if amplitude == 0 {
+            return 0;
+        }
+        let random = splitmix64(key ^ seed) % (2 * amplitude as u64 + 1);
+        random as i32 - amplitude
+    }
+
+    /// Index of the line to play among `scores` sorted from the best. Every
+    /// line gets a random bonus up to the margin of the level, the best sum
+    /// wins.
+    pub(super) fn pick(self, scores: &[i32], rng: &mut BotRng) -> usize {
+        let margin = MARGIN_PER_LEVEL * self.weakness();
+        if margin == 0 {
+            return 0;
+        }
+        let mut best = (0, i32::MIN);
+        for (i, &score) in scores.iter().enumerate() {
+            let score = score.saturating_add(rng.gen_range(0..=margin));
+            if score > best.1 {
+                best = (i, score);
+            }
+        }
+        best.0
+    }
+}
+
+/// Mixes the bits of `x`, so that close keys give unrelated numbers.
+const fn splitmix64(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
@@ -0,0 +1,396 @@
+use crate::game::{Color, Game, MoveList, PieceType, Position, Variant, BOARD_SIZE, COLOR_COUNT};
+use alloc::vec::Vec;
+use core::ops::RangeInclusive;
+
+#[cfg(feature = "nnue")]
+mod nnue;
+
+#[cfg(feature = "nnue")]
+pub use nnue::{Accumulator, Network, NnueError};
+
+/// Weights of the terms of [`evaluate_with`] in centipawns. The defaults are
+/// the ones of [`evaluate`], tuners adjust them through [`EvalParams::to_vec`]
+/// and [`EvalParams::from_slice`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EvalParams {
+    pub pawn: i32,
+    pub knight: i32,
+    pub bishop: i32,
+    pub rook: i32,
+    pub queen: i32,
+    pub doubled_pawn_penalty: i32,
+    pub isolated_pawn_penalty: i32,
+    /// Bonus of a passed pawn by the number of ranks it has advanced.
+    pub passed_pawn_bonus: [i32; BOARD_SIZE],
+    /// Bonus for every own pawn on the three files around the king one or two
+    /// ranks in front of it, scaled down towards the endgame.
+    pub pawn_shield_bonus: i32,
+    /// Penalty for every file next to the king without own pawn.
+    pub open_king_file_penalty: i32,
+    /// Bonus for every square a knight, bishop, rook or queen can move to.
+    pub mobility_bonus: i32,
+}
+
+/// Number of values of [`EvalParams::to_vec`].
+pub const EVAL_PARAM_COUNT: usize = 5 + 2 + BOARD_SIZE + 3;
+
+impl EvalParams {
+    pub const DEFAULT: EvalParams = EvalParams {
+        pawn: 100,
+        knight: 320,
+        bishop: 330,
+        rook: 500,
+        queen: 900,
+        doubled_pawn_penalty: 15,
+        isolated_pawn_penalty: 15,
+        passed_pawn_bonus: [0, 5, 10, 20, 35, 60, 100, 0],
+        pawn_shield_bonus: 10,
+        open_king_file_penalty: 15,
+        mobility_bonus: 4,
+    };
+
+    /// All weights in the order of the fields, [`EVAL_PARAM_COUNT`] long.
+    #[must_use]
+    pub fn to_vec(&self) -> Vec<i32> {
+        let mut values = alloc::vec![
+            self.pawn,
+            self.knight,
+            self.bishop,
+            self.rook,
+            self.queen,
+            self.doubled_pawn_penalty,
+            self.isolated_pawn_penalty,
+        ];
+        values.extend_from_slice(&self.passed_pawn_bonus);
+        values.extend_from_slice(&[
+            self.pawn_shield_bonus,
+            self.open_king_file_penalty,
+            self.mobility_bonus,
+        ]);
+        values
+    }
+
+    /// Inverse of [`EvalParams::to_vec`], `None` if `values` does not have
+    /// [`EVAL_PARAM_COUNT`] weights.
+    #[must_use]
+    pub fn from_slice(values: &[i32]) -> Option<Self> {
+        let values: &[i32; EVAL_PARAM_COUNT] = values.try_into().ok()?;
+        let (pieces, rest) = values.split_at(5);
+        let (pawns, rest) = rest.split_at(2);
+        let (passed, king) = rest.split_at(BOARD_SIZE);
+        Some(EvalParams {
+            pawn: pieces[0],
+            knight: pieces[1],
+            bishop: pieces[2],
+            rook: pieces[3],
+            queen: pieces[4],
+            doubled_pawn_penalty: pawns[0],
+            isolated_pawn_penalty: pawns[1],
+            passed_pawn_bonus: passed.try_into().ok()?,
+            pawn_shield_bonus: king[0],
+            open_king_file_penalty: king[1],
+            mobility_bonus: king[2],
+        })
+    }
+
+    /// Value of the piece in centipawns.
+    const fn piece_value(&self, piece_type: PieceType) -> i32 {
+        match piece_type {
+            PieceType::Pawn => self.pawn,
+            PieceType::Knight => self.knight,
+            PieceType::Bishop => self.bishop,
+            PieceType::Rook => self.rook,
+            PieceType::Queen => self.queen,
+            PieceType::King => 0,
+        }
+    }
+}
+
+impl Default for EvalParams {
+    fn default() -> Self {
+        EvalParams::DEFAULT
+    }
+}
+
+/// Bonus of a piece on a square from the view of White, the first row is the
+/// eighth rank. Black uses the tables mirrored.
+#[rustfmt::skip]
+const PAWN_TABLE: [i32; 64] = [
+     0,  0,  0,  0,  0,  0,  0,  0,
+    50, 50, 50, 50, 50, 50, 50, 50,
+    10, 10, 20, 30, 30, 20, 10, 10,
+     5,  5, 10, 25, 25, 10,  5,  5,
+     0,  0,  0, 20, 20,  0,  0,  0,
+     5, -5,-10,  0,  0,-10, -5,  5,
+     5, 10, 10,-20,-20, 10, 10,  5,
+     0,  0,  0,  0,  0,  0,  0,  0,
+];
+
+#[rustfmt::skip]
+const KNIGHT_TABLE: [i32; 64] = [
+    -50,-40,-30,-30,-30,-30,-40,-50,
+    -40,-20,  0,  0,  0,  0,-20,-40,
+    -30,  0, 10, 15, 15, 10,  0,-30,
+    -30,  5, 15, 20, 20, 15,  5,-30,
+    -30,  0, 15, 20, 20, 15,  0,-30,
+    -30,  5, 10, 15, 15, 10,  5,-30,
+    -40,-20,  0,  5,  5,  0,-20,-40,
+    -50,-40,-30,-30,-30,-30,-40,-50,
+];
+
+#[rustfmt::skip]
+const BISHOP_TABLE: [i32; 64] = [
+    -20,-10,-10,-10,-10,-10,-10,-20,
+    -10,  0,  0,  0,  0,  0,  0,-10,
+    -10,  0,  5, 10, 10,  5,  0,-10,
+    -10,  5,  5, 10, 10,  5,  5,-10,
+    -10,  0, 10, 10, 10, 10,  0,-10,
+    -10, 10, 10, 10, 10, 10, 10,-10,
+    -10,  5,  0,  0,  0,  0,  5,-10,
+    -20,-10,-10,-10,-10,-10,-10,-20,
+];
+
+#[rustfmt::skip]
+const ROOK_TABLE: [i32; 64] = [
+     0,  0,  0,  0,  0,  0,  0,  0,
+     5, 10, 10, 10, 10, 10, 10,  5,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+     0,  0,  0,  5,  5,  0,  0,  0,
+];
+
+#[rustfmt::skip]
+const QUEEN_TABLE: [i32; 64] = [
+    -20,-10,-10, -5, -5,-10,-10,-20,
+    -10,  0,  0,  0,  0,  0,  0,-10,
+    -10,  0,  5,  5,  5,  5,  0,-10,
+     -5,  0,  5,  5,  5,  5,  0, -5,
+      0,  0,  5,  5,  5,  5,  0, -5,
+    -10,  5,  5,  5,  5,  5,  0,-10,
+    -10,  0,  5,  0,  0,  0,  0,-10,
+    -20,-10,-10, -5, -5,-10,-10,-20,
+];
+
+/// The king hides behind its pawns while the other side still has pieces to
+/// attack it.
+#[rustfmt::skip]
+const KING_MIDDLEGAME_TABLE: [i32; 64] = [
+    -30,-40,-40,-50,-50,-40,-40,-30,
+    -30,-40,-40,-50,-50,-40,-40,-30,
+    -30,-40,-40,-50,-50,-40,-40,-30,
+    -30,-40,-40,-50,-50,-40,-40,-30,
+    -20,-30,-30,-40,-40,-30,-30,-20,
+    -10,-20,-20,-20,-20,-20,-20,-10,
+     20, 20,  0,  0,  0,  0, 20, 20,
+     20, 30, 10,  0,  0, 10, 30, 20,
+];
+
+/// In the endgame the king belongs in the center.
+#[rustfmt::skip]
+const KING_ENDGAME_TABLE: [i32; 64] = [
+    -50,-40,-30,-20,-20,-30,-40,-50,
+    -30,-20,-10,  0,  0,-10,-20,-30,
+    -30,-10, 20, 30, 30, 20,-10,-30,
+    -30,-10, 30, 40, 40, 30,-10,-30,
+    -30,-10, 30, 40, 40, 30,-10,-30,
+    -30,-10, 20, 30, 30, 20,-10,-30,
+    -30,-30,  0,  0,  0,  0,-30,-30,
+    -50,-30,-30,-30,-30,-30,-30,-50,
+];
+
+/// Weight of the piece for the game phase, the starting position sums up to
+/// [`MAX_PHASE`].
+const fn phase_weight(piece_type: PieceType) -> i32 {
+    match piece_type {
+        PieceType::Pawn | PieceType::King => 0,
+        PieceType::Knight | PieceType::Bishop => 1,
+        PieceType::Rook => 2,
+        PieceType::Queen => 4,
+    }
+}
+
+const MAX_PHASE: i32 = 24;
+
+/// Static evaluation of the position in centipawns from the view of White,
+/// positive if White is better. Sums up material, the squares of the pieces,
+/// pawn structure, king safety and mobility. Finished games are not detected,
+/// see [`Game::status`].
+///
+/// ```
+/// use rusty_chess_core::eval::evaluate;
+/// use rusty_chess_core::game::Game;
+///
+/// assert_eq!(evaluate(&Game::new()), 0);
+/// let game = Game::from_fen("rnb1kbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+/// assert!(evaluate(&game) > 800);
+/// ```
+#[must_use]
+pub fn evaluate(game: &Game) -> i32 {
+    evaluate_with(game, &EvalParams::DEFAULT)
+}
+
+/// Like [`evaluate`] with other weights of its terms.
+#[must_use]
+pub fn evaluate_with(game: &Game, params: &EvalParams) -> i32 {
+    if game.variant() == Variant::Antichess {
+        // losing all pieces is the goal, the squares do not matter
+        return -material(game, Color::White, params) + material(game, Color::Black, params);
+    }
+    let phase = phase(game);
+    let files = pawn_files(game);
+    let score = |color: Color| {
+        material(game, color, params)
+            + piece_squares(game, color, phase)
+            + pawn_structure(color, &files, params)
+            + king_safety(game, color, phase, &files, params)
+            + mobility(game, color, params)
+    };
+    score(Color::White) - score(Color::Black)
+}
+
+fn material(game: &Game, color: Color, params: &EvalParams) -> i32 {
+    game.pieces_of(color)
+        .map(|(_, piece)| params.piece_value(piece.piece_type))
+        .sum()
+}
+
+/// From [`MAX_PHASE`] in the opening down to 0 with only kings and pawns left.
+fn phase(game: &Game) -> i32 {
+    let phase: i32 = game
+        .pieces()
+        .map(|(_, piece)| phase_weight(piece.piece_type))
+        .sum();
+    phase.min(MAX_PHASE)
+}
+
+fn piece_squares(game: &Game, color: Color, phase: i32) -> i32 {
+    game.pieces_of(color)
+        .map(|(pos, piece)| {
+            let index = table_index(pos, color);
+            match piece.piece_type {
+                PieceType::Pawn => PAWN_TABLE[index],
+                PieceType::Knight => KNIGHT_TABLE[index],
+                PieceType::Bishop => BISHOP_TABLE[index],
+                PieceType::Rook => ROOK_TABLE[index],
+                PieceType::Queen => QUEEN_TABLE[index],
+                PieceType::King => {
+                    (KING_MIDDLEGAME_TABLE[index] * phase
+                        + KING_ENDGAME_TABLE[index] * (MAX_PHASE - phase))
+                        / MAX_PHASE
+                }
+            }
+        })
+        .sum()
+}
+
+/// Index into the square tables, which are written with the eighth rank first.
+fn table_index(pos: Position, color: Color) -> usize {
+    let file = file(pos);
+    let rank = relative_rank(pos, color);
+    (BOARD_SIZE - 1 - rank) * BOARD_SIZE + file
+}
+
+fn file(pos: Position) -> usize {
+    (pos.0 as u8 - b'a') as usize
+}
+
+/// Ranks from the own back rank, 0 for the first rank of White and the eighth
+/// of Black.
+fn relative_rank(pos: Position, color: Color) -> usize {
+    let rank = (pos.1 as u8 - b'1') as usize;
+    match color {
+        Color::White => rank,
+        Color::Black => BOARD_SIZE - 1 - rank,
+    }
+}
+
+/// Relative ranks of the pawns of each color on each file.
+type PawnFiles = [[Vec<usize>; BOARD_SIZE]; COLOR_COUNT];
+
+fn pawn_files(game: &Game) -> PawnFiles {
+    let mut files: PawnFiles = Default::default();
+    for (pos, piece) in game.pieces() {
+        if piece.piece_type == PieceType::Pawn {
+            files[piece.color as usize][file(pos)].push(relative_rank(pos, piece.color));
+        }
+    }
+    files
+}
+
+fn pawn_structure(color: Color, files: &PawnFiles, params: &EvalParams) -> i32 {
+    let own = &files[color as usize];
+    let other = &files[color.invert() as usize];
+    let mut score = 0;
+    for (file, ranks) in own.iter().enumerate() {
+        if ranks.is_empty() {
+            continue;
+        }
+        if ranks.len() > 1 {
+            score -= params.doubled_pawn_penalty * (ranks.len() as i32 - 1);
+        }
+        // the only pawns on this and the next files are the ones of this file
+        let around: usize = own[files_around(file)].iter().map(Vec::len).sum();
+        if around == ranks.len() {
+            score -= params.isolated_pawn_penalty * ranks.len() as i32;
+        }
+        for &rank in ranks {
+            // no pawn of the other side in front of it on the same or next files
+            let passed = other[files_around(file)]
+                .iter()
+                .flatten()
+                .all(|&other_rank| BOARD_SIZE - 1 - other_rank <= rank);
+            if passed {
+                score += params.passed_pawn_bonus[rank];
+            }
+        }
+    }
+    score
+}
+
+/// The file and the ones next to it.
+fn files_around(file: usize) -> RangeInclusive<usize> {
+    file.saturating_sub(1)..=(file + 1).min(BOARD_SIZE - 1)
+}
+
+fn king_safety(
+    game: &Game,
+    color: Color,
+    phase: i32,
+    files: &PawnFiles,
+    params: &EvalParams,
+) -> i32 {
+    let Some((king, _)) = game
+        .pieces_of(color)
+        .find(|(_, piece)| piece.piece_type == PieceType::King)
+    else {
+        return 0;
+    };
+    let king_file = file(king);
+    let king_rank = relative_rank(king, color);
+    let mut score = 0;
+    for ranks in &files[color as usize][files_around(king_file)] {
+        if ranks.is_empty() {
+            score -= params.open_king_file_penalty;
+        }
+        score += params.pawn_shield_bonus
+            * ranks
+                .iter()
+                .filter(|&&rank| rank > king_rank && rank <= king_rank + 2)
+                .count() as i32;
+    }
+    score * phase / MAX_PHASE
+}
+
+/// Squares the pieces could move to, ignoring pins and checks.
+fn mobility(game: &Game, color: Color, params: &EvalParams) -> i32 {
+    let mut moves = MoveList::new();
+    for (pos, piece) in game.pieces_of(color) {
+        if !matches!(piece.piece_type, PieceType::Pawn | PieceType::King) {
+            game.pseudo_legal_moves_to(pos, &mut moves);
+        }
+    }
+    params.mobility_bonus * moves.len() as i32
+}
@@ -0,0 +1,270 @@
+use crate::game::{Color, Game, Piece, PieceType, TOTAL_SQUARES};
+use alloc::vec::Vec;
+use core::fmt::{self, Formatter};
+
+/// Version written as the first byte by [`Network::to_bytes`].
+const NETWORK_FORMAT_VERSION: u8 = 1;
+
+/// A piece of either color on every square, seen from one side.
+const INPUT_SIZE: usize = 2 * 6 * TOTAL_SQUARES;
+
+/// Activations of the hidden layer are clipped to `0..=QA`.
+const QA: i32 = 255;
+/// The output weights are scaled up by this factor.
+const QB: i32 = 64;
+/// Output of the network for an advantage of one pawn.
+const SCALE: i32 = 400;
+
+/// Why bytes could not be read with [`Network::from_bytes`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NnueError {
+    /// The bytes end in the middle of a field.
+    UnexpectedEnd,
+    UnsupportedVersion(u8),
+    /// The hidden layer has no neurons.
+    EmptyHiddenLayer,
+    /// There are bytes left after the output bias.
+    TrailingBytes(usize),
+}
+
+impl fmt::Display for NnueError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            NnueError::UnexpectedEnd => write!(f, "the network is truncated"),
+            NnueError::UnsupportedVersion(version) => {
+                write!(f, "unsupported network format version {version}")
+            }
+            NnueError::EmptyHiddenLayer => write!(f, "the hidden layer is empty"),
+            NnueError::TrailingBytes(count) => {
+                write!(f, "{count} bytes left after the network")
+            }
+        }
+    }
+}
+
+impl core::error::Error for NnueError {}
+
+/// An efficiently updatable neural network evaluating standard chess
+/// positions: one input for every piece on every square seen from both sides,
+/// a hidden layer for each side with clipped ReLU activation and one output.
+/// The hidden layers are kept in an [`Accumulator`], which only changes for
+/// the squares a move changes.
+///
+/// ```
+/// use rusty_chess_core::eval::Network;
+/// use rusty_chess_core::game::Game;
+///
+/// // a network of one neuron that counts nothing
+/// let mut bytes = vec![1, 1, 0];
+/// bytes.resize(bytes.len() + 2 * (768 + 1 + 2 + 1), 0);
+/// let network = Network::from_bytes(&bytes).unwrap();
+/// assert_eq!(network.evaluate(&Game::new()), 0);
+/// assert_eq!(network.to_bytes(), bytes);
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct Network {
+    hidden_size: usize,
+    /// `hidden_size` weights for every input.
+    feature_weights: Vec<i16>,
+    feature_biases: Vec<i16>,
+    /// The weights of the side to move first, then the ones of the other side.
+    output_weights: Vec<i16>,
+    output_bias: i16,
+}
+
+impl fmt::Debug for Network {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Network")
+            .field("hidden_size", &self.hidden_size)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The hidden layers of a [`Network`] for a position, from the view of White
+/// and of Black.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Accumulator {
+    white: Vec<i16>,
+    black: Vec<i16>,
+}
+
+impl Network {
+    /// Reads a network written by [`Network::to_bytes`], e.g. the output of a
+    /// trainer loaded with `include_bytes!` or from a file.
+    ///
+    /// Layout: format version, hidden size as two little-endian bytes, then
+    /// little-endian `i16`s: the weights of every input to the hidden layer
+    /// (inputs of the own pieces first, by pawn, knight, bishop, rook, queen
+    /// and king, squares from a1 to h8 and mirrored for Black), the biases of
+    /// the hidden layer, the output weights and the output bias.
+    ///
+    /// # Errors
+    ///
+    /// If the bytes do not hold a network of this layout.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Network, NnueError> {
+        let (&version, rest) = bytes.split_first().ok_or(NnueError::UnexpectedEnd)?;
+        if version != NETWORK_FORMAT_VERSION {
+            return Err(NnueError::UnsupportedVersion(version));
+        }
+        let (size, mut rest) = rest.split_at_checked(2).ok_or(NnueError::UnexpectedEnd)?;
+        let hidden_size = usize::from(u16::from_le_bytes([size[0], size[1]]));
+        if hidden_size == 0 {
+            return Err(NnueError::EmptyHiddenLayer);
+        }
+        let mut read = |count: usize| -> Result<Vec<i16>, NnueError> {
+            let (values, tail) = rest
+                .split_at_checked(2 * count)
+                .ok_or(NnueError::UnexpectedEnd)?;
+            rest = tail;
+            Ok(values
+                .chunks_exact(2)
+                .map(|value| i16::from_le_bytes([value[0], value[1]]))
+                .collect())
+        };
+        let network = Network {
+            hidden_size,
+            feature_weights: read(INPUT_SIZE * hidden_size)?,
+            feature_biases: read(hidden_size)?,
+            output_weights: read(2 * hidden_size)?,
+            output_bias: read(1)?[0],
+        };
+        if !rest.is_empty() {
+            return Err(NnueError::TrailingBytes(rest.len()));
+        }
+        Ok(network)
+    }
+
+    /// The network in the layout of [`Network::from_bytes`].
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = alloc::vec![NETWORK_FORMAT_VERSION];
+        // the hidden size was read from two bytes
+        bytes.extend_from_slice(&(self.hidden_size as u16).to_le_bytes());
+        for value in self
+            .feature_weights
+            .iter()
+            .chain(&self.feature_biases)
+            .chain(&self.output_weights)
+            .chain([&self.output_bias])
+        {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Number of neurons of the hidden layer of each side.
+    #[must_use]
+    pub const fn hidden_size(&self) -> usize {
+        self.hidden_size
+    }
+
+    /// Centipawns from the view of White like [`super::evaluate`], computed
+    /// from scratch.
+    #[must_use]
+    pub fn evaluate(&self, game: &Game) -> i32 {
+        self.evaluate_accumulator(&self.accumulator(game), game.turn)
+    }
+
+    /// The hidden layers of the position.
+    #[must_use]
+    pub fn accumulator(&self, game: &Game) -> Accumulator {
+        let mut accumulator = Accumulator {
+            white: self.feature_biases.clone(),
+            black: self.feature_biases.clone(),
+        };
+        for (index, square) in game.board.iter().enumerate() {
+            if let Some(piece) = square {
+                self.add(&mut accumulator, *piece, index);
+            }
+        }
+        accumulator
+    }
+
+    /// Turns the accumulator of `before` into the one of `after` by changing
+    /// only the squares that differ, e.g. after a move.
+    pub fn update(&self, accumulator: &mut Accumulator, before: &Game, after: &Game) {
+        let squares = before.board.iter().zip(&after.board).enumerate();
+        for (index, (old, new)) in squares {
+            if old == new {
+                continue;
+            }
+            if let Some(piece) = old {
+                self.remove(accumulator, *piece, index);
+            }
+            if let Some(piece) = new {
+                self.add(accumulator, *piece, index);
+            }
+        }
+    }
+
+    /// Centipawns from the view of White of the position of `accumulator`
+    /// with `turn` to move.
+    #[must_use]
+    pub fn evaluate_accumulator(&self, accumulator: &Accumulator, turn: Color) -> i32 {
+        let (us, them) = match turn {
+            Color::White => (&accumulator.white, &accumulator.black),
+            Color::Black => (&accumulator.black, &accumulator.white),
+        };
+        let (us_weights, them_weights) = self.output_weights.split_at(self.hidden_size);
+        let layer = |hidden: &[i16], weights: &[i16]| -> i32 {
+            hidden
+                .iter()
+                .zip(weights)
+                .map(|(value, weight)| i32::from(*value).clamp(0, QA) * i32::from(*weight))
+                .sum()
+        };
+        let output =
+            i32::from(self.output_bias) + layer(us, us_weights) + layer(them, them_weights);
+        let score = output * SCALE / (QA * QB);
+        match turn {
+            Color::White => score,
+            Color::Black => -score,
+        }
+    }
+
+    fn add(&self, accumulator: &mut Accumulator, piece: Piece, square: usize) {
+        for (view, hidden) in [
+            (Color::White, &mut accumulator.white),
+            (Color::Black, &mut accumulator.black),
+        ] {
+            for (value, weight) in hidden.iter_mut().zip(self.weights(view, piece, square)) {
+                *value = value.wrapping_add(*weight);
+            }
+        }
+    }
+
+    fn remove(&self, accumulator: &mut Accumulator, piece: Piece, square: usize) {
+        for (view, hidden) in [
+            (Color::White, &mut accumulator.white),
+            (Color::Black, &mut accumulator.black),
+        ] {
+            for (value, weight) in hidden.iter_mut().zip(self.weights(view, piece, square)) {
+                *value = value.wrapping_sub(*weight);
+            }
+        }
+    }
+
+    /// Weights of the input of `piece` on `square` seen from `view`.
+    fn weights(&self, view: Color, piece: Piece, square: usize) -> &[i16] {
+        let start = input(view, piece, square) * self.hidden_size;
+        &self.feature_weights[start..start + self.hidden_size]
+    }
+}
+
+/// Black sees the board mirrored, so both sides see their pieces the same way.
+fn input(view: Color, piece: Piece, square: usize) -> usize {
+    let side = usize::from(piece.color != view);
+    let piece_type = match piece.piece_type {
+        PieceType::Pawn => 0,
+        PieceType::Knight => 1,
+        PieceType::Bishop => 2,
+        PieceType::Rook => 3,
+        PieceType::Queen => 4,
+        PieceType::King => 5,
+    };
+    let square = match view {
+        Color::White => square,
+        Color::Black => square ^ 56,
+    };
+    (side * 6 + piece_type) * TOTAL_SQUARES + square
+}
@@ -1,10 +1,76 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{self, Formatter};
+use core::str::FromStr;
+#[cfg(feature = "rayon")]
 use rayon::prelude::*;
-use std::collections::HashMap;
-use std::fmt::{self, Formatter};
-use std::sync::Mutex;
+
+mod attacks;
+mod binary;
+mod builder;
+mod clock;
+mod dead_position;
+mod describe;
+mod evasion;
+mod fen;
+mod formatter;
+mod handicap;
+mod material;
+mod mobility;
+mod move_list;
+mod null_move;
+mod perft;
+mod pgn;
+mod position_key;
+mod random;
+#[cfg(feature = "render")]
+mod render;
+mod replay;
+mod san;
+#[cfg(feature = "serde")]
+mod save;
+#[cfg(feature = "serde")]
+mod serialize;
+mod snapshot;
+mod stats;
+mod tables;
+mod tree;
+mod uci;
+mod undo;
+mod variant;
+mod zobrist;
+
+pub use attacks::Pin;
+pub use binary::DecodeError;
+pub use builder::{BoardBuilder, SetupError};
+pub use clock::{Clock, ClockStage};
+pub use fen::{FenError, STARTING_POSITION_FEN};
+pub use formatter::BoardFormatter;
+pub use handicap::Handicap;
+pub use material::MaterialCount;
+pub use move_list::{MoveList, MOVE_LIST_CAPACITY};
+pub use pgn::{Annotation, PgnError, PgnTags};
+pub use position_key::PositionKey;
+pub use random::{BotRng, Rng, SeedableRng};
+#[cfg(feature = "png")]
+pub use render::RenderError;
+#[cfg(feature = "render")]
+pub use render::{SvgRenderer, Theme};
+pub use replay::GameReplay;
+use san::piece_type_to_san_char;
+#[cfg(feature = "serde")]
+pub use save::{LoadError, SAVE_FORMAT_VERSION};
+#[cfg(feature = "stats")]
+pub use stats::Stats;
+use stats::{CloneCounter, Phase};
+use tables::{king_attacks, knight_attacks, pawn_attacks};
+pub use tree::{GameTree, NodeId};
+use undo::{RedoMove, UndoState};
+pub use variant::Variant;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 #[repr(u8)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Color {
     White,
     Black,
@@ -23,6 +89,7 @@ impl Color {
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 #[repr(u8)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PieceType {
     Pawn,
     Bishop,
@@ -48,6 +115,14 @@ impl PieceType {
 pub struct Position(pub char, pub char);
 
 impl Position {
+    /// The square on `file` (`'a'..='h'`) and `rank` (`'1'..='8'`), `None` if it
+    /// is not on the board.
+    #[must_use]
+    pub fn new(file: char, rank: char) -> Option<Position> {
+        let pos = Position(file, rank);
+        pos.try_as_index().map(|_| pos)
+    }
+
     /// Adds a tuple of i8 to the position and returns a new position.
     /// No boundary check is done!
     fn add(self, to_add: (i8, i8)) -> Position {
@@ -81,6 +156,38 @@ impl Position {
         Some(self.as_index())
     }
 }
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.0, self.1)
+    }
+}
+
+/// The string is not a square in algebraic notation like `e4`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsePositionError(pub String);
+
+impl fmt::Display for ParsePositionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "'{}' is not a square", self.0)
+    }
+}
+
+impl core::error::Error for ParsePositionError {}
+
+impl FromStr for Position {
+    type Err = ParsePositionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next(), chars.next()) {
+            (Some(file), Some(rank), None) => Position::new(file, rank),
+            _ => None,
+        }
+        .ok_or_else(|| ParsePositionError(s.to_string()))
+    }
+}
+
 impl From<(char, char)> for Position {
     fn from(val: (char, char)) -> Self {
         Position(val.0, val.1)
@@ -106,6 +213,14 @@ impl TryFrom<usize> for Position {
 }
 
 pub const BOARD_SIZE: usize = 8;
+
+/// Pieces a pawn can promote to.
+pub const PROMOTION_PIECE_TYPES: [PieceType; 4] = [
+    PieceType::Queen,
+    PieceType::Rook,
+    PieceType::Bishop,
+    PieceType::Knight,
+];
 pub const TOTAL_SQUARES: usize = BOARD_SIZE * BOARD_SIZE;
 type Board = [Option<Piece>; TOTAL_SQUARES];
 
@@ -115,8 +230,7 @@ const fn all_possibles_sqares() -> [(char, char); TOTAL_SQUARES] {
     while i < BOARD_SIZE {
         let mut j: usize = 0;
         while j < BOARD_SIZE {
-            squares[i * BOARD_SIZE + j] =
-                ((b'a' + i as u8) as char, (b'1' + j as u8) as char);
+            squares[i * BOARD_SIZE + j] = ((b'a' + i as u8) as char, (b'1' + j as u8) as char);
             j += 1;
         }
         i += 1;
@@ -125,6 +239,26 @@ const fn all_possibles_sqares() -> [(char, char); TOTAL_SQUARES] {
 }
 const ALL_POSSIBLE_SQUARES: [(char, char); TOTAL_SQUARES] = all_possibles_sqares();
 
+/// Value computed on first use. `Game` can only be shared between threads with
+/// the `std` feature.
+#[cfg(feature = "std")]
+type Cache<T> = std::sync::OnceLock<T>;
+
+#[cfg(not(feature = "std"))]
+type Cache<T> = core::cell::OnceCell<T>;
+
+/// All squares to generate moves from, split over threads with the `rayon`
+/// feature. Only worth it per square, the work per direction is too small.
+#[cfg(feature = "rayon")]
+fn squares() -> rayon::slice::Iter<'static, (char, char)> {
+    ALL_POSSIBLE_SQUARES.par_iter()
+}
+
+#[cfg(not(feature = "rayon"))]
+fn squares() -> core::slice::Iter<'static, (char, char)> {
+    ALL_POSSIBLE_SQUARES.iter()
+}
+
 const fn horizontal_directions() -> [([i8; BOARD_SIZE], [i8; BOARD_SIZE]); 4] {
     let mut forward = [0; BOARD_SIZE];
     let mut backward = [0; BOARD_SIZE];
@@ -184,6 +318,7 @@ enum Obstacle {
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Piece {
     pub piece_type: PieceType,
     pub color: Color,
@@ -248,6 +383,7 @@ impl Piece {
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 #[repr(u8)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 enum MoveType {
     Normal,
     Jump,
@@ -256,14 +392,83 @@ enum MoveType {
     ShortCastle,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Move {
     pub piece: Piece,
     pub from: Position,
     pub to: Position,
     pub captured_piece: Option<Piece>,
+    /// Piece a pawn promotes to. `None` for all other moves and for
+    /// promotions that still need to be chosen.
+    pub promotion: Option<PieceType>,
     move_type: MoveType,
-    traversed_squares: Vec<Position>,
+}
+
+impl Move {
+    /// A pawn reaching the last rank, it has to be played with a promotion piece.
+    #[must_use]
+    pub fn is_promotion(&self) -> bool {
+        self.piece.piece_type == PieceType::Pawn
+            && self.move_type == MoveType::Normal
+            && (self.to.1 == '8' || self.to.1 == '1')
+    }
+
+    /// Captures a piece, including en passant.
+    #[must_use]
+    pub const fn is_capture(&self) -> bool {
+        self.captured_piece.is_some()
+    }
+
+    #[must_use]
+    pub fn is_en_passant(&self) -> bool {
+        self.move_type == MoveType::Enpassant
+    }
+
+    /// Castling to either side, the move is the one of the king.
+    #[must_use]
+    pub fn is_castle(&self) -> bool {
+        matches!(self.move_type, MoveType::LongCastle | MoveType::ShortCastle)
+    }
+
+    /// `pos` is the starting square or a square the piece slides over. Other
+    /// pieces can block the move there, computed when needed so that moves
+    /// stay small and cheap to copy.
+    fn passes(&self, pos: Position) -> bool {
+        if pos == self.from {
+            return true;
+        }
+        if !matches!(
+            self.piece.piece_type,
+            PieceType::Queen | PieceType::Rook | PieceType::Bishop
+        ) {
+            return false;
+        }
+        let step = |from: char, to: char| (to as i8 - from as i8).signum();
+        let direction = (step(self.from.0, self.to.0), step(self.from.1, self.to.1));
+        let mut square = self.from.add(direction);
+        while square != self.to {
+            if square == pos {
+                return true;
+            }
+            square = square.add(direction);
+        }
+        false
+    }
+
+    /// The move checks the king of the other side when played in `game`. A
+    /// promotion without chosen piece is played as promotion to a queen.
+    #[must_use]
+    pub fn gives_check(&self, game: &Game) -> bool {
+        let mut game_after_move = game.clone();
+        let input = if self.is_promotion() {
+            let piece_type = self.promotion.unwrap_or(PieceType::Queen);
+            UserInput::MoveWithPromotion(self.from, self.to, piece_type)
+        } else {
+            UserInput::Move(self.from, self.to)
+        };
+        game_after_move.apply_input(&input).is_ok() && game_after_move.is_check()
+    }
 }
 
 impl fmt::Display for Move {
@@ -276,67 +481,209 @@ impl fmt::Display for Move {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum UserInput {
+    /// Moves a piece. A pawn reaching the last rank waits for [`UserInput::Promotion`].
     Move(Position, Position),
+    /// Moves a pawn to the last rank and promotes it to the given piece in one step.
+    MoveWithPromotion(Position, Position, PieceType),
+    /// Promotes the pawn on the square, see [`GameStatus::AwaitingPromotion`].
     Promotion(Piece, Position),
+    /// The side to move offers a draw and plays its move, the other side
+    /// answers on its turn with [`UserInput::AcceptDraw`] or
+    /// [`UserInput::DeclineDraw`], or declines by moving.
     Draw,
+    AcceptDraw,
+    DeclineDraw,
+    /// Claims a draw by threefold repetition or the fifty-move rule,
+    /// see [`Game::claimable_draw`].
+    ClaimDraw,
+    /// The side to move resigns.
     Resign,
+    /// The side to move ran out of time.
+    Timeout,
 }
 
-#[derive(Debug, Clone)]
-pub enum UserOutput {
-    CheckMate,
-    StaleMate,
-    InvalidMove,
-    Promotion(Position),
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DrawReason {
+    /// Claimed after fifty moves of each side without capture or pawn move.
+    FiftyMoveRule,
+    /// Claimed when the same position occurred three times.
+    ThreefoldRepetition,
+    /// Seventy-five moves of each side without capture or pawn move.
+    SeventyFiveMoveRule,
+    /// The same position occurred five times.
+    FivefoldRepetition,
+    /// A draw offer was accepted.
+    Agreement,
+    /// Neither side has enough pieces left to checkmate.
+    InsufficientMaterial,
+    /// No sequence of legal moves can lead to checkmate, e.g. a pawn wall that
+    /// neither king can break through.
+    DeadPosition,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GameStatus {
+    Ongoing,
+    /// The side to move is in check but has legal moves.
+    Check,
+    /// The given color has won.
+    Checkmate(Color),
+    /// The other side resigned, the given color has won.
+    Resignation(Color),
+    /// The other side ran out of time, the given color has won.
+    Timeout(Color),
+    /// The given color has won by the rules of the variant, e.g. by losing all
+    /// pieces in antichess.
+    VariantWin(Color),
+    Stalemate,
+    Draw(DrawReason),
+    /// The pawn on this square waits for [`UserInput::Promotion`].
+    AwaitingPromotion(Position),
+}
+
+impl GameStatus {
+    #[must_use]
+    pub const fn is_finished(&self) -> bool {
+        matches!(
+            self,
+            GameStatus::Checkmate(_)
+                | GameStatus::Resignation(_)
+                | GameStatus::Timeout(_)
+                | GameStatus::VariantWin(_)
+                | GameStatus::Stalemate
+                | GameStatus::Draw(_)
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GameResult {
+    WhiteWins,
+    BlackWins,
     Draw,
 }
 
+/// How a finished game ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TerminationReason {
+    Checkmate,
+    Resignation,
+    Timeout,
+    /// A win by the rules of the variant.
+    VariantEnd,
+    Stalemate,
+    Draw(DrawReason),
+}
+
+/// Why an input was rejected by [`Game::process_input`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MoveError {
+    /// The square is not on the board.
+    InvalidSquare(Position),
+    NoPieceAtSquare(Position),
+    /// The piece belongs to the side that is not to move.
+    NotYourTurn,
+    /// The piece cannot move to the destination.
+    IllegalDestination,
+    /// The move would leave (or keep) the own king in check.
+    LeavesKingInCheck,
+    /// In antichess a capture has to be played if there is one.
+    CaptureRequired,
+    /// The pawn on this square has to be promoted before the next move.
+    PromotionRequired(Position),
+    /// The promotion piece is not a queen, rook, bishop or knight of the side to
+    /// move, or the move is not a promotion.
+    InvalidPromotion,
+    /// There is no pawn waiting for its promotion on this square.
+    NoPromotionPending(Position),
+    /// Neither threefold repetition nor the fifty-move rule allow a draw claim.
+    NoDrawToClaim,
+    /// The other side has not offered a draw to accept or decline.
+    NoDrawOffer,
+    /// The game is finished, see [`Game::result`].
+    GameOver,
+}
+
+impl fmt::Display for MoveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            MoveError::InvalidSquare(pos) => write!(f, "{pos} is not a square"),
+            MoveError::NoPieceAtSquare(pos) => write!(f, "there is no piece on {pos}"),
+            MoveError::NotYourTurn => write!(f, "it is not the turn of this piece"),
+            MoveError::IllegalDestination => write!(f, "the piece cannot move there"),
+            MoveError::LeavesKingInCheck => write!(f, "the king would be in check"),
+            MoveError::CaptureRequired => write!(f, "a piece has to be captured"),
+            MoveError::PromotionRequired(pos) => {
+                write!(f, "the pawn on {pos} has to be promoted first")
+            }
+            MoveError::InvalidPromotion => write!(f, "this is not a valid promotion"),
+            MoveError::NoPromotionPending(pos) => {
+                write!(f, "there is no pawn to promote on {pos}")
+            }
+            MoveError::NoDrawToClaim => write!(f, "there is no draw to claim"),
+            MoveError::NoDrawOffer => write!(f, "the other side has not offered a draw"),
+            MoveError::GameOver => write!(f, "the game is already over"),
+        }
+    }
+}
+
+impl core::error::Error for MoveError {}
+
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Game {
     pub turn: Color,
+    #[cfg_attr(feature = "serde", serde(with = "serialize::board"))]
     pub board: Board,
     pub captured: [Vec<Piece>; COLOR_COUNT],
-    history: Vec<Move>,
-    number_of_repeated_board_states: HashMap<(Color, Board, Vec<Move>), u8>,
-    number_of_moves_without_captures_or_pawn_moves: u8,
+    history: Vec<PlayedMove>,
+    starting_fen: Option<String>,
+    awaiting_promotion: Option<Position>,
+    variant: Variant,
+    /// Status the game ended with, no input is accepted anymore once it is set.
+    final_status: Option<GameStatus>,
+    /// The side that offered a draw which was not answered yet.
+    draw_offer: Option<Color>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    clock: Option<Clock>,
+    undo_stack: Vec<UndoState>,
+    redo_stack: Vec<RedoMove>,
+    /// Zobrist keys of the positions after each completed move, starting with the initial one.
+    position_keys: Vec<u64>,
+    number_of_moves_without_captures_or_pawn_moves: u16,
+    fullmove_number: u16,
+    en_passant_square: Option<Position>,
     able_to_long_castle: [bool; COLOR_COUNT],
     able_to_short_castle: [bool; COLOR_COUNT],
-    protected_squares: [Vec<Position>; COLOR_COUNT],
-    pieces_attacking_king: [Vec<(Piece, Vec<Position>)>; COLOR_COUNT],
+    /// Zobrist hash of the pieces, the side to move and the castling rights,
+    /// updated with every change of them. See [`Game::zobrist_key`].
+    hash: u64,
+    /// Squares each color attacks or protects, computed on first use as only
+    /// king moves and castling need them.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    protected_squares: Cache<[Vec<Position>; COLOR_COUNT]>,
+    /// Moves of the other color that could capture the king, pins do not matter.
+    pieces_attacking_king: [Vec<Move>; COLOR_COUNT],
+    /// Legal moves of the side to move, generated on first use and reset
+    /// whenever the position changes.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    legal_moves: Cache<Vec<Move>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    #[allow(dead_code)] // only counts the clones
+    clone_counter: CloneCounter,
 }
 
 impl fmt::Display for Game {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        let mut res = String::new();
-        res.push_str("  -");
-        for _ in 1..=16 {
-            res.push_str("--");
-        }
-        res.push('\n');
-
-        for y in ('1'..='8').rev() {
-            res.push_str(format!("{y} | ").as_str());
-            for x in 'a'..='h' {
-                match &self.board[Position(x, y).as_index()] {
-                    None => res.push_str("  | "),
-                    Some(piece) => res.push_str(format!("{piece} | ").as_str()),
-                }
-            }
-            res.push_str("\n".to_string().as_str());
-            res.push_str("  -".to_string().as_str());
-            for _ in 1..=16 {
-                res.push_str("--");
-            }
-            res.push('\n');
-        }
-        res.push_str("    ");
-        for x in 'a'..='h' {
-            res.push_str(format!("{x}   ").as_str());
-        }
-        res.push('\n');
-        write!(f, "{res}")
+        write!(f, "{}", BoardFormatter::default().format(self))
     }
 }
 
@@ -346,6 +693,20 @@ impl Default for Game {
     }
 }
 
+/// A move of the game history together with the state right after it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlayedMove {
+    pub mv: Move,
+    /// Standard algebraic notation including check or mate suffix.
+    pub san: String,
+    /// Zobrist key of the resulting position, see [`Game::zobrist_key`].
+    pub key: u64,
+    pub halfmove_clock: u16,
+    pub fullmove_number: u16,
+    pub annotation: Annotation,
+}
+
 // NOTE: all the public functions are used by the UI
 impl Game {
     #[must_use]
@@ -374,21 +735,6 @@ impl Game {
         let captured = [Vec::new(), Vec::new()];
         let history = Vec::new();
         let able_to_castle = [true, true];
-        let mut protected_squares_white: Vec<Position> = Vec::new();
-        let mut protected_squares_black: Vec<Position> = Vec::new();
-        for x in 'a'..='h' {
-            protected_squares_white.push(Position(x, '3'));
-            protected_squares_white.push(Position(x, '2'));
-            protected_squares_black.push(Position(x, '6'));
-            protected_squares_black.push(Position(x, '7'));
-            if x != 'a' || x != 'h' {
-                protected_squares_white.push(Position(x, '1'));
-                protected_squares_black.push(Position(x, '8'));
-            }
-        }
-
-        let protected_squares = [protected_squares_white, protected_squares_black];
-
         let pieces_attacking_king = [Vec::new(), Vec::new()];
 
         let mut game = Game {
@@ -396,200 +742,632 @@ impl Game {
             board,
             captured,
             history,
-            protected_squares,
+            starting_fen: None,
+            awaiting_promotion: None,
+            variant: Variant::Standard,
+            final_status: None,
+            draw_offer: None,
+            clock: None,
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            hash: 0,
+            protected_squares: Cache::new(),
             pieces_attacking_king,
+            legal_moves: Cache::new(),
+            clone_counter: CloneCounter,
             number_of_moves_without_captures_or_pawn_moves: 0,
-            number_of_repeated_board_states: HashMap::new(),
+            fullmove_number: 1,
+            en_passant_square: None,
+            position_keys: Vec::new(),
             able_to_long_castle: able_to_castle,
             able_to_short_castle: able_to_castle,
         };
 
-        let board = game.board;
-        let all_possible_moves = game.get_all_possible_moves();
-        let key = (game.turn, board, all_possible_moves);
-
-        game.number_of_repeated_board_states.insert(key, 1);
+        game.hash = game.full_hash();
+        game.position_keys.push(game.zobrist_key());
 
         game
     }
 
+    /// Plays the user input and returns the status of the game afterwards.
+    /// Once the game is finished its result is kept, see [`Game::result`].
+    /// With a [`Clock`] and the `std` feature the time since the last input
+    /// is taken from the side to move, see [`Game::process_timed_input`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the move is not legal for the side to move or the
+    /// game is already over.
+    pub fn process_input(&mut self, user_input: &UserInput) -> Result<GameStatus, MoveError> {
+        #[cfg(feature = "std")]
+        if let Some(elapsed) = self.clock.as_mut().map(Clock::lap) {
+            return self.process_timed_input(user_input, elapsed);
+        }
+        self.process_untimed_input(user_input)
+    }
+
+    fn process_untimed_input(&mut self, user_input: &UserInput) -> Result<GameStatus, MoveError> {
+        if self.final_status.is_some() {
+            return Err(MoveError::GameOver);
+        }
+        let status = self.apply_input(user_input)?;
+        if status.is_finished() {
+            self.final_status = Some(status);
+        }
+        Ok(status)
+    }
+
+    /// Like [`Game::process_input`] but also plays on in positions that are
+    /// already decided, e.g. for walking through the move tree.
     #[allow(clippy::too_many_lines)]
-    pub fn process_input(&mut self, user_input: &UserInput) -> Option<UserOutput> {
+    fn apply_input(&mut self, user_input: &UserInput) -> Result<GameStatus, MoveError> {
         match user_input {
             UserInput::Move(from, to) => {
-                match self.get_move_if_valid(*from, *to) {
-                    Some(mv) => {
-                        if mv.piece.piece_type == PieceType::Pawn
-                            && mv.move_type == MoveType::Normal
-                            && (mv.to.1 == '8' || mv.to.1 == '1')
-                        {
-                            // update position
-                            self.board[from.as_index()] = None;
-                            self.board[to.as_index()] = Some(mv.piece);
-                            self.history.push(mv.clone());
-                            return Some(UserOutput::Promotion(mv.to));
-                        }
-
-                        self.turn = self.turn.invert();
-                        // update position
-                        self.board[from.as_index()] = None;
-                        self.board[to.as_index()] = Some(mv.piece);
-
-                        if mv.move_type == MoveType::Enpassant {
-                            let direction = if mv.piece.color == Color::White {
-                                1
-                            } else {
-                                -1
-                            };
-                            self.board[mv.to.add((0, -direction)).as_index()] = None;
-                        }
-                        if mv.move_type == MoveType::LongCastle {
-                            if mv.piece.color == Color::White {
-                                self.board[Position('a', '1').as_index()] = None;
-                                self.board[Position('d', '1').as_index()] =
-                                    Some(Piece::new(PieceType::Rook, Color::White));
-                            } else {
-                                self.board[Position('a', '8').as_index()] = None;
-                                self.board[Position('d', '8').as_index()] =
-                                    Some(Piece::new(PieceType::Rook, Color::Black));
-                            }
-                        }
-                        if mv.move_type == MoveType::ShortCastle {
-                            if mv.piece.color == Color::White {
-                                self.board[Position('h', '1').as_index()] = None;
-                                self.board[Position('f', '1').as_index()] =
-                                    Some(Piece::new(PieceType::Rook, Color::White));
-                            } else {
-                                self.board[Position('h', '8').as_index()] = None;
-                                self.board[Position('f', '8').as_index()] =
-                                    Some(Piece::new(PieceType::Rook, Color::Black));
-                            }
-                        }
-                        // FIXME: circular relationship in those function. Dirty fix was used by checking bool get_protected when checking if check
-                        //  get_all_protected_squares has to be run before pieces_attacking_king right now
-                        self.protected_squares = self.get_all_protected_squares(true);
-                        self.pieces_attacking_king = self.pieces_attacking_king(true);
-
-                        if let Some(captured_piece) = mv.captured_piece {
-                            self.captured[mv.piece.color as usize].push(captured_piece);
-                        }
-                        if (mv.piece.piece_type == PieceType::King
-                            || mv.piece.piece_type == PieceType::Rook)
-                            && (self.able_to_long_castle[mv.piece.color as usize]
-                                || self.able_to_short_castle[mv.piece.color as usize])
-                        {
-                            if mv.piece.piece_type == PieceType::King {
-                                self.able_to_short_castle[mv.piece.color as usize] = false;
-                                self.able_to_long_castle[mv.piece.color as usize] = false;
-                            } else {
-                                let long_caste_pos: Position = if mv.piece.color == Color::White {
-                                    Position('a', '1')
-                                } else {
-                                    Position('a', '8')
-                                };
-                                let short_caste_pos: Position = if mv.piece.color == Color::White {
-                                    Position('h', '1')
-                                } else {
-                                    Position('h', '8')
-                                };
-                                if mv.from == long_caste_pos {
-                                    self.able_to_long_castle[mv.piece.color as usize] = false;
-                                } else if mv.from == short_caste_pos {
-                                    self.able_to_short_castle[mv.piece.color as usize] = false;
-                                }
-                            }
+                let mv = self.get_move_if_valid(*from, *to)?;
+                self.undo_stack.push(self.undo_state());
+                self.redo_stack.clear();
+                // moving instead of answering declines the offer of the other side
+                if self.draw_offer == Some(self.turn.invert()) {
+                    self.draw_offer = None;
+                }
+                let san = self.san_without_suffix(&mv);
+                let castling_key = self.castling_key();
+                if let Some(captured_piece) = mv.captured_piece {
+                    self.captured[mv.piece.color as usize].push(captured_piece);
+                    // a captured rook cannot castle anymore
+                    let color = captured_piece.color;
+                    if captured_piece.piece_type == PieceType::Rook {
+                        let back_rank = if color == Color::White { '1' } else { '8' };
+                        if mv.to == Position('a', back_rank) {
+                            self.able_to_long_castle[color as usize] = false;
+                        } else if mv.to == Position('h', back_rank) {
+                            self.able_to_short_castle[color as usize] = false;
                         }
-
-                        if !(mv.captured_piece.is_some() || mv.piece.piece_type == PieceType::Pawn)
-                        {
-                            self.number_of_moves_without_captures_or_pawn_moves += 1;
+                    }
+                }
+                if (mv.piece.piece_type == PieceType::King
+                    || mv.piece.piece_type == PieceType::Rook)
+                    && (self.able_to_long_castle[mv.piece.color as usize]
+                        || self.able_to_short_castle[mv.piece.color as usize])
+                {
+                    if mv.piece.piece_type == PieceType::King {
+                        self.able_to_short_castle[mv.piece.color as usize] = false;
+                        self.able_to_long_castle[mv.piece.color as usize] = false;
+                    } else {
+                        let long_caste_pos: Position = if mv.piece.color == Color::White {
+                            Position('a', '1')
+                        } else {
+                            Position('a', '8')
+                        };
+                        let short_caste_pos: Position = if mv.piece.color == Color::White {
+                            Position('h', '1')
                         } else {
-                            self.number_of_moves_without_captures_or_pawn_moves = 0;
+                            Position('h', '8')
+                        };
+                        if mv.from == long_caste_pos {
+                            self.able_to_long_castle[mv.piece.color as usize] = false;
+                        } else if mv.from == short_caste_pos {
+                            self.able_to_short_castle[mv.piece.color as usize] = false;
                         }
+                    }
+                }
 
-                        self.history.push(mv);
+                self.hash ^= castling_key ^ self.castling_key();
 
-                        let board = self.board;
-                        let all_possible_moves = self.get_all_possible_moves();
+                if !(mv.captured_piece.is_some() || mv.piece.piece_type == PieceType::Pawn) {
+                    self.number_of_moves_without_captures_or_pawn_moves = self
+                        .number_of_moves_without_captures_or_pawn_moves
+                        .saturating_add(1);
+                } else {
+                    self.number_of_moves_without_captures_or_pawn_moves = 0;
+                }
 
-                        let key = (self.turn, board, all_possible_moves);
-                        if self.number_of_repeated_board_states.contains_key(&key) {
-                            let num_pos = self.number_of_repeated_board_states[&key];
-                            self.number_of_repeated_board_states
-                                .insert(key, num_pos + 1);
-                        } else {
-                            self.number_of_repeated_board_states.insert(key, 1);
-                        }
+                self.en_passant_square = if mv.piece.piece_type == PieceType::Pawn
+                    && (mv.from.1 as i8 - mv.to.1 as i8).abs() == 2
+                {
+                    // the square the pawn skipped over
+                    Some(Position(
+                        mv.to.0,
+                        ((mv.from.1 as u8 + mv.to.1 as u8) / 2) as char,
+                    ))
+                } else {
+                    None
+                };
 
-                        if self.no_possible_moves(self.turn) {
-                            return if self.check(self.turn) {
-                                Some(UserOutput::CheckMate)
-                            } else {
-                                Some(UserOutput::StaleMate)
-                            };
-                        }
+                if mv.is_promotion() {
+                    // update position
+                    self.set_square(*from, None);
+                    self.set_square(*to, Some(mv.piece));
+                    // completed with the promotion piece once it is chosen
+                    self.push_history(mv, san);
+                    self.clear_caches();
+                    self.awaiting_promotion = Some(*to);
+                    return Ok(GameStatus::AwaitingPromotion(*to));
+                }
 
-                        if self.is_a_draw() {
-                            return Some(UserOutput::Draw);
-                        }
+                self.switch_turn();
+                if self.turn == Color::White {
+                    self.fullmove_number += 1;
+                }
+                // update position
+                self.set_square(*from, None);
+                self.set_square(*to, Some(mv.piece));
 
-                        None
+                if mv.move_type == MoveType::Enpassant {
+                    let direction = if mv.piece.color == Color::White {
+                        1
+                    } else {
+                        -1
+                    };
+                    self.set_square(mv.to.add((0, -direction)), None);
+                }
+                if mv.move_type == MoveType::LongCastle {
+                    if mv.piece.color == Color::White {
+                        self.set_square(Position('a', '1'), None);
+                        self.set_square(
+                            Position('d', '1'),
+                            Some(Piece::new(PieceType::Rook, Color::White)),
+                        );
+                    } else {
+                        self.set_square(Position('a', '8'), None);
+                        self.set_square(
+                            Position('d', '8'),
+                            Some(Piece::new(PieceType::Rook, Color::Black)),
+                        );
                     }
-                    None => Some(UserOutput::InvalidMove),
                 }
+                if mv.move_type == MoveType::ShortCastle {
+                    if mv.piece.color == Color::White {
+                        self.set_square(Position('h', '1'), None);
+                        self.set_square(
+                            Position('f', '1'),
+                            Some(Piece::new(PieceType::Rook, Color::White)),
+                        );
+                    } else {
+                        self.set_square(Position('h', '8'), None);
+                        self.set_square(
+                            Position('f', '8'),
+                            Some(Piece::new(PieceType::Rook, Color::Black)),
+                        );
+                    }
+                }
+                self.update_attacks();
+
+                self.position_keys.push(self.zobrist_key());
+                let status = self.status();
+                let san = san + self.san_suffix(matches!(status, GameStatus::Checkmate(_)));
+                self.push_history(mv, san);
+                Ok(status)
+            }
+            UserInput::MoveWithPromotion(from, to, piece_type) => {
+                let mv = self.get_move_if_valid(*from, *to)?;
+                if !mv.is_promotion() || !self.promotion_piece_types().contains(piece_type) {
+                    return Err(MoveError::InvalidPromotion);
+                }
+                let piece = Piece::new(*piece_type, mv.piece.color);
+                self.apply_input(&UserInput::Move(*from, *to))?;
+                self.apply_input(&UserInput::Promotion(piece, *to))
             }
             UserInput::Promotion(piece, pos) => {
-                self.turn = self.turn.invert();
-                self.board[pos.as_index()] = Some(*piece);
-
-                // FIXME: circular relationship in those function. Dirty fix was used by checking bool get_protected when checking if check
-                //  get_all_protected_squares has to be run before pieces_attacking_king right now
-                self.protected_squares = self.get_all_protected_squares(true);
-                self.pieces_attacking_king = self.pieces_attacking_king(true);
-
-                if self.no_possible_moves(self.turn) {
-                    return if self.check(self.turn) {
-                        Some(UserOutput::CheckMate)
-                    } else {
-                        Some(UserOutput::StaleMate)
-                    };
+                if self.awaiting_promotion != Some(*pos) {
+                    return Err(MoveError::NoPromotionPending(*pos));
+                }
+                if piece.color != self.turn
+                    || !self.promotion_piece_types().contains(&piece.piece_type)
+                {
+                    return Err(MoveError::InvalidPromotion);
+                }
+                self.awaiting_promotion = None;
+                self.switch_turn();
+                if self.turn == Color::White {
+                    self.fullmove_number += 1;
+                }
+                self.set_square(*pos, Some(*piece));
+
+                self.update_attacks();
+                self.position_keys.push(self.zobrist_key());
+
+                let status = self.status();
+                let suffix = self.san_suffix(matches!(status, GameStatus::Checkmate(_)));
+                let key = self.zobrist_key();
+                let fullmove_number = self.fullmove_number;
+                if let Some(played) = self.history.last_mut() {
+                    played.mv.promotion = Some(piece.piece_type);
+                    played.san.push('=');
+                    played.san.push(piece_type_to_san_char(piece.piece_type));
+                    played.san.push_str(suffix);
+                    played.key = key;
+                    played.fullmove_number = fullmove_number;
                 }
 
-                None
+                Ok(status)
+            }
+            UserInput::ClaimDraw => {
+                let reason = self.claimable_draw().ok_or(MoveError::NoDrawToClaim)?;
+                Ok(GameStatus::Draw(reason))
             }
-            _ => {
-                unreachable!()
+            UserInput::Draw => {
+                self.draw_offer = Some(self.turn);
+                Ok(self.status())
             }
+            UserInput::AcceptDraw => {
+                self.take_draw_offer()?;
+                Ok(GameStatus::Draw(DrawReason::Agreement))
+            }
+            UserInput::DeclineDraw => {
+                self.take_draw_offer()?;
+                Ok(self.status())
+            }
+            UserInput::Resign => Ok(GameStatus::Resignation(self.turn.invert())),
+            UserInput::Timeout => Ok(GameStatus::Timeout(self.turn.invert())),
+        }
+    }
+
+    /// Status of the game in the current position.
+    #[must_use]
+    pub fn status(&self) -> GameStatus {
+        if let Some(status) = self.final_status {
+            return status;
+        }
+        if let Some(pos) = self.awaiting_promotion {
+            return GameStatus::AwaitingPromotion(pos);
+        }
+        if self.variant == Variant::Antichess && self.no_possible_moves(self.turn) {
+            return GameStatus::VariantWin(self.turn);
+        }
+        if self.no_possible_moves(self.turn) {
+            return if self.check(self.turn) {
+                GameStatus::Checkmate(self.turn.invert())
+            } else {
+                GameStatus::Stalemate
+            };
+        }
+        if let Some(reason) = self.draw_reason() {
+            return GameStatus::Draw(reason);
+        }
+        if self.check(self.turn) {
+            GameStatus::Check
+        } else {
+            GameStatus::Ongoing
+        }
+    }
+
+    /// Result of the game, `None` while it is still going on.
+    #[must_use]
+    pub fn result(&self) -> Option<GameResult> {
+        match self.status() {
+            GameStatus::Checkmate(winner)
+            | GameStatus::Resignation(winner)
+            | GameStatus::Timeout(winner)
+            | GameStatus::VariantWin(winner) => Some(if winner == Color::White {
+                GameResult::WhiteWins
+            } else {
+                GameResult::BlackWins
+            }),
+            GameStatus::Stalemate | GameStatus::Draw(_) => Some(GameResult::Draw),
+            GameStatus::Ongoing | GameStatus::Check | GameStatus::AwaitingPromotion(_) => None,
+        }
+    }
+
+    /// How the game ended, `None` while it is still going on.
+    #[must_use]
+    pub fn termination_reason(&self) -> Option<TerminationReason> {
+        match self.status() {
+            GameStatus::Checkmate(_) => Some(TerminationReason::Checkmate),
+            GameStatus::Resignation(_) => Some(TerminationReason::Resignation),
+            GameStatus::Timeout(_) => Some(TerminationReason::Timeout),
+            GameStatus::VariantWin(_) => Some(TerminationReason::VariantEnd),
+            GameStatus::Stalemate => Some(TerminationReason::Stalemate),
+            GameStatus::Draw(reason) => Some(TerminationReason::Draw(reason)),
+            GameStatus::Ongoing | GameStatus::Check | GameStatus::AwaitingPromotion(_) => None,
+        }
+    }
+
+    /// The side that offered a draw which was not accepted or declined yet.
+    #[must_use]
+    pub fn draw_offer(&self) -> Option<Color> {
+        self.draw_offer
+    }
+
+    /// All moves played so far. While a pawn waits for its promotion piece, it is
+    /// the last entry without promotion.
+    #[must_use]
+    pub fn history(&self) -> &[PlayedMove] {
+        &self.history
+    }
+
+    /// Plies since the last capture or pawn move, used for the fifty-move rule.
+    #[must_use]
+    pub const fn halfmove_clock(&self) -> u16 {
+        self.number_of_moves_without_captures_or_pawn_moves
+    }
+
+    /// Number of the current full move, starting at 1 and incremented after
+    /// each move of Black.
+    #[must_use]
+    pub const fn fullmove_number(&self) -> u16 {
+        self.fullmove_number
+    }
+
+    /// Square a pawn skipped with a double step in the last move, whether or not
+    /// it can be captured en passant (as in FEN).
+    #[must_use]
+    pub const fn en_passant_square(&self) -> Option<Position> {
+        self.en_passant_square
+    }
+
+    /// The right to castle short, independent of check or pieces in between.
+    #[must_use]
+    pub const fn can_castle_short(&self, color: Color) -> bool {
+        self.able_to_short_castle[color as usize]
+    }
+
+    /// The right to castle long, independent of check or pieces in between.
+    #[must_use]
+    pub const fn can_castle_long(&self, color: Color) -> bool {
+        self.able_to_long_castle[color as usize]
+    }
+
+    /// Changes the castling rights of `color` in the current position, e.g. from
+    /// a board editor. Earlier positions keep their rights when moves are undone.
+    ///
+    /// # Errors
+    ///
+    /// [`SetupError::InvalidCastlingRights`] if a right is granted without king and
+    /// rook on their initial squares or in antichess.
+    pub fn set_castling_rights(
+        &mut self,
+        color: Color,
+        short: bool,
+        long: bool,
+    ) -> Result<(), SetupError> {
+        let mut game = self.clone();
+        game.able_to_short_castle[color as usize] = short;
+        game.able_to_long_castle[color as usize] = long;
+        if game
+            .validate()
+            .contains(&SetupError::InvalidCastlingRights(color))
+        {
+            return Err(SetupError::InvalidCastlingRights(color));
+        }
+        let castling_key = self.castling_key();
+        self.able_to_short_castle[color as usize] = short;
+        self.able_to_long_castle[color as usize] = long;
+        self.hash ^= castling_key ^ self.castling_key();
+        // the position with other rights is a different one for repetitions
+        let key = self.zobrist_key();
+        if let Some(last_key) = self.position_keys.last_mut() {
+            *last_key = key;
+        }
+        Ok(())
+    }
+
+    /// The draw the side to move can claim with [`UserInput::ClaimDraw`], if any.
+    #[must_use]
+    pub fn claimable_draw(&self) -> Option<DrawReason> {
+        if self.awaiting_promotion.is_some() || self.status().is_finished() {
+            None
+        } else if self.number_of_moves_without_captures_or_pawn_moves >= 100 {
+            Some(DrawReason::FiftyMoveRule)
+        } else if self.repetitions() >= 3 {
+            Some(DrawReason::ThreefoldRepetition)
+        } else {
+            None
         }
     }
 
     #[must_use]
     pub fn get_all_currently_valid_moves(&self) -> Vec<Move> {
-        let all_possible_moves = ALL_POSSIBLE_SQUARES.par_iter().flat_map(|(x, y)| {
-            let mut all_possible_moves = Vec::new();
-            if let Some(piece) = &self.board[Position(*x, *y).as_index()] {
-                if piece.color == self.turn {
-                    all_possible_moves = self.get_valid_moves(Position(*x, *y));
-                }
+        self.legal_moves().to_vec()
+    }
+
+    /// Legal moves of the side to move. They are generated once per position,
+    /// so asking again, e.g. every frame of a UI, costs nothing until the next
+    /// [`Game::process_input`] or [`Game::undo`].
+    #[must_use]
+    pub fn legal_moves(&self) -> &[Move] {
+        self.legal_moves.get_or_init(|| {
+            stats::count_position();
+            if self.is_check() {
+                return self.evasion_moves();
             }
-            all_possible_moves
-        });
-        all_possible_moves.collect()
+            squares()
+                .flat_map(|(x, y)| {
+                    let pos = Position(*x, *y);
+                    match self.board[pos.as_index()] {
+                        Some(piece) if piece.color == self.turn => {
+                            self.legal_moves_from(pos).into()
+                        }
+                        _ => Vec::new(),
+                    }
+                })
+                .collect()
+        })
     }
 
+    /// Moves of the piece on `pos` by the movement rules, whether or not they
+    /// leave the own king in check. Castling already requires that the king does
+    /// not pass attacked squares. Use [`Game::is_legal`] or [`Game::legalize`] to
+    /// check the remaining rules.
+    #[must_use]
+    pub fn pseudo_legal_moves(&self, pos: Position) -> Vec<Move> {
+        let mut moves = MoveList::new();
+        self.pseudo_legal_moves_to(pos, &mut moves);
+        moves.into()
+    }
+
+    /// Appends the moves of [`Game::pseudo_legal_moves`] to `moves`, so the
+    /// moves of several pieces can be collected in one buffer.
+    pub fn pseudo_legal_moves_to(&self, pos: Position, moves: &mut MoveList) {
+        let _timer = stats::timer(Phase::MoveGeneration);
+        self.piece_moves(pos, false, moves);
+    }
+
+    /// A move of [`Game::pseudo_legal_moves`] is legal in the current position:
+    /// it does not leave the own king in check, or in antichess it is a capture
+    /// or no capture is possible.
+    #[must_use]
+    pub fn is_legal(&self, mv: &Move) -> bool {
+        if self.variant == Variant::Antichess {
+            return mv.captured_piece.is_some()
+                || !self.antichess_capture_available(mv.piece.color);
+        }
+        self.resolves_check(mv) && self.piece_is_not_pinned(mv)
+    }
+
+    /// The legal moves of pseudo legal `moves`, see [`Game::is_legal`].
+    #[must_use]
+    pub fn legalize(&self, moves: Vec<Move>) -> Vec<Move> {
+        let mut moves: MoveList = moves.into_iter().collect();
+        self.legalize_in_place(&mut moves);
+        moves.into()
+    }
+
+    /// Legal captures and promotions of `color`, e.g. for a quiescence search.
+    /// Quiet moves are never checked for legality, which makes this cheaper than
+    /// filtering all legal moves.
+    #[must_use]
+    pub fn capture_moves(&self, color: Color) -> Vec<Move> {
+        squares()
+            .flat_map(|(x, y)| {
+                let pos = Position(*x, *y);
+                if !self.board[pos.as_index()].is_some_and(|piece| piece.color == color) {
+                    return Vec::new();
+                }
+                self.pseudo_legal_moves(pos)
+                    .into_iter()
+                    .filter(|mv| {
+                        (mv.captured_piece.is_some() || mv.is_promotion()) && self.is_legal(mv)
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Legal moves of the piece on `pos`, generated when the iterator is first
+    /// advanced.
+    pub fn legal_moves_iter(&self, pos: Position) -> impl Iterator<Item = Move> + '_ {
+        core::iter::once(pos).flat_map(|pos| self.get_valid_moves(pos))
+    }
+
+    /// Legal moves of the side to move, generated piece by piece while iterating.
+    /// Stopping early skips the move generation of the remaining pieces.
+    pub fn all_legal_moves_iter(&self) -> impl Iterator<Item = Move> + '_ {
+        self.legal_moves_of(self.turn)
+    }
+
+    /// Replaces the content of `moves` with the legal moves of the side to move,
+    /// so one buffer can be reused for many positions.
+    pub fn legal_moves_into(&self, moves: &mut Vec<Move>) {
+        moves.clear();
+        moves.extend(self.all_legal_moves_iter());
+    }
+
+    /// Same as [`Game::legal_moves_into`] with a [`MoveList`], which does not
+    /// allocate for positions with up to [`MOVE_LIST_CAPACITY`] moves.
+    pub fn legal_moves_to(&self, moves: &mut MoveList) {
+        stats::count_position();
+        moves.clear();
+        moves.extend(self.all_legal_moves_iter());
+    }
+
+    /// Same as [`Game::get_valid_moves`], but empty for pieces of the side that is
+    /// not to move.
+    #[must_use]
+    pub fn get_valid_moves_for_turn(&self, pos: Position) -> Vec<Move> {
+        match pos.try_as_index().and_then(|index| self.board[index]) {
+            Some(piece) if piece.color == self.turn => self.get_valid_moves(pos),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Legal moves of the piece on `pos`, taken from [`Game::legal_moves`] for
+    /// the side to move.
     #[must_use]
     pub fn get_valid_moves(&self, pos: Position) -> Vec<Move> {
-        self.possible_moves(pos, false, true)
+        match pos.try_as_index().and_then(|index| self.board[index]) {
+            Some(piece) if piece.color == self.turn => self
+                .legal_moves()
+                .iter()
+                .filter(|mv| mv.from == pos)
+                .copied()
+                .collect(),
+            _ => self.legal_moves_from(pos).into(),
+        }
     }
 
     #[inline]
     pub fn check(&self, color: Color) -> bool {
         !self.pieces_attacking_king[color as usize].is_empty()
     }
+
+    /// All pieces on the board with their squares, rank by rank from a1 to h8.
+    pub fn pieces(&self) -> impl Iterator<Item = (Position, Piece)> + '_ {
+        self.board.iter().enumerate().filter_map(|(index, piece)| {
+            let pos = Position::try_from(index).expect("Board index is always valid.");
+            piece.map(|piece| (pos, piece))
+        })
+    }
+
+    /// The pieces of `color` with their squares, see [`Game::pieces`].
+    pub fn pieces_of(&self, color: Color) -> impl Iterator<Item = (Position, Piece)> + '_ {
+        self.pieces().filter(move |(_, piece)| piece.color == color)
+    }
+
+    /// The side to move is in check.
+    #[must_use]
+    pub fn is_check(&self) -> bool {
+        self.check(self.turn)
+    }
+
+    #[must_use]
+    pub fn is_checkmate(&self) -> bool {
+        matches!(self.status(), GameStatus::Checkmate(_))
+    }
+
+    #[must_use]
+    pub fn is_stalemate(&self) -> bool {
+        self.status() == GameStatus::Stalemate
+    }
+
+    /// The game ended in a draw of any kind, including stalemate.
+    #[must_use]
+    pub fn is_draw(&self) -> bool {
+        self.result() == Some(GameResult::Draw)
+    }
 }
 
 // NOTE: all the private functions are used by the game logic
 impl Game {
+    fn push_history(&mut self, mv: Move, san: String) {
+        self.history.push(PlayedMove {
+            mv,
+            san,
+            key: self.zobrist_key(),
+            halfmove_clock: self.number_of_moves_without_captures_or_pawn_moves,
+            fullmove_number: self.fullmove_number,
+            annotation: Annotation::default(),
+        });
+    }
+
+    /// Recomputes the pieces giving check and resets the values computed on
+    /// first use. Antichess has no check.
+    fn update_attacks(&mut self) {
+        let _timer = stats::timer(Phase::Attacks);
+        self.clear_caches();
+        self.pieces_attacking_king = if self.variant == Variant::Antichess {
+            [Vec::new(), Vec::new()]
+        } else {
+            self.pieces_attacking_king()
+        };
+    }
+
+    /// Has to be called whenever the board changes.
+    fn clear_caches(&mut self) {
+        self.legal_moves = Cache::new();
+        self.protected_squares = Cache::new();
+    }
+
     fn obstacles_in_one_move(&self, pos: Position) -> Option<Obstacle> {
         let Some(index) = pos.try_as_index() else {
             return Some(Obstacle::OutOfBoundary);
@@ -641,32 +1419,30 @@ impl Game {
         }
     }
 
-    fn get_all_protected_squares(&self, filter_pinned: bool) -> [Vec<Position>; COLOR_COUNT] {
-        let protected_squares_white = Mutex::new(Vec::new());
-        let protected_squares_black = Mutex::new(Vec::new());
-        protected_squares_white.lock().unwrap().reserve(64);
-        protected_squares_black.lock().unwrap().reserve(64);
-        ALL_POSSIBLE_SQUARES.par_iter().for_each(|(x, y)| {
-            if let Some(piece) = &self.board[Position(*x, *y).as_index()] {
-                let possible_moves = self.possible_moves(Position(*x, *y), true, filter_pinned);
-                for m in possible_moves {
-                    if piece.color == Color::White {
-                        protected_squares_white.lock().unwrap().push(m.to);
-                    } else {
-                        protected_squares_black.lock().unwrap().push(m.to);
-                    }
-                }
+    /// Antichess generates all moves as if nothing was protected.
+    fn protected_squares(&self) -> &[Vec<Position>; COLOR_COUNT] {
+        self.protected_squares.get_or_init(|| {
+            if self.variant == Variant::Antichess {
+                [Vec::new(), Vec::new()]
+            } else {
+                self.get_all_protected_squares()
             }
-        });
+        })
+    }
 
-        [
-            protected_squares_white.into_inner().unwrap(),
-            protected_squares_black.into_inner().unwrap(),
-        ]
+    fn get_all_protected_squares(&self) -> [Vec<Position>; COLOR_COUNT] {
+        let mut protected_squares = [Vec::with_capacity(64), Vec::with_capacity(64)];
+        let mut moves = MoveList::new();
+        for (pos, piece) in self.pieces() {
+            moves.clear();
+            self.piece_moves(pos, true, &mut moves);
+            protected_squares[piece.color as usize].extend(moves.iter().map(|m| m.to));
+        }
+        protected_squares
     }
 
     fn pos_protected(&self, pos: Position, color: Color) -> bool {
-        for pos_protected in self.protected_squares[color as usize].iter() {
+        for pos_protected in self.protected_squares()[color as usize].iter() {
             if pos == *pos_protected {
                 return true;
             }
@@ -681,10 +1457,8 @@ impl Game {
         pos: Position,
         piece: Piece,
         get_protected: bool,
-    ) -> Vec<Move> {
-        let mut moves = Vec::new();
-
-        let mut traversed_squares = vec![pos];
+        moves: &mut MoveList,
+    ) {
         for (x, y) in x_path.iter().zip(y_path) {
             let new_pos = pos.add((*x, *y));
             let obstacle = self.obstacles_in_one_move(new_pos);
@@ -694,14 +1468,13 @@ impl Game {
                     Obstacle::Piece(obstacle_color)
                         if get_protected || obstacle_color != piece.color =>
                     {
-                        traversed_squares.push(new_pos);
                         moves.push(Move {
                             piece,
                             move_type: MoveType::Normal,
                             from: pos,
                             to: new_pos,
-                            traversed_squares: traversed_squares.clone(),
                             captured_piece: self.board[new_pos.as_index()],
+                            promotion: None,
                         });
                     }
                     _ => {}
@@ -710,17 +1483,15 @@ impl Game {
                 break;
             }
 
-            traversed_squares.push(new_pos);
             moves.push(Move {
                 piece,
                 move_type: MoveType::Normal,
                 from: pos,
                 to: new_pos,
-                traversed_squares: traversed_squares.clone(),
                 captured_piece: self.board[new_pos.as_index()],
+                promotion: None,
             });
         }
-        moves
     }
 
     fn possible_horizontal_vertical_moves(
@@ -728,13 +1499,11 @@ impl Game {
         pos: Position,
         piece: Piece,
         get_protected: bool,
-    ) -> Vec<Move> {
-        HORIZONTAL_DIRECTIONS
-            .par_iter() // Convert to a parallel iterator
-            .flat_map(|(x_range, y_range)| {
-                self.get_moves_in_one_direction(x_range, y_range, pos, piece, get_protected)
-            })
-            .collect()
+        moves: &mut MoveList,
+    ) {
+        for (x_range, y_range) in &HORIZONTAL_DIRECTIONS {
+            self.get_moves_in_one_direction(x_range, y_range, pos, piece, get_protected, moves);
+        }
     }
 
     fn possible_diagonal_moves(
@@ -742,66 +1511,74 @@ impl Game {
         pos: Position,
         piece: Piece,
         get_protected: bool,
-    ) -> Vec<Move> {
-        DIAGONAL_DIRECTIONS
-            .par_iter() // Convert to a parallel iterator
-            .flat_map(|(x_range, y_range)| {
-                self.get_moves_in_one_direction(x_range, y_range, pos, piece, get_protected)
-            })
-            .collect()
+        moves: &mut MoveList,
+    ) {
+        for (x_range, y_range) in &DIAGONAL_DIRECTIONS {
+            self.get_moves_in_one_direction(x_range, y_range, pos, piece, get_protected, moves);
+        }
     }
 
-    fn pieces_attacking_king(
-        &self,
-        filter_pinned: bool,
-    ) -> [Vec<(Piece, Vec<Position>)>; COLOR_COUNT] {
-        let pieces_attacking_white = Mutex::new(Vec::new());
-        let pieces_attacking_black = Mutex::new(Vec::new());
-        pieces_attacking_white.lock().unwrap().reserve(16);
-        pieces_attacking_black.lock().unwrap().reserve(16);
-        ALL_POSSIBLE_SQUARES.par_iter().for_each(|(x, y)| {
-            let moves = self.possible_moves(Position(*x, *y), false, filter_pinned);
-            for mv in moves {
-                if let Some(piece) = mv.captured_piece {
-                    if piece.piece_type == PieceType::King {
-                        if mv.piece.color == Color::White {
-                            pieces_attacking_black
-                                .lock()
-                                .unwrap()
-                                .push((mv.piece, mv.traversed_squares));
-                        } else {
-                            pieces_attacking_white
-                                .lock()
-                                .unwrap()
-                                .push((mv.piece, mv.traversed_squares));
-                        }
-                    }
+    /// Pieces that could capture the king of the other color, pins do not matter.
+    /// Uses the protected squares of each piece, which neither need the
+    /// protected squares of the other color nor castling.
+    fn pieces_attacking_king(&self) -> [Vec<Move>; COLOR_COUNT] {
+        let mut pieces_attacking_king = [Vec::new(), Vec::new()];
+        let mut moves = MoveList::new();
+        for (pos, _) in self.pieces() {
+            moves.clear();
+            self.piece_moves(pos, true, &mut moves);
+            for mv in &moves {
+                if mv.captured_piece.is_some_and(|piece| {
+                    piece.piece_type == PieceType::King && piece.color != mv.piece.color
+                }) {
+                    pieces_attacking_king[mv.piece.color.invert() as usize].push(*mv);
                 }
             }
-        });
+        }
+        pieces_attacking_king
+    }
 
-        [
-            pieces_attacking_white.into_inner().unwrap(),
-            pieces_attacking_black.into_inner().unwrap(),
-        ]
+    /// Uses the cached moves if there are any, otherwise generates them lazily
+    /// piece by piece.
+    fn legal_moves_of(&self, color: Color) -> impl Iterator<Item = Move> + '_ {
+        let cached = self.legal_moves.get().filter(|_| color == self.turn);
+        let generated = self
+            .pieces_of(color)
+            .filter(move |_| cached.is_none())
+            .flat_map(|(pos, _)| self.legal_moves_from(pos));
+        cached.into_iter().flatten().copied().chain(generated)
     }
 
-    fn no_possible_moves(&self, color: Color) -> bool {
-        for x in 'a'..='h' {
-            for y in '1'..='8' {
-                let moves = self.possible_moves(Position(x, y), false, true);
-                if !moves.is_empty() && moves[0].piece.color == color {
-                    return false;
-                }
-            }
+    /// Generates the legal moves of the piece on `pos` without the cache.
+    fn legal_moves_from(&self, pos: Position) -> MoveList {
+        let mut moves = MoveList::new();
+        self.pseudo_legal_moves_to(pos, &mut moves);
+        self.legalize_in_place(&mut moves);
+        moves
+    }
+
+    /// Keeps only the legal moves of the pseudo legal `moves`.
+    fn legalize_in_place(&self, moves: &mut MoveList) {
+        let _timer = stats::timer(Phase::Legality);
+        if self.variant == Variant::Antichess {
+            self.antichess_legalize(moves);
+        } else {
+            moves.retain(|mv| self.is_legal(mv));
         }
-        true
     }
 
-    fn possbile_pawn_moves(&self, pos: Position, piece: Piece, get_protected: bool) -> Vec<Move> {
-        debug_assert_eq!(piece.piece_type, PieceType::Pawn);
+    fn no_possible_moves(&self, color: Color) -> bool {
+        self.legal_moves_of(color).next().is_none()
+    }
 
-        let mut moves = Vec::new();
+    fn possbile_pawn_moves(
+        &self,
+        pos: Position,
+        piece: Piece,
+        get_protected: bool,
+        moves: &mut MoveList,
+    ) {
+        debug_assert_eq!(piece.piece_type, PieceType::Pawn);
 
         let (direction, rel_pos_to_iter) = if piece.color == Color::White {
             (1, if pos.1 == '2' { 1..3 } else { 1..2 })
@@ -820,8 +1597,8 @@ impl Game {
                             move_type: MoveType::Normal,
                             from: pos,
                             to: new_pos,
-                            traversed_squares: vec![pos, new_pos],
                             captured_piece: self.board[new_pos.as_index()],
+                            promotion: None,
                         });
                     }
                     _ => {
@@ -832,163 +1609,103 @@ impl Game {
         }
 
         // check if able to capture a piece
-        for new_pos_int in [(1, direction), (-1, direction)] {
-            let new_pos = pos.add(new_pos_int);
-            match self.obstacles_in_one_move(new_pos) {
-                Some(Obstacle::Piece(other_piece_color)) => {
-                    if get_protected || other_piece_color != piece.color {
-                        moves.push(Move {
-                            piece,
-                            move_type: MoveType::Normal,
-                            from: pos,
-                            to: new_pos,
-                            traversed_squares: vec![pos, new_pos],
-                            captured_piece: self.board[new_pos.as_index()],
-                        });
-                    }
-                }
-                None => {
-                    if get_protected {
-                        moves.push(Move {
-                            piece,
-                            move_type: MoveType::Normal,
-                            from: pos,
-                            to: new_pos,
-                            traversed_squares: vec![pos, new_pos],
-                            captured_piece: self.board[new_pos.as_index()],
-                        });
-                    }
-                }
-                Some(Obstacle::OutOfBoundary) => {} // do nothing
+        for new_pos in pawn_attacks(piece.color, pos.as_index()).iter() {
+            let captured_piece = self.board[new_pos.as_index()];
+            let can_capture = captured_piece.is_some_and(|other| other.color != piece.color);
+            if get_protected || can_capture {
+                moves.push(Move {
+                    piece,
+                    move_type: MoveType::Normal,
+                    from: pos,
+                    to: new_pos,
+                    captured_piece,
+                    promotion: None,
+                });
             }
         }
 
         // Enpassant
         if !get_protected {
-            if let Some(last_move) = self.history.last() {
-                if last_move.piece.piece_type == PieceType::Pawn
-                    && (last_move.from.1 as i8 - last_move.to.1 as i8).abs() == 2
-                    && (last_move.to == pos.add((1, 0)) || last_move.to == pos.add((-1, 0)))
+            if let Some(en_passant_square) = self.en_passant_square {
+                // only pawns of the side that did not double step can take
+                let capture_rank = if piece.color == Color::White {
+                    '6'
+                } else {
+                    '3'
+                };
+                if en_passant_square.1 == capture_rank
+                    && (en_passant_square == pos.add((1, direction))
+                        || en_passant_square == pos.add((-1, direction)))
                 {
-                    let new_pos = if last_move.to == pos.add((1, 0)) {
-                        pos.add((1, direction))
-                    } else {
-                        pos.add((-1, direction))
-                    };
                     moves.push(Move {
                         piece,
                         move_type: MoveType::Enpassant,
                         from: pos,
-                        to: new_pos,
-                        traversed_squares: vec![pos, new_pos],
-                        captured_piece: self.board[new_pos.add((0, -direction)).as_index()],
+                        to: en_passant_square,
+                        captured_piece: self.board
+                            [en_passant_square.add((0, -direction)).as_index()],
+                        promotion: None,
                     });
                 }
             }
         }
-
-        moves
     }
 
-    fn possible_knight_moves(&self, pos: Position, piece: Piece, get_protected: bool) -> Vec<Move> {
-        let mut moves = Vec::new();
-        let dxdys: [(i8, i8); 8] = [
-            (2, 1),
-            (2, -1),
-            (-2, 1),
-            (-2, -1),
-            (1, 2),
-            (-1, 2),
-            (1, -2),
-            (-1, -2),
-        ];
-
-        for dxdy in dxdys {
-            let new_pos = pos.add(dxdy);
-            match self.obstacles_in_one_move(new_pos) {
-                None => {
-                    moves.push(Move {
-                        piece,
-                        move_type: MoveType::Jump,
-                        from: pos,
-                        to: new_pos,
-                        traversed_squares: vec![pos, new_pos],
-                        captured_piece: self.board[new_pos.as_index()],
-                    });
-                }
-                Some(Obstacle::Piece(obstacle_color)) => {
-                    if get_protected || piece.color != obstacle_color {
-                        moves.push(Move {
-                            piece,
-                            move_type: MoveType::Jump,
-                            from: pos,
-                            to: new_pos,
-                            traversed_squares: vec![pos, new_pos],
-                            captured_piece: self.board[new_pos.as_index()],
-                        });
-                    }
-                }
-                Some(Obstacle::OutOfBoundary) => {
-                    // nothing to do
-                }
+    fn possible_knight_moves(
+        &self,
+        pos: Position,
+        piece: Piece,
+        get_protected: bool,
+        moves: &mut MoveList,
+    ) {
+        for new_pos in knight_attacks(pos.as_index()).iter() {
+            let captured_piece = self.board[new_pos.as_index()];
+            if get_protected || captured_piece.is_none_or(|other| other.color != piece.color) {
+                moves.push(Move {
+                    piece,
+                    move_type: MoveType::Jump,
+                    from: pos,
+                    to: new_pos,
+                    captured_piece,
+                    promotion: None,
+                });
             }
         }
-        moves
     }
 
-    fn possible_queen_moves(&self, pos: Position, piece: Piece, get_protected: bool) -> Vec<Move> {
-        QUEEN_DIRECTIONS
-            .par_iter() // Convert to a parallel iterator
-            .flat_map(|(x_range, y_range)| {
-                self.get_moves_in_one_direction(x_range, y_range, pos, piece, get_protected)
-            })
-            .collect()
+    fn possible_queen_moves(
+        &self,
+        pos: Position,
+        piece: Piece,
+        get_protected: bool,
+        moves: &mut MoveList,
+    ) {
+        for (x_range, y_range) in &QUEEN_DIRECTIONS {
+            self.get_moves_in_one_direction(x_range, y_range, pos, piece, get_protected, moves);
+        }
     }
 
-    fn possible_king_moves(&self, pos: Position, piece: Piece, get_protected: bool) -> Vec<Move> {
-        let mut moves = Vec::with_capacity(8);
-        for (x, y) in [
-            (-1, 1),
-            (0, 1),
-            (1, 1),
-            (1, 0),
-            (1, -1),
-            (0, -1),
-            (-1, -1),
-            (-1, 0),
-        ] {
-            let new_pos = pos.add((x, y));
-            match self.obstacles_in_one_move(new_pos) {
-                None => {
-                    if get_protected || !self.pos_protected(new_pos, piece.color.invert()) {
-                        moves.push(Move {
-                            piece,
-                            move_type: MoveType::Normal,
-                            from: pos,
-                            to: new_pos,
-                            traversed_squares: vec![pos, new_pos],
-                            captured_piece: self.board[new_pos.as_index()],
-                        });
-                    }
-                }
-                Some(Obstacle::Piece(obstacle_color)) => {
-                    if get_protected
-                        || (!self.pos_protected(new_pos, piece.color.invert())
-                            && piece.color != obstacle_color)
-                    {
-                        moves.push(Move {
-                            piece,
-                            move_type: MoveType::Normal,
-                            from: pos,
-                            to: new_pos,
-                            traversed_squares: vec![pos, new_pos],
-                            captured_piece: self.board[new_pos.as_index()],
-                        });
-                    }
-                }
-                Some(Obstacle::OutOfBoundary) => {
-                    // nothing to do
-                }
+    fn possible_king_moves(
+        &self,
+        pos: Position,
+        piece: Piece,
+        get_protected: bool,
+        moves: &mut MoveList,
+    ) {
+        for new_pos in king_attacks(pos.as_index()).iter() {
+            let captured_piece = self.board[new_pos.as_index()];
+            if get_protected
+                || (captured_piece.is_none_or(|other| other.color != piece.color)
+                    && !self.pos_protected(new_pos, piece.color.invert()))
+            {
+                moves.push(Move {
+                    piece,
+                    move_type: MoveType::Normal,
+                    from: pos,
+                    to: new_pos,
+                    captured_piece,
+                    promotion: None,
+                });
             }
         }
 
@@ -998,8 +1715,8 @@ impl Game {
                 move_type: MoveType::LongCastle,
                 from: pos,
                 to: pos.add((-2, 0)),
-                traversed_squares: vec![pos, pos.add((-1, 0)), pos.add((-2, 0))],
                 captured_piece: self.board[pos.add((-2, 0)).as_index()],
+                promotion: None,
             });
         }
         if !get_protected && self.can_short_castle(piece.color) {
@@ -1008,132 +1725,172 @@ impl Game {
                 move_type: MoveType::ShortCastle,
                 from: pos,
                 to: pos.add((2, 0)),
-                traversed_squares: vec![pos, pos.add((1, 0)), pos.add((2, 0))],
                 captured_piece: self.board[pos.add((2, 0)).as_index()],
+                promotion: None,
             });
         }
-        moves
     }
 
-    fn possible_moves(&self, pos: Position, get_protected: bool, filter_pinned: bool) -> Vec<Move> {
+    /// Moves of the piece on `pos` by the movement rules. With `get_protected`
+    /// the squares the piece protects, also if an own piece stands there.
+    fn piece_moves(&self, pos: Position, get_protected: bool, moves: &mut MoveList) {
         let Some(piece) = self.board[pos.as_index()] else {
             // no piece there -> no moves
-            return Vec::new();
+            return;
         };
+        let count = moves.len();
+        match piece.piece_type {
+            PieceType::King => self.possible_king_moves(pos, piece, get_protected, moves),
 
-        if !get_protected
-            && self.check(piece.color)
-            && self.pieces_attacking_king[piece.color as usize].len() > 1
-            && piece.piece_type != PieceType::King
-        {
-            // double check: only king can move
-            return Vec::new();
-        }
-
-        let mut moves = match piece.piece_type {
-            PieceType::King => self.possible_king_moves(pos, piece, get_protected),
+            PieceType::Queen => self.possible_queen_moves(pos, piece, get_protected, moves),
 
-            PieceType::Queen => self.possible_queen_moves(pos, piece, get_protected),
-
-            PieceType::Rook => self.possible_horizontal_vertical_moves(pos, piece, get_protected),
-
-            PieceType::Bishop => self.possible_diagonal_moves(pos, piece, get_protected),
-
-            PieceType::Knight => self.possible_knight_moves(pos, piece, get_protected),
+            PieceType::Rook => {
+                self.possible_horizontal_vertical_moves(pos, piece, get_protected, moves)
+            }
 
-            PieceType::Pawn => self.possbile_pawn_moves(pos, piece, get_protected),
-        };
+            PieceType::Bishop => self.possible_diagonal_moves(pos, piece, get_protected, moves),
 
-        if !get_protected && self.check(piece.color) && piece.piece_type != PieceType::King {
-            // filter out moves that do not protect the king. Only for pieces other than the king.
-            debug_assert_eq!(
-                1,
-                self.pieces_attacking_king[piece.color as usize].len(),
-                "More than one piece attacking the king"
-            );
-            let (_, pos) = self.pieces_attacking_king[piece.color as usize][0].clone();
-            moves = moves
-                .into_par_iter()
-                .filter(|x| pos.contains(&x.to))
-                .collect();
-        }
+            PieceType::Knight => self.possible_knight_moves(pos, piece, get_protected, moves),
 
-        if filter_pinned {
-            moves = moves
-                .into_par_iter()
-                .filter(|x| self.piece_is_not_pinned(x))
-                .collect();
+            PieceType::Pawn => self.possbile_pawn_moves(pos, piece, get_protected, moves),
         }
-
-        moves
+        stats::count_moves(moves.len() - count);
     }
 
+    /// Whether the own king is safe after the move. Plays it on a copy of the
+    /// board only and looks for attackers of the king from there.
     fn piece_is_not_pinned(&self, mv: &Move) -> bool {
         // NOTE: We also consider the King here such that he does not move into a check
         // for example when the King moves in the same direction as the line of attack of a Rook
-        let mut game_after_move = self.clone();
-        game_after_move.turn = game_after_move.turn.invert();
-        // update position
-        game_after_move.board[mv.from.as_index()] = None;
-        game_after_move.board[mv.to.as_index()] = Some(mv.piece);
+        let mut board = self.board;
+        board[mv.from.as_index()] = None;
+        board[mv.to.as_index()] = Some(mv.piece);
         if mv.move_type == MoveType::Enpassant {
             let direction = if mv.piece.color == Color::White {
                 1
             } else {
                 -1
             };
-            game_after_move.board[mv.to.add((0, -direction)).as_index()] = None;
+            board[mv.to.add((0, -direction)).as_index()] = None;
         }
-        game_after_move.protected_squares = game_after_move.get_all_protected_squares(false);
-        game_after_move.pieces_attacking_king = game_after_move.pieces_attacking_king(false);
-        game_after_move.pieces_attacking_king[mv.piece.color as usize].is_empty()
-    }
-
-    fn get_move_if_valid(&self, from: Position, to: Position) -> Option<Move> {
-        match self.obstacles_in_one_move(from) {
-            Some(Obstacle::Piece(piece_color)) => {
-                if self.turn == piece_color {
-                    let matching_moves: Vec<Move> = self
-                        .possible_moves(from, false, true)
-                        .into_par_iter()
-                        .filter(|x| x.to == to)
-                        .collect();
-                    if matching_moves.is_empty() {
-                        None
-                    } else {
-                        debug_assert_eq!(1, matching_moves.len());
-                        Some(matching_moves[0].clone())
-                    }
-                } else {
-                    None
-                }
+        let king = Piece::new(PieceType::King, mv.piece.color);
+        let Some(king_index) = board.iter().position(|piece| *piece == Some(king)) else {
+            return true;
+        };
+        let king_pos = Position::try_from(king_index).expect("index is on the board");
+        !attacks::square_attacked(&board, king_pos, mv.piece.color.invert())
+    }
+
+    fn get_move_if_valid(&self, from: Position, to: Position) -> Result<Move, MoveError> {
+        if let Some(pos) = self.awaiting_promotion {
+            return Err(MoveError::PromotionRequired(pos));
+        }
+        for pos in [from, to] {
+            if pos.try_as_index().is_none() {
+                return Err(MoveError::InvalidSquare(pos));
             }
-            _ => None,
+        }
+        let Some(piece) = self.board[from.as_index()] else {
+            return Err(MoveError::NoPieceAtSquare(from));
+        };
+        if piece.color != self.turn {
+            return Err(MoveError::NotYourTurn);
+        }
+
+        let matching_moves: Vec<Move> = self
+            .get_valid_moves(from)
+            .into_iter()
+            .filter(|x| x.to == to)
+            .collect();
+        if let Some(mv) = matching_moves.first() {
+            debug_assert_eq!(1, matching_moves.len());
+            Ok(*mv)
+        } else if self.variant == Variant::Antichess {
+            if self.pseudo_legal_moves(from).iter().any(|mv| mv.to == to) {
+                Err(MoveError::CaptureRequired)
+            } else {
+                Err(MoveError::IllegalDestination)
+            }
+        } else if self.reachable_ignoring_king_safety(from, to) {
+            Err(MoveError::LeavesKingInCheck)
+        } else {
+            Err(MoveError::IllegalDestination)
         }
     }
 
-    fn get_all_possible_moves(&self) -> Vec<Move> {
-        let all_possible_moves = ALL_POSSIBLE_SQUARES.par_iter().flat_map(|(x, y)| {
-            let mut all_possible_moves = Vec::new();
-            if self.board[Position(*x, *y).as_index()].is_some() {
-                all_possible_moves = self.possible_moves(Position(*x, *y), true, true)
+    /// Whether the piece on `from` could go to `to` if the safety of its own king
+    /// was not taken into account. Only used to explain why a move is invalid.
+    fn reachable_ignoring_king_safety(&self, from: Position, to: Position) -> bool {
+        let mut game = self.clone();
+        game.pieces_attacking_king[self.turn as usize].clear();
+        let mut protected_squares = self.protected_squares().clone();
+        protected_squares[self.turn.invert() as usize].clear();
+        game.protected_squares = Cache::from(protected_squares);
+        game.pseudo_legal_moves(from).iter().any(|mv| mv.to == to)
+    }
+
+    /// How many times the current position occurred, counting itself. Only
+    /// positions since the last capture or pawn move can be the same.
+    fn repetitions(&self) -> usize {
+        let Some(key) = self.position_keys.last() else {
+            return 0;
+        };
+        self.position_keys
+            .iter()
+            .rev()
+            .take(self.number_of_moves_without_captures_or_pawn_moves as usize + 1)
+            .filter(|other| *other == key)
+            .count()
+    }
+
+    /// King against king, king and a minor piece against king, or only bishops
+    /// besides the kings that all stand on squares of the same color.
+    fn insufficient_material(&self) -> bool {
+        let mut knights = 0;
+        let mut bishops_on_square_color = [0; COLOR_COUNT];
+        for (index, piece) in self.board.iter().enumerate() {
+            let Some(piece) = piece else {
+                continue;
+            };
+            match piece.piece_type {
+                PieceType::King => {}
+                PieceType::Knight => knights += 1,
+                PieceType::Bishop => {
+                    bishops_on_square_color[(index / BOARD_SIZE + index % BOARD_SIZE) % 2] += 1;
+                }
+                PieceType::Pawn | PieceType::Rook | PieceType::Queen => return false,
             }
-            all_possible_moves
-        });
-        all_possible_moves.collect()
+        }
+        let [dark_square_bishops, light_square_bishops] = bishops_on_square_color;
+        match knights {
+            0 => dark_square_bishops == 0 || light_square_bishops == 0,
+            1 => dark_square_bishops + light_square_bishops == 0,
+            _ => false,
+        }
+    }
+
+    /// Removes the draw offer the side to move answers, the offering side can
+    /// not answer its own.
+    fn take_draw_offer(&mut self) -> Result<(), MoveError> {
+        if self.draw_offer != Some(self.turn.invert()) {
+            return Err(MoveError::NoDrawOffer);
+        }
+        self.draw_offer = None;
+        Ok(())
     }
 
-    fn is_a_draw(&self) -> bool {
-        if self.number_of_moves_without_captures_or_pawn_moves >= 50 {
-            true
+    /// Draws that end the game without a claim.
+    fn draw_reason(&self) -> Option<DrawReason> {
+        if self.variant == Variant::Standard && self.insufficient_material() {
+            Some(DrawReason::InsufficientMaterial)
+        } else if self.variant == Variant::Standard && self.dead_position() {
+            Some(DrawReason::DeadPosition)
+        } else if self.number_of_moves_without_captures_or_pawn_moves >= 150 {
+            Some(DrawReason::SeventyFiveMoveRule)
+        } else if self.repetitions() >= 5 {
+            Some(DrawReason::FivefoldRepetition)
         } else {
-            self.number_of_repeated_board_states
-                .clone()
-                .into_iter()
-                .filter(|(_, num)| *num >= 3)
-                .peekable()
-                .peek()
-                .is_some()
+            None
         }
     }
 }
@@ -0,0 +1,137 @@
+use super::tables::{knight_attacks, pawn_attacks, Targets};
+use super::{Board, Color, Game, MoveList, Piece, PieceType, Position, Variant};
+use alloc::vec::Vec;
+
+const DIRECTIONS: [(i8, i8); 8] = [
+    (0, 1),
+    (0, -1),
+    (1, 0),
+    (-1, 0),
+    (1, 1),
+    (1, -1),
+    (-1, 1),
+    (-1, -1),
+];
+
+/// Whether a piece of color `by` attacks `target` on `board`, without
+/// generating any moves.
+pub(super) fn square_attacked(board: &Board, target: Position, by: Color) -> bool {
+    let piece_on = |squares: Targets, piece_type: PieceType| {
+        squares
+            .iter()
+            .any(|pos| board[pos.as_index()] == Some(Piece::new(piece_type, by)))
+    };
+    // a pawn of `by` attacks the target from where a pawn on the target would attack
+    let target_index = target.as_index();
+    if piece_on(pawn_attacks(by.invert(), target_index), PieceType::Pawn)
+        || piece_on(knight_attacks(target_index), PieceType::Knight)
+    {
+        return true;
+    }
+    DIRECTIONS.iter().any(|direction| {
+        let diagonal = direction.0 != 0 && direction.1 != 0;
+        let slider = if diagonal {
+            PieceType::Bishop
+        } else {
+            PieceType::Rook
+        };
+        let mut pos = target.add(*direction);
+        let mut distance = 1;
+        while let Some(index) = pos.try_as_index() {
+            if let Some(piece) = board[index] {
+                return piece.color == by
+                    && (piece.piece_type == slider
+                        || piece.piece_type == PieceType::Queen
+                        || (piece.piece_type == PieceType::King && distance == 1));
+            }
+            pos = pos.add(*direction);
+            distance += 1;
+        }
+        false
+    })
+}
+
+/// A piece that can not leave the line between its king and an enemy piece.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pin {
+    pub piece: Piece,
+    pub pos: Position,
+    pub pinned_by: Piece,
+    pub pinned_by_pos: Position,
+    /// Squares from next to the king up to the pinning piece, the pinned piece
+    /// can only move on those.
+    pub ray: Vec<Position>,
+}
+
+impl Game {
+    /// Pieces of `color` that attack `pos`, or protect it if a piece of their own
+    /// color stands there. Pinned pieces count as attackers, as they still give
+    /// check.
+    #[must_use]
+    pub fn attackers_of(&self, pos: Position, color: Color) -> Vec<(Piece, Position)> {
+        let mut moves = MoveList::new();
+        self.pieces_of(color)
+            .filter(|(from, _)| {
+                moves.clear();
+                self.piece_moves(*from, true, &mut moves);
+                moves.iter().any(|mv| mv.to == pos)
+            })
+            .map(|(from, piece)| (piece, from))
+            .collect()
+    }
+
+    /// Pieces of `color` that are pinned to their own king.
+    #[must_use]
+    pub fn pinned_pieces(&self, color: Color) -> Vec<Pin> {
+        let king = Piece::new(PieceType::King, color);
+        let Some((king_pos, _)) = self.pieces().find(|(_, piece)| *piece == king) else {
+            return Vec::new();
+        };
+        if self.variant != Variant::Standard {
+            return Vec::new();
+        }
+        DIRECTIONS
+            .iter()
+            .filter_map(|direction| self.pin_in_direction(king_pos, color, *direction))
+            .collect()
+    }
+
+    fn pin_in_direction(
+        &self,
+        king_pos: Position,
+        color: Color,
+        direction: (i8, i8),
+    ) -> Option<Pin> {
+        let diagonal = direction.0 != 0 && direction.1 != 0;
+        let mut pinned: Option<(Piece, Position)> = None;
+        let mut ray = Vec::new();
+        let mut pos = king_pos.add(direction);
+        while let Some(index) = pos.try_as_index() {
+            ray.push(pos);
+            if let Some(piece) = self.board[index] {
+                match pinned {
+                    None if piece.color == color => pinned = Some((piece, pos)),
+                    None => return None,
+                    Some((pinned_piece, pinned_pos)) => {
+                        let slides_here = piece.piece_type == PieceType::Queen
+                            || piece.piece_type
+                                == if diagonal {
+                                    PieceType::Bishop
+                                } else {
+                                    PieceType::Rook
+                                };
+                        return (piece.color != color && slides_here).then_some(Pin {
+                            piece: pinned_piece,
+                            pos: pinned_pos,
+                            pinned_by: piece,
+                            pinned_by_pos: pos,
+                            ray,
+                        });
+                    }
+                }
+            }
+            pos = pos.add(direction);
+        }
+        None
+    }
+}
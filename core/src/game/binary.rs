@@ -0,0 +1,140 @@
+use super::{FenError, Game, PieceType, Position, UserInput, Variant};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Formatter};
+
+/// Version written as the first byte by [`Game::to_bytes`].
+const BINARY_FORMAT_VERSION: u8 = 1;
+
+/// Why bytes could not be read with [`Game::from_bytes`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The bytes end in the middle of a field.
+    UnexpectedEnd,
+    UnsupportedVersion(u8),
+    InvalidVariant(u8),
+    InvalidFen(FenError),
+    /// The move with this index is not legal in the encoded game.
+    InvalidMove(usize),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::UnexpectedEnd => write!(f, "the encoded game is truncated"),
+            DecodeError::UnsupportedVersion(version) => {
+                write!(f, "unsupported binary format version {version}")
+            }
+            DecodeError::InvalidVariant(variant) => write!(f, "unknown variant {variant}"),
+            DecodeError::InvalidFen(e) => write!(f, "invalid starting position: {e}"),
+            DecodeError::InvalidMove(index) => write!(f, "move {} is not legal", index + 1),
+        }
+    }
+}
+
+impl core::error::Error for DecodeError {}
+
+const fn variant_to_byte(variant: Variant) -> u8 {
+    match variant {
+        Variant::Standard => 0,
+        Variant::Antichess => 1,
+    }
+}
+
+const fn promotion_to_bits(promotion: Option<PieceType>) -> u16 {
+    match promotion {
+        None | Some(PieceType::Pawn) => 0,
+        Some(PieceType::Knight) => 1,
+        Some(PieceType::Bishop) => 2,
+        Some(PieceType::Rook) => 3,
+        Some(PieceType::Queen) => 4,
+        Some(PieceType::King) => 5,
+    }
+}
+
+/// Decodes a move written as `from | to << 6 | promotion << 12`, squares by
+/// their index on the board.
+fn decode_move(encoded: u16) -> Option<(Position, Position, Option<PieceType>)> {
+    let from = Position::try_from(usize::from(encoded & 0x3f)).ok()?;
+    let to = Position::try_from(usize::from((encoded >> 6) & 0x3f)).ok()?;
+    let promotion = match encoded >> 12 {
+        0 => None,
+        1 => Some(PieceType::Knight),
+        2 => Some(PieceType::Bishop),
+        3 => Some(PieceType::Rook),
+        4 => Some(PieceType::Queen),
+        5 => Some(PieceType::King),
+        _ => return None,
+    };
+    Some((from, to, promotion))
+}
+
+impl Game {
+    /// Compact encoding of the starting position and the moves of the game,
+    /// small enough to share a game as a link. A result that does not follow
+    /// from the moves, like a resignation, is not kept.
+    ///
+    /// Layout: format version, variant, length of the starting FEN (0 for the
+    /// starting position of the variant), the FEN and two big-endian bytes per
+    /// move.
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let fen = self.starting_fen.as_deref().unwrap_or_default();
+        let mut bytes = Vec::with_capacity(3 + fen.len() + 2 * self.history.len());
+        bytes.push(BINARY_FORMAT_VERSION);
+        bytes.push(variant_to_byte(self.variant));
+        // the piece placement alone has at most 71 characters
+        bytes.push(u8::try_from(fen.len()).expect("FEN is shorter than 256 bytes"));
+        bytes.extend_from_slice(fen.as_bytes());
+        for played in &self.history {
+            let mv = &played.mv;
+            let encoded = mv.from.as_index() as u16
+                | (mv.to.as_index() as u16) << 6
+                | promotion_to_bits(mv.promotion) << 12;
+            bytes.extend_from_slice(&encoded.to_be_bytes());
+        }
+        bytes
+    }
+
+    /// Replays a game encoded with [`Game::to_bytes`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bytes are truncated, come from an unknown format
+    /// version or contain a move that is not legal.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Game, DecodeError> {
+        let [version, variant, fen_len, rest @ ..] = bytes else {
+            return Err(DecodeError::UnexpectedEnd);
+        };
+        if *version != BINARY_FORMAT_VERSION {
+            return Err(DecodeError::UnsupportedVersion(*version));
+        }
+        let variant = match variant {
+            0 => Variant::Standard,
+            1 => Variant::Antichess,
+            _ => return Err(DecodeError::InvalidVariant(*variant)),
+        };
+        let fen_len = usize::from(*fen_len);
+        if rest.len() < fen_len || (rest.len() - fen_len) % 2 != 0 {
+            return Err(DecodeError::UnexpectedEnd);
+        }
+        let (fen, moves) = rest.split_at(fen_len);
+        let mut game = if fen.is_empty() {
+            Game::with_variant(variant)
+        } else {
+            let fen = String::from_utf8_lossy(fen);
+            Game::from_fen_with_variant(&fen, variant).map_err(DecodeError::InvalidFen)?
+        };
+        for (index, encoded) in moves.chunks_exact(2).enumerate() {
+            let (from, to, promotion) = decode_move(u16::from_be_bytes([encoded[0], encoded[1]]))
+                .ok_or(DecodeError::InvalidMove(index))?;
+            let input = match promotion {
+                Some(piece_type) => UserInput::MoveWithPromotion(from, to, piece_type),
+                None => UserInput::Move(from, to),
+            };
+            game.process_input(&input)
+                .map_err(|_| DecodeError::InvalidMove(index))?;
+        }
+        Ok(game)
+    }
+}
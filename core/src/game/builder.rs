@@ -0,0 +1,295 @@
+use super::{
+    Board, Cache, CloneCounter, Color, Game, Piece, PieceType, Position, Variant, COLOR_COUNT,
+    TOTAL_SQUARES,
+};
+use alloc::vec::Vec;
+use core::fmt::{self, Formatter};
+
+/// Why a position set up with a [`BoardBuilder`] can not be played.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SetupError {
+    /// A piece was placed outside of the board.
+    InvalidSquare(Position),
+    /// Each color needs exactly one king (except in antichess).
+    WrongNumberOfKings(Color, usize),
+    PawnOnBackRank(Position),
+    /// King and rook of the color are not on their initial squares.
+    InvalidCastlingRights(Color),
+    /// No pawn can just have moved two squares over the en passant square.
+    InvalidEnPassantSquare(Position),
+    /// The side that is not to move is in check.
+    OpponentInCheck,
+    BothKingsInCheck,
+}
+
+impl fmt::Display for SetupError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SetupError::InvalidSquare(pos) => write!(f, "{pos} is not on the board"),
+            SetupError::WrongNumberOfKings(color, n) => {
+                write!(f, "{color:?} needs exactly one king but has {n}")
+            }
+            SetupError::PawnOnBackRank(pos) => {
+                write!(f, "pawn on {pos} is on a back rank")
+            }
+            SetupError::InvalidCastlingRights(color) => {
+                write!(f, "{color:?} can not castle in this position")
+            }
+            SetupError::InvalidEnPassantSquare(pos) => {
+                write!(f, "invalid en passant square {pos}")
+            }
+            SetupError::OpponentInCheck => write!(f, "the side not to move is in check"),
+            SetupError::BothKingsInCheck => write!(f, "both kings are in check"),
+        }
+    }
+}
+
+impl core::error::Error for SetupError {}
+
+/// Sets up an arbitrary position, starting from an empty board with White to move.
+///
+/// ```
+/// use rusty_chess_core::game::{BoardBuilder, Color, Piece, PieceType, Position};
+///
+/// let game = BoardBuilder::new()
+///     .piece(Position('e', '1'), Piece::new(PieceType::King, Color::White))
+///     .piece(Position('h', '1'), Piece::new(PieceType::Rook, Color::White))
+///     .piece(Position('e', '8'), Piece::new(PieceType::King, Color::Black))
+///     .castling(Color::White, true, false)
+///     .build()
+///     .unwrap();
+/// assert_eq!(game.to_fen(), "4k3/8/8/8/8/8/8/4K2R w K - 0 1");
+/// ```
+#[derive(Debug, Clone)]
+pub struct BoardBuilder {
+    board: Board,
+    invalid_square: Option<Position>,
+    turn: Color,
+    able_to_long_castle: [bool; COLOR_COUNT],
+    able_to_short_castle: [bool; COLOR_COUNT],
+    en_passant_square: Option<Position>,
+    halfmove_clock: u16,
+    fullmove_number: u16,
+    variant: Variant,
+}
+
+impl Default for BoardBuilder {
+    fn default() -> Self {
+        BoardBuilder {
+            board: [None; TOTAL_SQUARES],
+            invalid_square: None,
+            turn: Color::White,
+            able_to_long_castle: [false; COLOR_COUNT],
+            able_to_short_castle: [false; COLOR_COUNT],
+            en_passant_square: None,
+            halfmove_clock: 0,
+            fullmove_number: 1,
+            variant: Variant::Standard,
+        }
+    }
+}
+
+impl BoardBuilder {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces all pieces on the board.
+    #[must_use]
+    pub const fn board(mut self, board: Board) -> Self {
+        self.board = board;
+        self
+    }
+
+    /// Puts `piece` on `pos`, replacing the piece standing there.
+    #[must_use]
+    pub fn piece(mut self, pos: Position, piece: Piece) -> Self {
+        match pos.try_as_index() {
+            Some(index) => self.board[index] = Some(piece),
+            None => self.invalid_square = self.invalid_square.or(Some(pos)),
+        }
+        self
+    }
+
+    #[must_use]
+    pub fn remove(mut self, pos: Position) -> Self {
+        if let Some(index) = pos.try_as_index() {
+            self.board[index] = None;
+        }
+        self
+    }
+
+    #[must_use]
+    pub const fn turn(mut self, color: Color) -> Self {
+        self.turn = color;
+        self
+    }
+
+    #[must_use]
+    pub const fn castling(mut self, color: Color, short: bool, long: bool) -> Self {
+        self.able_to_short_castle[color as usize] = short;
+        self.able_to_long_castle[color as usize] = long;
+        self
+    }
+
+    /// Square a pawn of the side not to move just skipped with a double step.
+    #[must_use]
+    pub const fn en_passant_square(mut self, pos: Option<Position>) -> Self {
+        self.en_passant_square = pos;
+        self
+    }
+
+    #[must_use]
+    pub const fn halfmove_clock(mut self, halfmove_clock: u16) -> Self {
+        self.halfmove_clock = halfmove_clock;
+        self
+    }
+
+    /// Starts at 1, a value of 0 is treated as 1.
+    #[must_use]
+    pub fn fullmove_number(mut self, fullmove_number: u16) -> Self {
+        self.fullmove_number = fullmove_number.max(1);
+        self
+    }
+
+    #[must_use]
+    pub const fn variant(mut self, variant: Variant) -> Self {
+        self.variant = variant;
+        self
+    }
+
+    /// Validates the position and starts a game from it.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first problem found, see [`SetupError`].
+    pub fn build(self) -> Result<Game, SetupError> {
+        if let Some(pos) = self.invalid_square {
+            return Err(SetupError::InvalidSquare(pos));
+        }
+        let game = self.build_unchecked();
+        match game.validate().into_iter().next() {
+            Some(error) => Err(error),
+            None => Ok(game),
+        }
+    }
+
+    /// Starts a game without validating the position.
+    pub(super) fn build_unchecked(self) -> Game {
+        let mut game = Game {
+            turn: self.turn,
+            board: self.board,
+            captured: [Vec::new(), Vec::new()],
+            history: Vec::new(),
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            starting_fen: None,
+            awaiting_promotion: None,
+            variant: self.variant,
+            final_status: None,
+            draw_offer: None,
+            clock: None,
+            position_keys: Vec::new(),
+            number_of_moves_without_captures_or_pawn_moves: self.halfmove_clock,
+            fullmove_number: self.fullmove_number,
+            en_passant_square: self.en_passant_square,
+            able_to_long_castle: self.able_to_long_castle,
+            able_to_short_castle: self.able_to_short_castle,
+            hash: 0,
+            protected_squares: Cache::new(),
+            pieces_attacking_king: [Vec::new(), Vec::new()],
+            legal_moves: Cache::new(),
+            clone_counter: CloneCounter,
+        };
+        game.hash = game.full_hash();
+        game.update_attacks();
+
+        game.position_keys.push(game.zobrist_key());
+        game.starting_fen = Some(game.to_fen());
+        let status = game.status();
+        if status.is_finished() {
+            game.final_status = Some(status);
+        }
+        game
+    }
+}
+
+/// King and rook of `color` stand on their initial squares for castling to the
+/// side of the rook on file `rook_x`.
+pub(super) fn castling_possible(board: &Board, color: Color, rook_x: char) -> bool {
+    let y = if color == Color::White { '1' } else { '8' };
+    board[Position('e', y).as_index()] == Some(Piece::new(PieceType::King, color))
+        && board[Position(rook_x, y).as_index()] == Some(Piece::new(PieceType::Rook, color))
+}
+
+/// The pawn that just moved two squares over `pos` stands in front of it and
+/// the squares it passed are empty.
+pub(super) fn en_passant_square_possible(board: &Board, turn: Color, pos: Position) -> bool {
+    let (expected_y, pawn_y, start_y, pawn_color) = match turn {
+        Color::White => ('6', '5', '7', Color::Black),
+        Color::Black => ('3', '4', '2', Color::White),
+    };
+    ('a'..='h').contains(&pos.0)
+        && pos.1 == expected_y
+        && board[Position(pos.0, pawn_y).as_index()]
+            == Some(Piece::new(PieceType::Pawn, pawn_color))
+        && board[pos.as_index()].is_none()
+        && board[Position(pos.0, start_y).as_index()].is_none()
+}
+
+impl Game {
+    /// All structural problems of the position, in the order of [`SetupError`].
+    /// Empty for every game played from a valid position, useful to check
+    /// positions loaded from external data.
+    #[must_use]
+    pub fn validate(&self) -> Vec<SetupError> {
+        let mut errors = Vec::new();
+        if self.variant == Variant::Standard {
+            for color in [Color::White, Color::Black] {
+                let kings = self
+                    .board
+                    .iter()
+                    .flatten()
+                    .filter(|piece| **piece == Piece::new(PieceType::King, color))
+                    .count();
+                if kings != 1 {
+                    errors.push(SetupError::WrongNumberOfKings(color, kings));
+                }
+            }
+        }
+        for (pos, piece) in self.pieces() {
+            if piece.piece_type == PieceType::Pawn
+                && (pos.1 == '1' || pos.1 == '8')
+                && self.awaiting_promotion != Some(pos)
+            {
+                errors.push(SetupError::PawnOnBackRank(pos));
+            }
+        }
+        for color in [Color::White, Color::Black] {
+            if (self.able_to_long_castle[color as usize]
+                && (self.variant == Variant::Antichess
+                    || !castling_possible(&self.board, color, 'a')))
+                || (self.able_to_short_castle[color as usize]
+                    && (self.variant == Variant::Antichess
+                        || !castling_possible(&self.board, color, 'h')))
+            {
+                errors.push(SetupError::InvalidCastlingRights(color));
+            }
+        }
+        if let Some(pos) = self.en_passant_square {
+            if !en_passant_square_possible(&self.board, self.turn, pos) {
+                errors.push(SetupError::InvalidEnPassantSquare(pos));
+            }
+        }
+        let attacks = self.pieces_attacking_king();
+        if self.variant == Variant::Standard && !attacks[self.turn.invert() as usize].is_empty() {
+            errors.push(if attacks[self.turn as usize].is_empty() {
+                SetupError::OpponentInCheck
+            } else {
+                SetupError::BothKingsInCheck
+            });
+        }
+        errors
+    }
+}
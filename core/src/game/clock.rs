@@ -0,0 +1,269 @@
+use super::{Color, Game, GameStatus, MoveError, UserInput, COLOR_COUNT};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::time::Duration;
+
+/// A period of a time control: each side has to play `moves` moves with the
+/// time it has left and `time` added at the start of the stage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClockStage {
+    /// `None` for the rest of the game.
+    pub moves: Option<u16>,
+    pub time: Duration,
+    /// Added after every move of the stage.
+    pub increment: Duration,
+}
+
+impl ClockStage {
+    #[must_use]
+    pub const fn new(moves: Option<u16>, time: Duration, increment: Duration) -> Self {
+        ClockStage {
+            moves,
+            time,
+            increment,
+        }
+    }
+}
+
+/// The chess clock of a game, see [`Game::set_clock`]. The time of the side to
+/// move runs until its move is played, a side whose time runs out loses with
+/// [`GameStatus::Timeout`]. Time left over is carried into the next stage.
+///
+/// ```
+/// use core::time::Duration;
+/// use rusty_chess_core::game::{Clock, ClockStage, Color};
+///
+/// // 40 moves in 90 minutes, then 30 minutes for the rest, 30 seconds increment
+/// let increment = Duration::from_secs(30);
+/// let clock = Clock::new(vec![
+///     ClockStage::new(Some(40), Duration::from_secs(90 * 60), increment),
+///     ClockStage::new(None, Duration::from_secs(30 * 60), increment),
+/// ]);
+/// assert_eq!(clock.remaining(Color::White), Duration::from_secs(90 * 60));
+/// assert_eq!(clock.moves_to_go(Color::White), Some(40));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Clock {
+    stages: Vec<ClockStage>,
+    remaining: [Duration; COLOR_COUNT],
+    /// Index of the stage of each side in `stages`.
+    stage: [usize; COLOR_COUNT],
+    /// Moves of each side in its current stage.
+    moves: [u16; COLOR_COUNT],
+    flagged: Option<Color>,
+    /// When the time of the side to move started to run.
+    #[cfg(feature = "std")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    turn_start: Option<std::time::Instant>,
+}
+
+impl Clock {
+    /// A clock going through `stages`, the last one repeats once its moves are
+    /// played, e.g. 40 moves in 2 hours for the whole game.
+    ///
+    /// # Panics
+    ///
+    /// If there are no stages.
+    #[must_use]
+    pub fn new(stages: Vec<ClockStage>) -> Self {
+        assert!(!stages.is_empty(), "A clock needs at least one stage.");
+        let time = stages[0].time;
+        Clock {
+            stages,
+            remaining: [time; COLOR_COUNT],
+            stage: [0; COLOR_COUNT],
+            moves: [0; COLOR_COUNT],
+            flagged: None,
+            #[cfg(feature = "std")]
+            turn_start: None,
+        }
+    }
+
+    /// `time` for the whole game.
+    #[must_use]
+    pub fn sudden_death(time: Duration) -> Self {
+        Self::with_increment(time, Duration::ZERO)
+    }
+
+    /// `time` for the whole game and `increment` added after every move.
+    #[must_use]
+    pub fn with_increment(time: Duration, increment: Duration) -> Self {
+        Self::new(vec![ClockStage::new(None, time, increment)])
+    }
+
+    /// Time `color` had left after its last move.
+    #[must_use]
+    pub const fn remaining(&self, color: Color) -> Duration {
+        self.remaining[color as usize]
+    }
+
+    /// The stage `color` plays in.
+    #[must_use]
+    pub fn stage(&self, color: Color) -> &ClockStage {
+        &self.stages[self.stage[color as usize]]
+    }
+
+    /// Moves `color` has to play until the next stage, `None` in the last one
+    /// without a move count.
+    #[must_use]
+    pub fn moves_to_go(&self, color: Color) -> Option<u16> {
+        self.stage(color)
+            .moves
+            .map(|moves| moves.saturating_sub(self.moves[color as usize]))
+    }
+
+    /// The side whose time ran out.
+    #[must_use]
+    pub const fn flagged(&self) -> Option<Color> {
+        self.flagged
+    }
+
+    /// Takes `elapsed` from the time of `color` and adds the increment, like
+    /// pressing the clock after a move. Returns `false` if the time ran out,
+    /// e.g. for a frontend that keeps the game itself.
+    pub fn press(&mut self, color: Color, elapsed: Duration) -> bool {
+        if !self.spend(color, elapsed) {
+            return false;
+        }
+        self.complete_move(color);
+        true
+    }
+
+    /// Takes `elapsed` from the time of `color`, `false` if it ran out.
+    fn spend(&mut self, color: Color, elapsed: Duration) -> bool {
+        if self.flagged.is_some() {
+            return false;
+        }
+        match self.remaining[color as usize].checked_sub(elapsed) {
+            Some(left) if !left.is_zero() => {
+                self.remaining[color as usize] = left;
+                true
+            }
+            _ => {
+                self.remaining[color as usize] = Duration::ZERO;
+                self.flagged = Some(color);
+                false
+            }
+        }
+    }
+
+    /// Adds the increment and starts the next stage once its moves are played.
+    fn complete_move(&mut self, color: Color) {
+        let side = color as usize;
+        let stage = self.stages[self.stage[side]];
+        self.remaining[side] += stage.increment;
+        self.moves[side] += 1;
+        if stage.moves.is_some_and(|moves| self.moves[side] >= moves) {
+            self.stage[side] = (self.stage[side] + 1).min(self.stages.len() - 1);
+            self.moves[side] = 0;
+            self.remaining[side] += self.stages[self.stage[side]].time;
+        }
+    }
+
+    /// Time since the last stamp, and stamps now.
+    #[cfg(feature = "std")]
+    pub(super) fn lap(&mut self) -> Duration {
+        let now = std::time::Instant::now();
+        let elapsed = self
+            .turn_start
+            .map_or(Duration::ZERO, |start| now.duration_since(start));
+        self.turn_start = Some(now);
+        elapsed
+    }
+}
+
+impl Game {
+    /// Plays the rest of the game with `clock`. With the `std` feature its time
+    /// starts to run for the side to move, and every
+    /// [`Game::process_input`] takes the time since the last one from it.
+    /// Without it, see [`Game::process_timed_input`]. Undoing a move does not
+    /// give the time back.
+    pub fn set_clock(&mut self, clock: Clock) {
+        self.clock = Some(clock);
+        #[cfg(feature = "std")]
+        if let Some(clock) = &mut self.clock {
+            clock.lap();
+        }
+    }
+
+    #[must_use]
+    pub const fn clock(&self) -> Option<&Clock> {
+        self.clock.as_ref()
+    }
+
+    /// Like [`Game::process_input`] but the side to move took `elapsed` for
+    /// it, e.g. measured by a frontend without the `std` feature. If its time
+    /// ran out, the game ends with [`GameStatus::Timeout`] instead of the
+    /// input. Without a clock the time is ignored.
+    ///
+    /// ```
+    /// use core::time::Duration;
+    /// use rusty_chess_core::game::{Clock, Color, Game, GameStatus, Position, UserInput};
+    ///
+    /// let mut game = Game::new();
+    /// game.set_clock(Clock::with_increment(Duration::from_secs(60), Duration::from_secs(1)));
+    /// let e4 = UserInput::Move(Position('e', '2'), Position('e', '4'));
+    /// game.process_timed_input(&e4, Duration::from_secs(10)).unwrap();
+    /// assert_eq!(game.clock().unwrap().remaining(Color::White), Duration::from_secs(51));
+    ///
+    /// let e5 = UserInput::Move(Position('e', '7'), Position('e', '5'));
+    /// let status = game.process_timed_input(&e5, Duration::from_secs(61));
+    /// assert_eq!(status, Ok(GameStatus::Timeout(Color::White)));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Game::process_input`], the time is taken also then.
+    pub fn process_timed_input(
+        &mut self,
+        user_input: &UserInput,
+        elapsed: Duration,
+    ) -> Result<GameStatus, MoveError> {
+        if self.final_status.is_some() {
+            return Err(MoveError::GameOver);
+        }
+        let turn = self.turn;
+        if let Some(clock) = &mut self.clock {
+            // the time of the other side starts now
+            #[cfg(feature = "std")]
+            clock.lap();
+            if !clock.spend(turn, elapsed) {
+                return self.process_untimed_input(&UserInput::Timeout);
+            }
+        }
+        let status = self.process_untimed_input(user_input)?;
+        if self.turn != turn {
+            if let Some(clock) = &mut self.clock {
+                clock.complete_move(turn);
+            }
+        }
+        Ok(status)
+    }
+
+    /// Time the side to move has left right now, `None` without a clock.
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn time_left(&self) -> Option<Duration> {
+        let clock = self.clock.as_ref()?;
+        let running = clock
+            .turn_start
+            .map_or(Duration::ZERO, |start| start.elapsed());
+        Some(clock.remaining(self.turn).saturating_sub(running))
+    }
+
+    /// Ends the game with [`GameStatus::Timeout`] if the time of the side to
+    /// move ran out while it is thinking, e.g. checked by a frontend every
+    /// second. Returns the status of the game.
+    #[cfg(feature = "std")]
+    pub fn check_flag(&mut self) -> GameStatus {
+        if !self.status().is_finished() && self.time_left().is_some_and(|left| left.is_zero()) {
+            // the whole time is taken, so this flags
+            if let Err(e) = self.process_input(&UserInput::Timeout) {
+                unreachable!("A timeout is valid in every game that goes on: {e}");
+            }
+        }
+        self.status()
+    }
+}
@@ -0,0 +1,95 @@
+use super::{Color, Game, PieceType, Position, TOTAL_SQUARES};
+use alloc::vec;
+use alloc::vec::Vec;
+
+const KING_STEPS: [(i8, i8); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+impl Game {
+    /// Only kings and pawns are left, every pawn is blocked by a pawn of the other
+    /// color and no king can ever capture a pawn. Pawns never move again and kings
+    /// can not give check, so no sequence of moves ends in checkmate.
+    pub(super) fn dead_position(&self) -> bool {
+        if self.en_passant_square.is_some() || self.check(self.turn) {
+            return false;
+        }
+        let mut kings = Vec::with_capacity(2);
+        for (index, piece) in self.board.iter().enumerate() {
+            let Some(piece) = piece else {
+                continue;
+            };
+            let pos = Position::try_from(index).expect("Board index is always valid.");
+            match piece.piece_type {
+                PieceType::King => kings.push((pos, piece.color)),
+                PieceType::Pawn if self.pawn_locked(pos, piece.color) => {}
+                _ => return false,
+            }
+        }
+        kings
+            .iter()
+            .all(|(pos, color)| !self.king_can_capture_a_pawn(*pos, *color))
+    }
+
+    fn pawn_is_at(&self, pos: Position, color: Color) -> bool {
+        pos.try_as_index()
+            .and_then(|index| self.board[index])
+            .is_some_and(|piece| piece.piece_type == PieceType::Pawn && piece.color == color)
+    }
+
+    /// The pawn can neither move forward nor capture.
+    fn pawn_locked(&self, pos: Position, color: Color) -> bool {
+        let direction = if color == Color::White { 1 } else { -1 };
+        let other = color.invert();
+        self.pawn_is_at(pos.add((0, direction)), other)
+            && !self.pawn_is_at(pos.add((-1, direction)), other)
+            && !self.pawn_is_at(pos.add((1, direction)), other)
+    }
+
+    fn attacked_by_pawn(&self, pos: Position, color: Color) -> bool {
+        let direction = if color == Color::White { 1 } else { -1 };
+        self.pawn_is_at(pos.add((-1, -direction)), color)
+            || self.pawn_is_at(pos.add((1, -direction)), color)
+    }
+
+    /// Walks the king over all squares it can ever reach and checks whether an
+    /// unprotected pawn of the other color is next to one of them.
+    fn king_can_capture_a_pawn(&self, king: Position, color: Color) -> bool {
+        let other = color.invert();
+        let mut visited = [false; TOTAL_SQUARES];
+        visited[king.as_index()] = true;
+        let mut to_visit = vec![king];
+        while let Some(pos) = to_visit.pop() {
+            for step in KING_STEPS {
+                let next = pos.add(step);
+                let Some(index) = next.try_as_index() else {
+                    continue;
+                };
+                if visited[index] {
+                    continue;
+                }
+                match self.board[index] {
+                    Some(piece) if piece.piece_type == PieceType::Pawn => {
+                        if piece.color == other && !self.attacked_by_pawn(next, other) {
+                            return true;
+                        }
+                    }
+                    _ => {
+                        if !self.attacked_by_pawn(next, other) {
+                            visited[index] = true;
+                            to_visit.push(next);
+                        }
+                    }
+                }
+            }
+        }
+        false
+    }
+}
@@ -0,0 +1,55 @@
+use super::{Game, Move, MoveType, PieceType};
+use alloc::format;
+use alloc::string::String;
+
+const fn piece_type_name(piece_type: PieceType) -> &'static str {
+    match piece_type {
+        PieceType::Pawn => "pawn",
+        PieceType::Knight => "knight",
+        PieceType::Bishop => "bishop",
+        PieceType::Rook => "rook",
+        PieceType::Queen => "queen",
+        PieceType::King => "king",
+    }
+}
+
+impl Move {
+    /// The move in words for announcements and screen readers, e.g.
+    /// `Knight from g1 to f3, capturing a pawn, check`. `game` is the position
+    /// before the move, as for [`Game::san`].
+    #[must_use]
+    pub fn describe(&self, game: &Game) -> String {
+        let mut description = match self.move_type {
+            MoveType::ShortCastle => String::from("Castling kingside"),
+            MoveType::LongCastle => String::from("Castling queenside"),
+            _ => {
+                let name = piece_type_name(self.piece.piece_type);
+                let mut chars = name.chars();
+                let capitalized: String = chars
+                    .next()
+                    .map(|c| c.to_ascii_uppercase())
+                    .into_iter()
+                    .chain(chars)
+                    .collect();
+                format!("{capitalized} from {} to {}", self.from, self.to)
+            }
+        };
+        if let Some(captured_piece) = self.captured_piece {
+            description.push_str(", capturing a ");
+            description.push_str(piece_type_name(captured_piece.piece_type));
+            if self.move_type == MoveType::Enpassant {
+                description.push_str(" en passant");
+            }
+        }
+        if let Some(piece_type) = self.promotion {
+            description.push_str(", promoting to a ");
+            description.push_str(piece_type_name(piece_type));
+        }
+        match game.san(self).chars().last() {
+            Some('#') => description.push_str(", checkmate"),
+            Some('+') => description.push_str(", check"),
+            _ => {}
+        }
+        description
+    }
+}
@@ -0,0 +1,61 @@
+use alloc::vec::Vec;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use super::{squares, Color, Game, Move, MoveType, PieceType, Position};
+
+impl Game {
+    /// Legal moves of the side to move while it is in check: king moves and, if
+    /// only one piece gives check, captures of that piece and moves onto its line
+    /// to the king. Empty if the side to move is not in check.
+    #[must_use]
+    pub fn evasion_moves(&self) -> Vec<Move> {
+        let color = self.turn;
+        let checking_pieces = self.pieces_attacking_king[color as usize].len();
+        if checking_pieces == 0 {
+            return Vec::new();
+        }
+        squares()
+            .flat_map(|(x, y)| {
+                let pos = Position(*x, *y);
+                match self.board[pos.as_index()] {
+                    // double check: only king can move
+                    Some(piece)
+                        if piece.color == color
+                            && (piece.piece_type == PieceType::King || checking_pieces == 1) =>
+                    {
+                        self.pseudo_legal_moves(pos)
+                            .into_iter()
+                            .filter(|mv| self.resolves_check(mv) && self.piece_is_not_pinned(mv))
+                            .collect()
+                    }
+                    _ => Vec::new(),
+                }
+            })
+            .collect()
+    }
+
+    /// A move of a piece other than the king can only protect it from a single
+    /// checking piece by capturing it or moving onto its line to the king.
+    /// Whether the king itself is safe afterwards is not checked.
+    pub(super) fn resolves_check(&self, mv: &Move) -> bool {
+        if mv.piece.piece_type == PieceType::King {
+            return true;
+        }
+        match self.pieces_attacking_king[mv.piece.color as usize].as_slice() {
+            [] => true,
+            [check] => {
+                check.passes(mv.to)
+                    || (mv.move_type == MoveType::Enpassant
+                        && check.passes(en_passant_capture_square(mv.to, mv.piece.color)))
+            }
+            _ => false,
+        }
+    }
+}
+
+/// The square of the pawn captured en passant on `to` by a pawn of `color`.
+fn en_passant_capture_square(to: Position, color: Color) -> Position {
+    let direction = if color == Color::White { 1 } else { -1 };
+    to.add((0, -direction))
+}
@@ -0,0 +1,274 @@
+use super::builder::{castling_possible, en_passant_square_possible, BoardBuilder};
+use super::{Board, Color, Game, Piece, PieceType, Position, Variant, COLOR_COUNT, TOTAL_SQUARES};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{self, Formatter};
+
+/// FEN of the standard starting position.
+pub const STARTING_POSITION_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+/// The field of a FEN string that could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FenError {
+    /// A FEN needs 4 to 6 space separated fields (the clocks are optional).
+    WrongNumberOfFields(usize),
+    InvalidPiecePlacement(String),
+    InvalidSideToMove(String),
+    InvalidCastlingRights(String),
+    InvalidEnPassantSquare(String),
+    InvalidHalfmoveClock(String),
+    InvalidFullmoveNumber(String),
+}
+
+impl fmt::Display for FenError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            FenError::WrongNumberOfFields(n) => {
+                write!(f, "expected 4 to 6 FEN fields but got {n}")
+            }
+            FenError::InvalidPiecePlacement(s) => write!(f, "invalid piece placement '{s}'"),
+            FenError::InvalidSideToMove(s) => write!(f, "invalid side to move '{s}'"),
+            FenError::InvalidCastlingRights(s) => write!(f, "invalid castling rights '{s}'"),
+            FenError::InvalidEnPassantSquare(s) => write!(f, "invalid en passant square '{s}'"),
+            FenError::InvalidHalfmoveClock(s) => write!(f, "invalid halfmove clock '{s}'"),
+            FenError::InvalidFullmoveNumber(s) => write!(f, "invalid fullmove number '{s}'"),
+        }
+    }
+}
+
+impl core::error::Error for FenError {}
+
+pub(super) const fn piece_to_char(piece: Piece) -> char {
+    let c = match piece.piece_type {
+        PieceType::Pawn => 'p',
+        PieceType::Knight => 'n',
+        PieceType::Bishop => 'b',
+        PieceType::Rook => 'r',
+        PieceType::Queen => 'q',
+        PieceType::King => 'k',
+    };
+    match piece.color {
+        Color::White => c.to_ascii_uppercase(),
+        Color::Black => c,
+    }
+}
+
+const fn char_to_piece(c: char) -> Option<Piece> {
+    let piece_type = match c.to_ascii_lowercase() {
+        'p' => PieceType::Pawn,
+        'n' => PieceType::Knight,
+        'b' => PieceType::Bishop,
+        'r' => PieceType::Rook,
+        'q' => PieceType::Queen,
+        'k' => PieceType::King,
+        _ => return None,
+    };
+    let color = if c.is_ascii_uppercase() {
+        Color::White
+    } else {
+        Color::Black
+    };
+    Some(Piece::new(piece_type, color))
+}
+
+fn parse_piece_placement(placement: &str) -> Option<Board> {
+    let mut board: Board = [None; TOTAL_SQUARES];
+    let ranks: Vec<&str> = placement.split('/').collect();
+    if ranks.len() != 8 {
+        return None;
+    }
+    for (rank, y) in ranks.iter().zip(('1'..='8').rev()) {
+        let mut x = b'a';
+        for c in rank.chars() {
+            if let Some(empty_squares) = c.to_digit(10) {
+                if !(1..=8).contains(&empty_squares) {
+                    return None;
+                }
+                x += empty_squares as u8;
+            } else {
+                let piece = char_to_piece(c)?;
+                board[Position(x as char, y).try_as_index()?] = Some(piece);
+                x += 1;
+            }
+            if x > b'h' + 1 {
+                return None;
+            }
+        }
+        if x != b'h' + 1 {
+            return None;
+        }
+    }
+    Some(board)
+}
+
+impl Game {
+    /// Sets up a game from a FEN string. The halfmove clock and fullmove number
+    /// may be omitted and default to `0` and `1`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first field that is malformed or inconsistent with the board,
+    /// e.g. castling rights without king and rook on their initial squares.
+    pub fn from_fen(fen: &str) -> Result<Game, FenError> {
+        Game::from_fen_with_variant(fen, Variant::Standard)
+    }
+
+    /// Sets up a game of `variant` from a FEN string, see [`Game::from_fen`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Game::from_fen`], castling rights are also rejected in antichess.
+    pub fn from_fen_with_variant(fen: &str, variant: Variant) -> Result<Game, FenError> {
+        let fields: Vec<&str> = fen.split_whitespace().collect();
+        if !(4..=6).contains(&fields.len()) {
+            return Err(FenError::WrongNumberOfFields(fields.len()));
+        }
+
+        let board = parse_piece_placement(fields[0])
+            .ok_or_else(|| FenError::InvalidPiecePlacement(fields[0].to_string()))?;
+
+        let turn = match fields[1] {
+            "w" => Color::White,
+            "b" => Color::Black,
+            s => return Err(FenError::InvalidSideToMove(s.to_string())),
+        };
+
+        let mut able_to_long_castle = [false; COLOR_COUNT];
+        let mut able_to_short_castle = [false; COLOR_COUNT];
+        if fields[2] != "-" {
+            let invalid_castling = || FenError::InvalidCastlingRights(fields[2].to_string());
+            if variant == Variant::Antichess {
+                return Err(invalid_castling());
+            }
+            for c in fields[2].chars() {
+                let (color, rights, rook_x) = match c {
+                    'K' => (Color::White, &mut able_to_short_castle, 'h'),
+                    'Q' => (Color::White, &mut able_to_long_castle, 'a'),
+                    'k' => (Color::Black, &mut able_to_short_castle, 'h'),
+                    'q' => (Color::Black, &mut able_to_long_castle, 'a'),
+                    _ => return Err(invalid_castling()),
+                };
+                if rights[color as usize] || !castling_possible(&board, color, rook_x) {
+                    return Err(invalid_castling());
+                }
+                rights[color as usize] = true;
+            }
+        }
+
+        let en_passant_square = if fields[3] == "-" {
+            None
+        } else {
+            let invalid_en_passant = || FenError::InvalidEnPassantSquare(fields[3].to_string());
+            let mut chars = fields[3].chars();
+            let (Some(x), Some(y), None) = (chars.next(), chars.next(), chars.next()) else {
+                return Err(invalid_en_passant());
+            };
+            if !en_passant_square_possible(&board, turn, Position(x, y)) {
+                return Err(invalid_en_passant());
+            }
+            Some(Position(x, y))
+        };
+
+        let halfmove_clock = match fields.get(4) {
+            None => 0,
+            Some(s) => s
+                .parse()
+                .map_err(|_| FenError::InvalidHalfmoveClock((*s).to_string()))?,
+        };
+        let fullmove_number = match fields.get(5) {
+            None => 1,
+            Some(s) => match s.parse() {
+                Ok(n) if n >= 1 => n,
+                _ => return Err(FenError::InvalidFullmoveNumber((*s).to_string())),
+            },
+        };
+
+        let white = Color::White as usize;
+        let black = Color::Black as usize;
+        Ok(BoardBuilder::new()
+            .board(board)
+            .turn(turn)
+            .castling(
+                Color::White,
+                able_to_short_castle[white],
+                able_to_long_castle[white],
+            )
+            .castling(
+                Color::Black,
+                able_to_short_castle[black],
+                able_to_long_castle[black],
+            )
+            .en_passant_square(en_passant_square)
+            .halfmove_clock(halfmove_clock)
+            .fullmove_number(fullmove_number)
+            .variant(variant)
+            .build_unchecked())
+    }
+
+    /// Serializes the game state in Forsyth-Edwards Notation
+    /// (e.g. `rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1`).
+    #[must_use]
+    pub fn to_fen(&self) -> String {
+        let mut fen = String::new();
+        for y in ('1'..='8').rev() {
+            let mut empty_squares = 0;
+            for x in 'a'..='h' {
+                match self.board[Position(x, y).as_index()] {
+                    None => empty_squares += 1,
+                    Some(piece) => {
+                        if empty_squares > 0 {
+                            fen.push_str(&empty_squares.to_string());
+                            empty_squares = 0;
+                        }
+                        fen.push(piece_to_char(piece));
+                    }
+                }
+            }
+            if empty_squares > 0 {
+                fen.push_str(&empty_squares.to_string());
+            }
+            if y != '1' {
+                fen.push('/');
+            }
+        }
+
+        fen.push(' ');
+        fen.push(match self.turn {
+            Color::White => 'w',
+            Color::Black => 'b',
+        });
+
+        fen.push(' ');
+        let castling_rights = [
+            (self.able_to_short_castle[Color::White as usize], 'K'),
+            (self.able_to_long_castle[Color::White as usize], 'Q'),
+            (self.able_to_short_castle[Color::Black as usize], 'k'),
+            (self.able_to_long_castle[Color::Black as usize], 'q'),
+        ];
+        if castling_rights.iter().any(|(right, _)| *right) {
+            for (right, c) in castling_rights {
+                if right {
+                    fen.push(c);
+                }
+            }
+        } else {
+            fen.push('-');
+        }
+
+        fen.push(' ');
+        match self.en_passant_square {
+            Some(pos) => {
+                fen.push(pos.0);
+                fen.push(pos.1);
+            }
+            None => fen.push('-'),
+        }
+
+        fen.push_str(&format!(
+            " {} {}",
+            self.number_of_moves_without_captures_or_pawn_moves, self.fullmove_number
+        ));
+        fen
+    }
+}
@@ -0,0 +1,114 @@
+use super::fen::piece_to_char;
+use super::{Game, Position};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::{format, vec};
+
+/// Renders the board as text, by default like the [`Display`](core::fmt::Display)
+/// of [`Game`]: Unicode glyphs, White at the bottom and coordinates.
+///
+/// ```
+/// use rusty_chess_core::game::{BoardFormatter, Game};
+///
+/// let board = BoardFormatter::new()
+///     .unicode(false)
+///     .coordinates(false)
+///     .format(&Game::new());
+/// assert!(board.starts_with("---------------------------------\n| r | n | b |"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoardFormatter {
+    unicode: bool,
+    flipped: bool,
+    coordinates: bool,
+    last_move: bool,
+}
+
+impl Default for BoardFormatter {
+    fn default() -> Self {
+        BoardFormatter {
+            unicode: true,
+            flipped: false,
+            coordinates: true,
+            last_move: false,
+        }
+    }
+}
+
+impl BoardFormatter {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Chess glyphs instead of the FEN letters (upper case for White).
+    #[must_use]
+    pub const fn unicode(mut self, unicode: bool) -> Self {
+        self.unicode = unicode;
+        self
+    }
+
+    /// Black at the bottom.
+    #[must_use]
+    pub const fn flipped(mut self, flipped: bool) -> Self {
+        self.flipped = flipped;
+        self
+    }
+
+    /// Rank numbers on the left and file letters below the board.
+    #[must_use]
+    pub const fn coordinates(mut self, coordinates: bool) -> Self {
+        self.coordinates = coordinates;
+        self
+    }
+
+    /// Marks the squares of the last move with a `*`.
+    #[must_use]
+    pub const fn last_move(mut self, last_move: bool) -> Self {
+        self.last_move = last_move;
+        self
+    }
+
+    #[must_use]
+    pub fn format(&self, game: &Game) -> String {
+        let mut ranks: Vec<char> = ('1'..='8').rev().collect();
+        let mut files: Vec<char> = ('a'..='h').collect();
+        if self.flipped {
+            ranks.reverse();
+            files.reverse();
+        }
+        let marked: Vec<Position> = match game.history.last() {
+            Some(played) if self.last_move => vec![played.mv.from, played.mv.to],
+            _ => Vec::new(),
+        };
+        let margin = if self.coordinates { "  " } else { "" };
+        let separator = format!("{margin}-{}\n", "--".repeat(16));
+
+        let mut res = separator.clone();
+        for y in &ranks {
+            if self.coordinates {
+                res.push_str(&format!("{y} "));
+            }
+            res.push_str("| ");
+            for x in &files {
+                let pos = Position(*x, *y);
+                match game.board[pos.as_index()] {
+                    None => res.push(' '),
+                    Some(piece) if self.unicode => res.push_str(&piece.to_string()),
+                    Some(piece) => res.push(piece_to_char(piece)),
+                }
+                res.push_str(if marked.contains(&pos) { "*| " } else { " | " });
+            }
+            res.push('\n');
+            res.push_str(&separator);
+        }
+        if self.coordinates {
+            res.push_str("    ");
+            for x in &files {
+                res.push_str(&format!("{x}   "));
+            }
+            res.push('\n');
+        }
+        res
+    }
+}
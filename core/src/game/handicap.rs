@@ -0,0 +1,37 @@
+use super::Game;
+
+/// Material or tempo White gives to a weaker opponent playing Black.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Handicap {
+    /// White plays without the f2 pawn and Black moves first.
+    PawnAndMove,
+    /// White plays without the b1 knight.
+    KnightOdds,
+    /// White plays without the a1 rook and can not castle long.
+    RookOdds,
+    /// White plays without the queen.
+    QueenOdds,
+    /// Black moves first from the starting position.
+    FirstMove,
+}
+
+impl Handicap {
+    const fn fen(self) -> &'static str {
+        match self {
+            Handicap::PawnAndMove => "rnbqkbnr/pppppppp/8/8/8/8/PPPPP1PP/RNBQKBNR b KQkq - 0 1",
+            Handicap::KnightOdds => "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/R1BQKBNR w KQkq - 0 1",
+            Handicap::RookOdds => "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/1NBQKBNR w Kkq - 0 1",
+            Handicap::QueenOdds => "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNB1KBNR w KQkq - 0 1",
+            Handicap::FirstMove => "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR b KQkq - 0 1",
+        }
+    }
+}
+
+impl Game {
+    /// New game in which White gives the odds of `handicap`. The starting
+    /// position is kept as FEN, so exported PGNs can be replayed.
+    #[must_use]
+    pub fn with_handicap(handicap: Handicap) -> Game {
+        Game::from_fen(handicap.fen()).expect("Handicap positions are valid FEN.")
+    }
+}
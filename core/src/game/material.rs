@@ -0,0 +1,47 @@
+use super::{Color, Game, PieceType};
+
+/// Number of pieces of each type a color has on the board, kings excluded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct MaterialCount {
+    pub pawns: u8,
+    pub knights: u8,
+    pub bishops: u8,
+    pub rooks: u8,
+    pub queens: u8,
+}
+
+impl MaterialCount {
+    /// Sum of the [`PieceType::value`] of all pieces.
+    #[must_use]
+    pub const fn value(&self) -> u32 {
+        self.pawns as u32 * PieceType::Pawn.value() as u32
+            + self.knights as u32 * PieceType::Knight.value() as u32
+            + self.bishops as u32 * PieceType::Bishop.value() as u32
+            + self.rooks as u32 * PieceType::Rook.value() as u32
+            + self.queens as u32 * PieceType::Queen.value() as u32
+    }
+}
+
+impl Game {
+    #[must_use]
+    pub fn material(&self, color: Color) -> MaterialCount {
+        let mut material = MaterialCount::default();
+        for (_, piece) in self.pieces_of(color) {
+            match piece.piece_type {
+                PieceType::Pawn => material.pawns += 1,
+                PieceType::Knight => material.knights += 1,
+                PieceType::Bishop => material.bishops += 1,
+                PieceType::Rook => material.rooks += 1,
+                PieceType::Queen => material.queens += 1,
+                PieceType::King => {}
+            }
+        }
+        material
+    }
+
+    /// Material value of White minus the one of Black, positive if White is ahead.
+    #[must_use]
+    pub fn material_balance(&self) -> i32 {
+        self.material(Color::White).value() as i32 - self.material(Color::Black).value() as i32
+    }
+}
@@ -0,0 +1,16 @@
+use super::{Color, Game, Position};
+
+impl Game {
+    /// Number of legal moves `color` would have if it was to move. A promotion
+    /// counts as one move.
+    #[must_use]
+    pub fn mobility(&self, color: Color) -> usize {
+        self.legal_moves_of(color).count()
+    }
+
+    /// Number of legal moves of the piece on `pos`, 0 for an empty square.
+    #[must_use]
+    pub fn piece_mobility(&self, pos: Position) -> usize {
+        self.get_valid_moves(pos).len()
+    }
+}
@@ -0,0 +1,108 @@
+use super::{stats, Move};
+use alloc::vec::Vec;
+use core::ops::Deref;
+use smallvec::SmallVec;
+
+/// Moves stored on the stack without allocations, only positions with more
+/// moves spill to the heap.
+pub const MOVE_LIST_CAPACITY: usize = 64;
+
+/// Buffer for generated moves, e.g. one per ply of a search. It can be cleared
+/// and filled again with [`Game::legal_moves_to`](super::Game::legal_moves_to)
+/// and [`Game::pseudo_legal_moves_to`](super::Game::pseudo_legal_moves_to).
+///
+/// ```
+/// use rusty_chess_core::game::{Game, MoveList};
+///
+/// let game = Game::new();
+/// let mut moves = MoveList::new();
+/// game.legal_moves_to(&mut moves);
+/// assert_eq!(moves.len(), 20);
+/// assert!(moves.iter().all(|mv| !mv.is_capture()));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MoveList(SmallVec<[Move; MOVE_LIST_CAPACITY]>);
+
+impl MoveList {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, mv: Move) {
+        let spilled = self.0.spilled();
+        self.0.push(mv);
+        if !spilled && self.0.spilled() {
+            stats::count_allocation();
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    /// Keeps only the moves for which `f` returns `true`.
+    pub fn retain(&mut self, mut f: impl FnMut(&Move) -> bool) {
+        self.0.retain(|mv| f(mv));
+    }
+
+    #[must_use]
+    pub fn as_slice(&self) -> &[Move] {
+        &self.0
+    }
+
+    /// The moves were moved to the heap because there were more than
+    /// [`MOVE_LIST_CAPACITY`].
+    #[must_use]
+    pub fn spilled(&self) -> bool {
+        self.0.spilled()
+    }
+}
+
+impl Deref for MoveList {
+    type Target = [Move];
+
+    fn deref(&self) -> &[Move] {
+        &self.0
+    }
+}
+
+impl Extend<Move> for MoveList {
+    fn extend<I: IntoIterator<Item = Move>>(&mut self, iter: I) {
+        let spilled = self.0.spilled();
+        self.0.extend(iter);
+        if !spilled && self.0.spilled() {
+            stats::count_allocation();
+        }
+    }
+}
+
+impl FromIterator<Move> for MoveList {
+    fn from_iter<I: IntoIterator<Item = Move>>(iter: I) -> Self {
+        MoveList(iter.into_iter().collect())
+    }
+}
+
+impl IntoIterator for MoveList {
+    type Item = Move;
+    type IntoIter = smallvec::IntoIter<[Move; MOVE_LIST_CAPACITY]>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a MoveList {
+    type Item = &'a Move;
+    type IntoIter = core::slice::Iter<'a, Move>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl From<MoveList> for Vec<Move> {
+    fn from(moves: MoveList) -> Vec<Move> {
+        moves.0.into_vec()
+    }
+}
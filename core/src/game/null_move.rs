@@ -0,0 +1,35 @@
+use super::{Color, Game, MoveError};
+
+impl Game {
+    /// Passes the turn to the other side without moving a piece, e.g. to see
+    /// what it threatens. This is not a move of the rules of chess, so it is not
+    /// recorded in the history and can not be undone, play it on a copy of the
+    /// game.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the side to move is in check, a pawn waits for its
+    /// promotion or the game is over.
+    pub fn make_null_move(&mut self) -> Result<(), MoveError> {
+        if self.final_status.is_some() {
+            return Err(MoveError::GameOver);
+        }
+        if let Some(pos) = self.awaiting_promotion {
+            return Err(MoveError::PromotionRequired(pos));
+        }
+        if self.is_check() {
+            return Err(MoveError::LeavesKingInCheck);
+        }
+        self.en_passant_square = None;
+        self.number_of_moves_without_captures_or_pawn_moves = self
+            .number_of_moves_without_captures_or_pawn_moves
+            .saturating_add(1);
+        self.switch_turn();
+        if self.turn == Color::White {
+            self.fullmove_number += 1;
+        }
+        self.update_attacks();
+        self.position_keys.push(self.zobrist_key());
+        Ok(())
+    }
+}
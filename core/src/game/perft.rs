@@ -0,0 +1,77 @@
+use super::{Game, Move, UserInput};
+use alloc::vec;
+use alloc::vec::Vec;
+
+impl Game {
+    /// Counts the leaf nodes of the tree of legal moves up to `depth` plies.
+    /// Every promotion piece counts as a separate move.
+    #[must_use]
+    pub fn perft(&self, depth: u8) -> u64 {
+        if depth == 0 {
+            return 1;
+        }
+        let moves = self.get_all_currently_valid_moves();
+        if depth == 1 {
+            return moves
+                .iter()
+                .map(|mv| {
+                    if mv.is_promotion() {
+                        self.promotion_piece_types().len() as u64
+                    } else {
+                        1
+                    }
+                })
+                .sum();
+        }
+        moves
+            .iter()
+            .flat_map(|mv| self.games_after_move(mv))
+            .map(|(_, game)| game.perft(depth - 1))
+            .sum()
+    }
+
+    /// Node count of [`Game::perft`] split by the first move, sorted by the UCI
+    /// notation of the move. Helps to find which move generation is wrong.
+    #[must_use]
+    pub fn perft_divide(&self, depth: u8) -> Vec<(Move, u64)> {
+        if depth == 0 {
+            return Vec::new();
+        }
+        let mut divide: Vec<(Move, u64)> = self
+            .get_all_currently_valid_moves()
+            .iter()
+            .flat_map(|mv| self.games_after_move(mv))
+            .map(|(mv, game)| {
+                let nodes = game.perft(depth - 1);
+                (mv, nodes)
+            })
+            .collect();
+        divide.sort_by_key(|(mv, _)| mv.to_uci());
+        divide
+    }
+
+    /// The positions after a legal move, one for each promotion piece.
+    fn games_after_move(&self, mv: &Move) -> Vec<(Move, Game)> {
+        let play = |input: UserInput, mv: Move| {
+            let mut game = self.clone();
+            if let Err(e) = game.apply_input(&input) {
+                unreachable!("Generated move {mv} is not valid: {e}");
+            }
+            (mv, game)
+        };
+        if !mv.is_promotion() {
+            return vec![play(UserInput::Move(mv.from, mv.to), *mv)];
+        }
+        self.promotion_piece_types()
+            .iter()
+            .map(|piece_type| {
+                let mut promotion = *mv;
+                promotion.promotion = Some(*piece_type);
+                play(
+                    UserInput::MoveWithPromotion(mv.from, mv.to, *piece_type),
+                    promotion,
+                )
+            })
+            .collect()
+    }
+}
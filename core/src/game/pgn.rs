@@ -0,0 +1,545 @@
+use super::{
+    Color, DrawReason, FenError, Game, GameResult, GameStatus, GameTree, NodeId, PlayedMove,
+    Variant, STARTING_POSITION_FEN,
+};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{self, Formatter};
+
+/// PGN export keeps movetext lines below this length.
+const MAX_LINE_LENGTH: usize = 80;
+
+/// Move suffixes and the NAG each of them stands for.
+const SUFFIX_NAGS: [(&str, u8); 6] = [
+    ("!", 1),
+    ("?", 2),
+    ("!!", 3),
+    ("??", 4),
+    ("!?", 5),
+    ("?!", 6),
+];
+
+/// Tag values of the Seven Tag Roster. The `Result` tag is taken from the game.
+/// Unknown values are `?` as recommended by the PGN standard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PgnTags {
+    pub event: String,
+    pub site: String,
+    /// Formatted as `YYYY.MM.DD`, unknown parts replaced by `?`.
+    pub date: String,
+    pub round: String,
+    pub white: String,
+    pub black: String,
+}
+
+impl Default for PgnTags {
+    fn default() -> Self {
+        PgnTags {
+            event: String::from("?"),
+            site: String::from("?"),
+            date: String::from("????.??.??"),
+            round: String::from("?"),
+            white: String::from("?"),
+            black: String::from("?"),
+        }
+    }
+}
+
+/// Comment and Numeric Annotation Glyphs of a move, e.g. `$1` for a good move.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Annotation {
+    pub comment: Option<String>,
+    pub nags: Vec<u8>,
+}
+
+impl Annotation {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.comment.is_none() && self.nags.is_empty()
+    }
+}
+
+/// Why a PGN could not be imported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PgnError {
+    /// A tag pair is not of the form `[Name "Value"]`.
+    InvalidTag(String),
+    InvalidFen(FenError),
+    UnknownVariant(String),
+    /// The move is not legal or not valid standard algebraic notation.
+    IllegalMove(String),
+    InvalidNag(String),
+    UnterminatedComment,
+    /// A variation is not closed or closed without being opened.
+    UnbalancedVariation,
+}
+
+impl fmt::Display for PgnError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            PgnError::InvalidTag(tag) => write!(f, "invalid tag pair '{tag}'"),
+            PgnError::InvalidFen(e) => write!(f, "invalid FEN tag: {e}"),
+            PgnError::UnknownVariant(variant) => write!(f, "unknown variant '{variant}'"),
+            PgnError::IllegalMove(san) => write!(f, "illegal move '{san}'"),
+            PgnError::InvalidNag(nag) => write!(f, "invalid annotation glyph '{nag}'"),
+            PgnError::UnterminatedComment => write!(f, "comment is not closed"),
+            PgnError::UnbalancedVariation => write!(f, "unbalanced parentheses of a variation"),
+        }
+    }
+}
+
+impl core::error::Error for PgnError {}
+
+fn push_tag(pgn: &mut String, name: &str, value: &str) {
+    let value = value.replace('\\', "\\\\").replace('"', "\\\"");
+    pgn.push_str(&format!("[{name} \"{value}\"]\n"));
+}
+
+/// Tag pairs of the Seven Tag Roster, the variant and the starting position.
+fn push_tags(pgn: &mut String, tags: &PgnTags, result: &str, variant: Variant, fen: &str) {
+    push_tag(pgn, "Event", &tags.event);
+    push_tag(pgn, "Site", &tags.site);
+    push_tag(pgn, "Date", &tags.date);
+    push_tag(pgn, "Round", &tags.round);
+    push_tag(pgn, "White", &tags.white);
+    push_tag(pgn, "Black", &tags.black);
+    push_tag(pgn, "Result", result);
+    if variant == Variant::Antichess {
+        push_tag(pgn, "Variant", "Antichess");
+    }
+    if fen != STARTING_POSITION_FEN {
+        push_tag(pgn, "SetUp", "1");
+        push_tag(pgn, "FEN", fen);
+    }
+    pgn.push('\n');
+}
+
+/// Adds the tokens of a move played by `turn` with its annotation. Returns
+/// whether the next move needs its move number, as after a comment.
+fn push_move_tokens(
+    tokens: &mut Vec<String>,
+    played: &PlayedMove,
+    annotation: &Annotation,
+    (turn, move_number): (Color, u16),
+    needs_number: bool,
+) -> bool {
+    if turn == Color::White {
+        tokens.push(format!("{move_number}."));
+    } else if needs_number {
+        tokens.push(format!("{move_number}..."));
+    }
+    tokens.push(played.san.clone());
+    tokens.extend(annotation.nags.iter().map(|nag| format!("${nag}")));
+    match &annotation.comment {
+        // a closing brace would end the comment early
+        Some(comment) => {
+            tokens.push(format!("{{{}}}", comment.replace('}', "")));
+            true
+        }
+        None => false,
+    }
+}
+
+/// Joins the tokens to lines of at most [`MAX_LINE_LENGTH`] characters.
+fn push_movetext(pgn: &mut String, tokens: Vec<String>) {
+    let mut line_length = 0;
+    for token in tokens {
+        if line_length > 0 && line_length + 1 + token.len() > MAX_LINE_LENGTH {
+            pgn.push('\n');
+            line_length = 0;
+        } else if line_length > 0 && token != ")" && !pgn.ends_with('(') {
+            pgn.push(' ');
+            line_length += 1;
+        }
+        line_length += token.len();
+        pgn.push_str(&token);
+    }
+    pgn.push('\n');
+}
+
+impl Game {
+    /// Exports the game as PGN with default tags, see [`Game::to_pgn_with_tags`].
+    #[must_use]
+    pub fn to_pgn(&self) -> String {
+        self.to_pgn_with_tags(&PgnTags::default())
+    }
+
+    /// Exports the game as PGN with the Seven Tag Roster and the moves played so far
+    /// in standard algebraic notation, with the annotations of the moves. Games not
+    /// started from the initial position get the `SetUp` and `FEN` tags.
+    #[must_use]
+    pub fn to_pgn_with_tags(&self, tags: &PgnTags) -> String {
+        let result = self.pgn_result();
+        let starting_fen = self
+            .starting_fen
+            .as_deref()
+            .unwrap_or(STARTING_POSITION_FEN);
+
+        let mut pgn = String::new();
+        push_tags(&mut pgn, tags, result, self.variant, starting_fen);
+
+        // side to move and move number of the starting position
+        let mut fields = starting_fen.split_whitespace().skip(1);
+        let mut turn = if fields.next() == Some("b") {
+            Color::Black
+        } else {
+            Color::White
+        };
+        let mut move_number: u16 = fields.nth(3).and_then(|n| n.parse().ok()).unwrap_or(1);
+
+        let mut tokens = Vec::with_capacity(self.history.len() * 3 / 2 + 1);
+        let mut needs_number = true;
+        for played in &self.history {
+            needs_number = push_move_tokens(
+                &mut tokens,
+                played,
+                &played.annotation,
+                (turn, move_number),
+                needs_number,
+            );
+            if turn == Color::Black {
+                move_number += 1;
+            }
+            turn = turn.invert();
+        }
+        tokens.push(result.to_string());
+        push_movetext(&mut pgn, tokens);
+        pgn
+    }
+
+    /// Imports the main line of a PGN, see [`GameTree::from_pgn`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`GameTree::from_pgn`].
+    pub fn from_pgn(pgn: &str) -> Result<Game, PgnError> {
+        let tree = GameTree::from_pgn(pgn)?;
+        let mainline: Vec<NodeId> = tree.mainline().collect();
+        let last = mainline.last().map_or(tree.root(), |id| *id);
+        let mut game = tree
+            .game(last)
+            .expect("the main line only contains nodes of the tree")
+            .clone();
+        for (played, id) in game.history.iter_mut().zip(&mainline[1..]) {
+            played.annotation = tree.annotation(*id).cloned().unwrap_or_default();
+        }
+        Ok(game)
+    }
+
+    /// Imports every game of a PGN file with several games, e.g. a database
+    /// or the archive of a tournament. A tag after the moves of a game starts
+    /// the next one.
+    ///
+    /// ```
+    /// use rusty_chess_core::game::Game;
+    ///
+    /// let games = Game::from_pgn_games("[Round \"1\"]\n\n1. e4 e5 *\n\n[Round \"2\"]\n\n1. d4 *\n");
+    /// assert_eq!(games.len(), 2);
+    /// assert_eq!(games[1].as_ref().unwrap().history().len(), 1);
+    /// ```
+    pub fn from_pgn_games(pgn: &str) -> Vec<Result<Game, PgnError>> {
+        let mut games = Vec::new();
+        let mut current = String::new();
+        let mut in_moves = false;
+        for line in pgn.lines() {
+            let is_tag = line.trim_start().starts_with('[');
+            if is_tag && in_moves {
+                games.push(Game::from_pgn(&current));
+                current.clear();
+                in_moves = false;
+            }
+            in_moves |= !is_tag && !line.trim().is_empty();
+            current.push_str(line);
+            current.push('\n');
+        }
+        if in_moves {
+            games.push(Game::from_pgn(&current));
+        }
+        games
+    }
+
+    /// Annotation of the move `history()[index]`.
+    pub fn annotation_mut(&mut self, index: usize) -> Option<&mut Annotation> {
+        self.history
+            .get_mut(index)
+            .map(|played| &mut played.annotation)
+    }
+
+    fn pgn_result(&self) -> &'static str {
+        match self.result() {
+            Some(GameResult::WhiteWins) => "1-0",
+            Some(GameResult::BlackWins) => "0-1",
+            Some(GameResult::Draw) => "1/2-1/2",
+            None => "*",
+        }
+    }
+}
+
+impl GameTree {
+    /// Exports the tree as PGN with default tags, see [`GameTree::to_pgn_with_tags`].
+    #[must_use]
+    pub fn to_pgn(&self) -> String {
+        self.to_pgn_with_tags(&PgnTags::default())
+    }
+
+    /// Exports the tree as PGN starting at the root, with the variations in
+    /// parentheses after the main line move they replace. The result is the one
+    /// of the main line.
+    #[must_use]
+    pub fn to_pgn_with_tags(&self, tags: &PgnTags) -> String {
+        let root = self.game(self.root()).expect("the root can not be deleted");
+        let end = self.mainline().last().unwrap_or(self.root());
+        let result = self.game(end).map_or("*", Game::pgn_result);
+
+        let mut pgn = String::new();
+        push_tags(&mut pgn, tags, result, root.variant, &root.to_fen());
+        let mut tokens = Vec::new();
+        self.push_line_tokens(&mut tokens, self.root(), true);
+        tokens.push(result.to_string());
+        push_movetext(&mut pgn, tokens);
+        pgn
+    }
+
+    /// Adds the moves of the line after `id` and, after each of them, its
+    /// alternatives as variations.
+    fn push_line_tokens(&self, tokens: &mut Vec<String>, id: NodeId, needs_number: bool) {
+        let mut needs_number = needs_number;
+        let mut id = id;
+        while let Some(next) = self.next(id) {
+            needs_number = self.push_node_tokens(tokens, id, next, needs_number);
+            for variation in self.variations(id) {
+                tokens.push(String::from("("));
+                let needs_number = self.push_node_tokens(tokens, id, *variation, true);
+                self.push_line_tokens(tokens, *variation, needs_number);
+                tokens.push(String::from(")"));
+            }
+            needs_number |= !self.variations(id).is_empty();
+            id = next;
+        }
+    }
+
+    fn push_node_tokens(
+        &self,
+        tokens: &mut Vec<String>,
+        parent: NodeId,
+        id: NodeId,
+        needs_number: bool,
+    ) -> bool {
+        let (Some(game), Some(played)) = (self.game(parent), self.played_move(id)) else {
+            return false;
+        };
+        let annotation = self.annotation(id).cloned().unwrap_or_default();
+        push_move_tokens(
+            tokens,
+            played,
+            &annotation,
+            (game.turn, game.fullmove_number),
+            needs_number,
+        )
+    }
+
+    /// Imports the first game of a PGN with its variations, comments after moves
+    /// and NAGs, also written as `!`, `?`, `!!`, `??`, `!?` and `?!`. Comments
+    /// before the first move of the game or of a variation are dropped. A decisive
+    /// result without mate is kept as resignation and a draw without reason as
+    /// agreed draw.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for malformed tags, comments or variations and for moves
+    /// that are not legal.
+    pub fn from_pgn(pgn: &str) -> Result<GameTree, PgnError> {
+        let (tags, movetext) = parse_tags(pgn)?;
+        let tag = |name: &str| {
+            tags.iter()
+                .find(|(tag, _)| tag == name)
+                .map(|(_, value)| value.as_str())
+        };
+        let variant = match tag("Variant") {
+            None => Variant::Standard,
+            Some(variant) if variant.eq_ignore_ascii_case("standard") => Variant::Standard,
+            Some(variant) if variant.eq_ignore_ascii_case("antichess") => Variant::Antichess,
+            Some(variant) => return Err(PgnError::UnknownVariant(variant.to_string())),
+        };
+        let game = match tag("FEN") {
+            Some(fen) => Game::from_fen_with_variant(fen, variant).map_err(PgnError::InvalidFen)?,
+            None => Game::with_variant(variant),
+        };
+
+        let mut tree = GameTree::new(game);
+        let mut current = tree.root();
+        // the nodes to go back to when the open variations end
+        let mut variations = Vec::new();
+        let mut result = None;
+        for token in tokenize(movetext)? {
+            match token {
+                Token::Move(san) => {
+                    let illegal_move = || PgnError::IllegalMove(san.to_string());
+                    let game = tree.game(current).ok_or_else(illegal_move)?;
+                    let mv = game.move_from_san(san).ok_or_else(illegal_move)?;
+                    current = tree.add_move(current, &mv).map_err(|_| illegal_move())?;
+                }
+                Token::Nag(nag) => {
+                    if let Some(annotation) = tree.annotation_mut(current) {
+                        annotation.nags.push(nag);
+                    }
+                }
+                Token::Comment(comment) => {
+                    let at_variation_start = variations
+                        .last()
+                        .is_some_and(|node| tree.parent(*node) == Some(current));
+                    if let Some(annotation) =
+                        tree.annotation_mut(current).filter(|_| !at_variation_start)
+                    {
+                        annotation.comment = Some(match annotation.comment.take() {
+                            Some(previous) => format!("{previous} {comment}"),
+                            None => comment.to_string(),
+                        });
+                    }
+                }
+                Token::StartVariation => {
+                    let parent = tree.parent(current).ok_or(PgnError::UnbalancedVariation)?;
+                    variations.push(current);
+                    current = parent;
+                }
+                Token::EndVariation => {
+                    current = variations.pop().ok_or(PgnError::UnbalancedVariation)?;
+                }
+                Token::Result(token) if variations.is_empty() => {
+                    result = Some(token);
+                    break;
+                }
+                Token::Result(_) => {}
+            }
+        }
+        if !variations.is_empty() {
+            return Err(PgnError::UnbalancedVariation);
+        }
+
+        let end = tree.mainline().last().unwrap_or(tree.root());
+        if let Some(game) = tree
+            .game_mut(end)
+            .filter(|game| game.final_status.is_none())
+        {
+            game.final_status = match result {
+                Some("1-0") => Some(GameStatus::Resignation(Color::White)),
+                Some("0-1") => Some(GameStatus::Resignation(Color::Black)),
+                Some("1/2-1/2") => Some(GameStatus::Draw(DrawReason::Agreement)),
+                _ => None,
+            };
+        }
+        Ok(tree)
+    }
+}
+
+/// Names and values of tag pairs.
+type Tags = Vec<(String, String)>;
+
+/// Splits a PGN into its tag pairs and the movetext after them.
+fn parse_tags(pgn: &str) -> Result<(Tags, &str), PgnError> {
+    let mut tags = Vec::new();
+    let mut rest = pgn.trim_start();
+    while let Some(tag) = rest.strip_prefix('[') {
+        let invalid_tag =
+            || PgnError::InvalidTag(tag.lines().next().unwrap_or_default().to_string());
+        let tag = tag.trim_start();
+        let name_end = tag.find(char::is_whitespace).ok_or_else(invalid_tag)?;
+        let (name, value) = tag.split_at(name_end);
+        let mut chars = value
+            .trim_start()
+            .strip_prefix('"')
+            .ok_or_else(invalid_tag)?
+            .char_indices();
+        let mut unescaped = String::new();
+        let value_end = loop {
+            match chars.next().ok_or_else(invalid_tag)? {
+                (_, '\\') => unescaped.push(chars.next().ok_or_else(invalid_tag)?.1),
+                (i, '"') => break i,
+                (_, c) => unescaped.push(c),
+            }
+        };
+        let after_value = &value.trim_start()[1 + value_end + 1..];
+        rest = after_value
+            .trim_start()
+            .strip_prefix(']')
+            .ok_or_else(invalid_tag)?
+            .trim_start();
+        tags.push((name.to_string(), unescaped));
+    }
+    Ok((tags, rest))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token<'a> {
+    Move(&'a str),
+    Nag(u8),
+    Comment(&'a str),
+    StartVariation,
+    EndVariation,
+    Result(&'a str),
+}
+
+fn tokenize(movetext: &str) -> Result<Vec<Token<'_>>, PgnError> {
+    let is_delimiter = |c: char| c.is_whitespace() || "{}();$".contains(c);
+    let mut tokens = Vec::new();
+    let mut rest = movetext;
+    while let Some(c) = rest.chars().next() {
+        match c {
+            c if c.is_whitespace() => rest = rest.trim_start(),
+            '{' => {
+                let end = rest.find('}').ok_or(PgnError::UnterminatedComment)?;
+                tokens.push(Token::Comment(rest[1..end].trim()));
+                rest = &rest[end + 1..];
+            }
+            ';' => {
+                let end = rest.find('\n').unwrap_or(rest.len());
+                tokens.push(Token::Comment(rest[1..end].trim()));
+                rest = &rest[end..];
+            }
+            '(' => {
+                tokens.push(Token::StartVariation);
+                rest = &rest[1..];
+            }
+            ')' => {
+                tokens.push(Token::EndVariation);
+                rest = &rest[1..];
+            }
+            _ => {
+                let end = rest[1..].find(is_delimiter).map_or(rest.len(), |i| i + 1);
+                let word = &rest[..end];
+                rest = &rest[end..];
+                if let Some(nag) = word.strip_prefix('$') {
+                    let nag = nag
+                        .parse()
+                        .map_err(|_| PgnError::InvalidNag(word.to_string()))?;
+                    tokens.push(Token::Nag(nag));
+                } else if ["1-0", "0-1", "1/2-1/2", "*"].contains(&word) {
+                    tokens.push(Token::Result(word));
+                } else {
+                    push_move_token(&mut tokens, word);
+                }
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+/// Adds a move that may have a move number in front and a suffix annotation.
+fn push_move_token<'a>(tokens: &mut Vec<Token<'a>>, word: &'a str) {
+    let without_number = word.trim_start_matches(|c: char| c.is_ascii_digit());
+    let san = match without_number.strip_prefix('.') {
+        Some(san) => san.trim_start_matches('.'),
+        None => word,
+    };
+    if san.is_empty() {
+        return;
+    }
+    let without_suffix = san.trim_end_matches(['!', '?']);
+    tokens.push(Token::Move(without_suffix));
+    let suffix = &san[without_suffix.len()..];
+    if let Some((_, nag)) = SUFFIX_NAGS.iter().find(|(s, _)| *s == suffix) {
+        tokens.push(Token::Nag(*nag));
+    }
+}
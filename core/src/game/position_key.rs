@@ -0,0 +1,31 @@
+use super::{Board, Color, Game, Position, COLOR_COUNT};
+
+/// Everything that makes two positions the same by the repetition rules: the
+/// pieces, the side to move, castling rights and an en passant square that can
+/// actually be used. Unlike [`Game`] it ignores the history and move counters,
+/// so transpositions compare equal, e.g. for transposition tables or opening books.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PositionKey {
+    pub board: Board,
+    pub turn: Color,
+    pub able_to_long_castle: [bool; COLOR_COUNT],
+    pub able_to_short_castle: [bool; COLOR_COUNT],
+    pub en_passant_square: Option<Position>,
+}
+
+impl Game {
+    /// Key of the current position, see [`PositionKey`]. Equal keys have the
+    /// same [`Game::zobrist_key`].
+    #[must_use]
+    pub fn position_key(&self) -> PositionKey {
+        PositionKey {
+            board: self.board,
+            turn: self.turn,
+            able_to_long_castle: self.able_to_long_castle,
+            able_to_short_castle: self.able_to_short_castle,
+            en_passant_square: self
+                .en_passant_square
+                .filter(|pos| self.en_passant_capture_possible(*pos)),
+        }
+    }
+}
@@ -0,0 +1,15 @@
+use super::{Game, Move};
+use rand::seq::SliceRandom;
+pub use rand::{Rng, SeedableRng};
+
+/// Random number generator for bots. The same seed gives the same moves, so a
+/// game can be replayed with e.g. `BotRng::seed_from_u64(42)`.
+pub type BotRng = rand::rngs::SmallRng;
+
+impl Game {
+    /// A random legal move of the side to move, `None` if there is none.
+    #[must_use]
+    pub fn random_move<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<Move> {
+        self.legal_moves().choose(rng).copied()
+    }
+}
@@ -0,0 +1,298 @@
+use super::{Color, Game, Piece, PieceType, Position};
+use alloc::format;
+use alloc::string::String;
+#[cfg(feature = "png")]
+use alloc::{string::ToString, vec::Vec};
+#[cfg(feature = "png")]
+use core::fmt::{self, Formatter};
+
+/// Side length of a square in SVG user units, the image is scaled to
+/// [`SvgRenderer::size`].
+const SQUARE: u32 = 45;
+
+const FONT_FAMILY: &str = "DejaVu Sans, Segoe UI Symbol, sans-serif";
+
+/// Colors of a board image as CSS colors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Theme {
+    pub light_square: String,
+    pub dark_square: String,
+    /// Drawn over the squares of the last move.
+    pub last_move: String,
+    /// Drawn under a king in check.
+    pub check: String,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::brown()
+    }
+}
+
+impl Theme {
+    #[must_use]
+    pub fn brown() -> Theme {
+        Theme {
+            light_square: String::from("#f0d9b5"),
+            dark_square: String::from("#b58863"),
+            last_move: String::from("#cdd26a"),
+            check: String::from("#e05050"),
+        }
+    }
+
+    #[must_use]
+    pub fn blue() -> Theme {
+        Theme {
+            light_square: String::from("#dee3e6"),
+            dark_square: String::from("#8ca2ad"),
+            last_move: String::from("#9bc700"),
+            check: String::from("#e05050"),
+        }
+    }
+
+    #[must_use]
+    pub fn green() -> Theme {
+        Theme {
+            light_square: String::from("#ffffdd"),
+            dark_square: String::from("#86a666"),
+            last_move: String::from("#f6f669"),
+            check: String::from("#e05050"),
+        }
+    }
+}
+
+/// Why a board image could not be rasterized.
+#[cfg(feature = "png")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RenderError {
+    /// Images need to be at least one pixel wide.
+    InvalidSize(u32),
+    InvalidSvg(String),
+    Encoding(String),
+}
+
+#[cfg(feature = "png")]
+impl fmt::Display for RenderError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            RenderError::InvalidSize(size) => write!(f, "invalid image size {size}"),
+            RenderError::InvalidSvg(e) => write!(f, "could not read the SVG: {e}"),
+            RenderError::Encoding(e) => write!(f, "could not encode the PNG: {e}"),
+        }
+    }
+}
+
+#[cfg(feature = "png")]
+impl core::error::Error for RenderError {}
+
+/// Draws the board as an image, e.g. for diagrams.
+///
+/// ```
+/// use rusty_chess_core::game::{Game, SvgRenderer, Theme};
+///
+/// let svg = SvgRenderer::new()
+///     .size(240)
+///     .theme(Theme::blue())
+///     .render(&Game::new());
+/// assert!(svg.starts_with("<svg"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SvgRenderer {
+    size: u32,
+    flipped: bool,
+    coordinates: bool,
+    last_move: bool,
+    theme: Theme,
+}
+
+impl Default for SvgRenderer {
+    fn default() -> Self {
+        SvgRenderer {
+            size: 360,
+            flipped: false,
+            coordinates: true,
+            last_move: true,
+            theme: Theme::default(),
+        }
+    }
+}
+
+impl SvgRenderer {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Width and height of the image in pixels.
+    #[must_use]
+    pub const fn size(mut self, size: u32) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Black at the bottom.
+    #[must_use]
+    pub const fn flipped(mut self, flipped: bool) -> Self {
+        self.flipped = flipped;
+        self
+    }
+
+    /// Rank numbers and file letters on the edge squares.
+    #[must_use]
+    pub const fn coordinates(mut self, coordinates: bool) -> Self {
+        self.coordinates = coordinates;
+        self
+    }
+
+    /// Highlights the squares of the last move.
+    #[must_use]
+    pub const fn last_move(mut self, last_move: bool) -> Self {
+        self.last_move = last_move;
+        self
+    }
+
+    #[must_use]
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Column and row of `pos` in the image, counted from the top left.
+    fn square_origin(&self, pos: Position) -> (u32, u32) {
+        let x = u32::from(pos.0 as u8 - b'a');
+        let y = u32::from(b'8' - pos.1 as u8);
+        if self.flipped {
+            ((7 - x) * SQUARE, (7 - y) * SQUARE)
+        } else {
+            (x * SQUARE, y * SQUARE)
+        }
+    }
+
+    fn push_square(&self, svg: &mut String, pos: Position, fill: &str) {
+        let (x, y) = self.square_origin(pos);
+        svg.push_str(&format!(
+            "<rect x=\"{x}\" y=\"{y}\" width=\"{SQUARE}\" height=\"{SQUARE}\" fill=\"{fill}\"/>"
+        ));
+    }
+
+    /// The position as SVG image.
+    #[must_use]
+    pub fn render(&self, game: &Game) -> String {
+        let board_size = 8 * SQUARE;
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{0}\" \
+             viewBox=\"0 0 {board_size} {board_size}\">",
+            self.size
+        );
+        let theme = &self.theme;
+        for (index, square) in game.board.iter().enumerate() {
+            let pos = Position::try_from(index).expect("the board has 64 squares");
+            let light = (index / 8 + index % 8) % 2 == 1;
+            let fill = if light {
+                &theme.light_square
+            } else {
+                &theme.dark_square
+            };
+            self.push_square(&mut svg, pos, fill);
+            if *square == Some(Piece::new(PieceType::King, game.turn)) && game.is_check() {
+                self.push_square(&mut svg, pos, &theme.check);
+            }
+            if self.coordinates {
+                let fill = if light {
+                    &theme.dark_square
+                } else {
+                    &theme.light_square
+                };
+                self.push_coordinates(&mut svg, pos, fill);
+            }
+        }
+        if let Some(played) = game.history.last().filter(|_| self.last_move) {
+            for pos in [played.mv.from, played.mv.to] {
+                let (x, y) = self.square_origin(pos);
+                svg.push_str(&format!(
+                    "<rect x=\"{x}\" y=\"{y}\" width=\"{SQUARE}\" height=\"{SQUARE}\" fill=\"{}\" \
+                     fill-opacity=\"0.5\"/>",
+                    theme.last_move
+                ));
+            }
+        }
+        for (pos, piece) in game.pieces() {
+            self.push_piece(&mut svg, pos, piece);
+        }
+        svg.push_str("</svg>");
+        svg
+    }
+
+    /// Rank number on the left and file letter on the bottom edge of the image.
+    fn push_coordinates(&self, svg: &mut String, pos: Position, fill: &str) {
+        let (x, y) = self.square_origin(pos);
+        let (left_file, bottom_rank) = if self.flipped { ('h', '8') } else { ('a', '1') };
+        let style = format!("font-family=\"{FONT_FAMILY}\" font-size=\"9\" fill=\"{fill}\"");
+        if pos.0 == left_file {
+            svg.push_str(&format!(
+                "<text x=\"{}\" y=\"{}\" {style}>{}</text>",
+                x + 2,
+                y + 10,
+                pos.1
+            ));
+        }
+        if pos.1 == bottom_rank {
+            svg.push_str(&format!(
+                "<text x=\"{}\" y=\"{}\" {style}>{}</text>",
+                x + SQUARE - 7,
+                y + SQUARE - 2,
+                pos.0
+            ));
+        }
+    }
+
+    /// Pieces are drawn with the filled chess glyphs, white ones with an outline.
+    fn push_piece(&self, svg: &mut String, pos: Position, piece: Piece) {
+        let glyph = match piece.piece_type {
+            PieceType::King => '\u{265A}',
+            PieceType::Queen => '\u{265B}',
+            PieceType::Rook => '\u{265C}',
+            PieceType::Bishop => '\u{265D}',
+            PieceType::Knight => '\u{265E}',
+            PieceType::Pawn => '\u{265F}',
+        };
+        let (fill, stroke) = match piece.color {
+            Color::White => ("#ffffff", "#000000"),
+            Color::Black => ("#000000", "#000000"),
+        };
+        let (x, y) = self.square_origin(pos);
+        svg.push_str(&format!(
+            "<text x=\"{}\" y=\"{}\" font-family=\"{FONT_FAMILY}\" \
+             font-size=\"38\" text-anchor=\"middle\" fill=\"{fill}\" stroke=\"{stroke}\" \
+             stroke-width=\"1.2\">{glyph}</text>",
+            x + SQUARE / 2,
+            y + SQUARE - 8
+        ));
+    }
+
+    /// The position as PNG image, rendered with the fonts of the system.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the size is 0.
+    #[cfg(feature = "png")]
+    pub fn render_png(&self, game: &Game) -> Result<Vec<u8>, RenderError> {
+        use resvg::{tiny_skia, usvg};
+
+        let mut options = usvg::Options::default();
+        options.fontdb_mut().load_system_fonts();
+        let tree = usvg::Tree::from_str(&self.render(game), &options)
+            .map_err(|e| RenderError::InvalidSvg(e.to_string()))?;
+        let mut pixmap = tiny_skia::Pixmap::new(self.size, self.size)
+            .ok_or(RenderError::InvalidSize(self.size))?;
+        let scale = self.size as f32 / tree.size().width();
+        resvg::render(
+            &tree,
+            tiny_skia::Transform::from_scale(scale, scale),
+            &mut pixmap.as_mut(),
+        );
+        pixmap
+            .encode_png()
+            .map_err(|e| RenderError::Encoding(e.to_string()))
+    }
+}
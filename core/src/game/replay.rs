@@ -0,0 +1,95 @@
+use super::{Board, Color, Game, PlayedMove};
+
+/// Walks through the positions of a game without changing it, e.g. to review
+/// it. Every position is available right away, see [`Game::replay`].
+#[derive(Debug, Clone, Copy)]
+pub struct GameReplay<'a> {
+    game: &'a Game,
+    ply: usize,
+}
+
+impl<'a> GameReplay<'a> {
+    /// Number of half moves played, the last position has this ply.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.game.history.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.game.history.is_empty()
+    }
+
+    /// Half moves played up to the current position, 0 for the starting position.
+    #[must_use]
+    pub const fn ply(&self) -> usize {
+        self.ply
+    }
+
+    /// Jumps to the position after `ply` half moves. Returns `false` and stays
+    /// if the game is shorter.
+    pub fn goto(&mut self, ply: usize) -> bool {
+        if ply > self.len() {
+            return false;
+        }
+        self.ply = ply;
+        true
+    }
+
+    /// Steps one half move forward, `false` at the last position.
+    pub fn forward(&mut self) -> bool {
+        self.goto(self.ply + 1)
+    }
+
+    /// Steps one half move back, `false` at the starting position.
+    pub fn back(&mut self) -> bool {
+        self.ply > 0 && self.goto(self.ply - 1)
+    }
+
+    #[must_use]
+    pub fn board(&self) -> &'a Board {
+        match self.game.undo_stack.get(self.ply) {
+            Some(state) => &state.board,
+            None => &self.game.board,
+        }
+    }
+
+    #[must_use]
+    pub fn turn(&self) -> Color {
+        match self.game.undo_stack.get(self.ply) {
+            Some(state) => state.turn,
+            None => self.game.turn,
+        }
+    }
+
+    /// The move that led to the current position.
+    #[must_use]
+    pub fn last_move(&self) -> Option<&'a PlayedMove> {
+        self.ply.checked_sub(1).map(|ply| &self.game.history[ply])
+    }
+}
+
+impl Game {
+    /// Replay of the game, starting at its first position.
+    #[must_use]
+    pub const fn replay(&self) -> GameReplay<'_> {
+        GameReplay { game: self, ply: 0 }
+    }
+
+    /// Takes back or plays again moves until `ply` half moves were played, see
+    /// [`Game::undo`] and [`Game::redo`]. Returns `false` if the game can not
+    /// get there, e.g. because the moves after the current one are unknown.
+    pub fn goto_ply(&mut self, ply: usize) -> bool {
+        while self.history.len() > ply {
+            if self.undo().is_none() {
+                return false;
+            }
+        }
+        while self.history.len() < ply {
+            if self.redo().is_none() {
+                return false;
+            }
+        }
+        true
+    }
+}
@@ -0,0 +1,146 @@
+use super::{Game, Move, MoveType, PieceType, Position, UserInput, ALL_POSSIBLE_SQUARES};
+use alloc::string::String;
+use alloc::vec::Vec;
+
+pub(super) const fn piece_type_to_san_char(piece_type: PieceType) -> char {
+    match piece_type {
+        PieceType::Pawn => 'P',
+        PieceType::Knight => 'N',
+        PieceType::Bishop => 'B',
+        PieceType::Rook => 'R',
+        PieceType::Queen => 'Q',
+        PieceType::King => 'K',
+    }
+}
+
+impl Move {
+    /// Standard algebraic notation of the move, see [`Game::san`].
+    #[must_use]
+    pub fn to_san(&self, game: &Game) -> String {
+        game.san(self)
+    }
+}
+
+impl Game {
+    /// Standard algebraic notation of a legal move in the current position
+    /// (e.g. `Nbd7`, `exd6`, `O-O+` or `e8=Q#`), including disambiguation and
+    /// check or mate suffix. A promotion without chosen piece is written as
+    /// promotion to a queen.
+    #[must_use]
+    pub fn san(&self, mv: &Move) -> String {
+        let mut game_after_move = self.clone();
+        let input = if mv.is_promotion() {
+            let piece_type = mv.promotion.unwrap_or(PieceType::Queen);
+            UserInput::MoveWithPromotion(mv.from, mv.to, piece_type)
+        } else {
+            UserInput::Move(mv.from, mv.to)
+        };
+        match game_after_move.apply_input(&input) {
+            Ok(_) => game_after_move
+                .history
+                .pop()
+                .map(|played| played.san)
+                .unwrap_or_default(),
+            // not a legal move here, so there is no resulting position to check
+            Err(_) => self.san_without_suffix(mv),
+        }
+    }
+
+    /// Parses a move in standard algebraic notation and returns it if it is
+    /// legal for the side to move. Check, mate and annotation suffixes are
+    /// ignored, castling may also be written with zeros (`0-0`) and the `=`
+    /// of promotions may be left out (`e8Q`).
+    #[must_use]
+    pub fn move_from_san(&self, san: &str) -> Option<Move> {
+        let mut san: Vec<char> = san
+            .trim()
+            .trim_end_matches(['+', '#', '!', '?'])
+            .chars()
+            .map(|c| if c == '0' { 'O' } else { c })
+            .collect();
+        let promotion = match san.as_slice() {
+            [file, .., piece] if file.is_ascii_lowercase() && piece.is_ascii_uppercase() => {
+                let piece_type = self
+                    .promotion_piece_types()
+                    .iter()
+                    .find(|piece_type| piece_type_to_san_char(**piece_type) == *piece)
+                    .copied()?;
+                san.pop();
+                if san.last() == Some(&'=') {
+                    san.pop();
+                }
+                Some(piece_type)
+            }
+            _ => None,
+        };
+        let san: String = san.into_iter().collect();
+        let mut mv = self
+            .get_all_currently_valid_moves()
+            .into_iter()
+            .find(|mv| {
+                mv.is_promotion() == promotion.is_some() && self.san_without_suffix(mv) == san
+            })?;
+        mv.promotion = promotion;
+        Some(mv)
+    }
+
+    /// Standard algebraic notation of a legal move in the current position,
+    /// without promotion piece and without check or mate suffix.
+    pub(super) fn san_without_suffix(&self, mv: &Move) -> String {
+        match mv.move_type {
+            MoveType::ShortCastle => return String::from("O-O"),
+            MoveType::LongCastle => return String::from("O-O-O"),
+            _ => {}
+        }
+
+        let mut san = String::new();
+        if mv.piece.piece_type == PieceType::Pawn {
+            if mv.captured_piece.is_some() {
+                san.push(mv.from.0);
+                san.push('x');
+            }
+        } else {
+            san.push(piece_type_to_san_char(mv.piece.piece_type));
+
+            // other pieces of the same kind that could also go to the target square
+            let ambiguous: Vec<Position> = ALL_POSSIBLE_SQUARES
+                .iter()
+                .map(|(x, y)| Position(*x, *y))
+                .filter(|pos| *pos != mv.from && self.board[pos.as_index()] == Some(mv.piece))
+                .filter(|pos| {
+                    self.get_valid_moves(*pos)
+                        .iter()
+                        .any(|other| other.to == mv.to)
+                })
+                .collect();
+            if !ambiguous.is_empty() {
+                if ambiguous.iter().all(|pos| pos.0 != mv.from.0) {
+                    san.push(mv.from.0);
+                } else if ambiguous.iter().all(|pos| pos.1 != mv.from.1) {
+                    san.push(mv.from.1);
+                } else {
+                    san.push(mv.from.0);
+                    san.push(mv.from.1);
+                }
+            }
+
+            if mv.captured_piece.is_some() {
+                san.push('x');
+            }
+        }
+        san.push(mv.to.0);
+        san.push(mv.to.1);
+        san
+    }
+
+    /// Check or mate suffix for the side to move, to be called after a move was played.
+    pub(super) fn san_suffix(&self, checkmate: bool) -> &'static str {
+        if !self.check(self.turn) {
+            ""
+        } else if checkmate {
+            "#"
+        } else {
+            "+"
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use super::{Color, FenError, Game, GameStatus, UserInput, Variant};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{self, Formatter};
+use serde::{Deserialize, Serialize};
+
+/// Version written by [`Game::save_json`]. It only changes when old readers can
+/// not understand a save anymore, new optional fields keep the version.
+pub const SAVE_FORMAT_VERSION: u32 = 1;
+
+/// Why a save game could not be loaded with [`Game::load_json`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LoadError {
+    InvalidJson(String),
+    /// The save was written by a newer version of the library.
+    UnsupportedVersion(u32),
+    InvalidFen(FenError),
+    /// The move with this index is not legal in the saved game.
+    InvalidMove(usize, String),
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::InvalidJson(e) => write!(f, "invalid save game: {e}"),
+            LoadError::UnsupportedVersion(version) => write!(
+                f,
+                "save game version {version} is newer than the supported version {SAVE_FORMAT_VERSION}"
+            ),
+            LoadError::InvalidFen(e) => write!(f, "invalid starting position: {e}"),
+            LoadError::InvalidMove(index, mv) => write!(f, "move {} '{mv}' is not legal", index + 1),
+        }
+    }
+}
+
+impl core::error::Error for LoadError {}
+
+/// The game as a starting position and the moves played from it, so saves do
+/// not depend on how the game is stored internally. Unknown fields are ignored
+/// and missing optional ones take their default.
+#[derive(Serialize, Deserialize)]
+struct SaveGame {
+    version: u32,
+    #[serde(default)]
+    variant: Variant,
+    /// `None` for the starting position of the variant.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    fen: Option<String>,
+    /// Moves in UCI notation.
+    #[serde(default)]
+    moves: Vec<String>,
+    /// Results that do not follow from the moves, like a resignation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    final_status: Option<GameStatus>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    draw_offer: Option<Color>,
+}
+
+impl Game {
+    /// The game as JSON that can be read again with [`Game::load_json`], also
+    /// by later versions of the library.
+    #[must_use]
+    pub fn save_json(&self) -> String {
+        let save = SaveGame {
+            version: SAVE_FORMAT_VERSION,
+            variant: self.variant,
+            fen: self.starting_fen.clone(),
+            moves: self
+                .history
+                .iter()
+                .map(|played| played.mv.to_uci())
+                .collect(),
+            final_status: self.final_status,
+            draw_offer: self.draw_offer,
+        };
+        serde_json::to_string(&save).expect("save games only contain strings and numbers")
+    }
+
+    /// Replays a game saved with [`Game::save_json`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JSON is malformed, comes from a newer format
+    /// version or its moves are not legal.
+    pub fn load_json(json: &str) -> Result<Game, LoadError> {
+        let save: SaveGame =
+            serde_json::from_str(json).map_err(|e| LoadError::InvalidJson(e.to_string()))?;
+        if save.version > SAVE_FORMAT_VERSION {
+            return Err(LoadError::UnsupportedVersion(save.version));
+        }
+        let mut game = match &save.fen {
+            Some(fen) => {
+                Game::from_fen_with_variant(fen, save.variant).map_err(LoadError::InvalidFen)?
+            }
+            None => Game::with_variant(save.variant),
+        };
+        for (index, uci) in save.moves.iter().enumerate() {
+            let invalid_move = || LoadError::InvalidMove(index, uci.clone());
+            let mv = game.move_from_uci(uci).ok_or_else(invalid_move)?;
+            let input = match mv.promotion {
+                Some(piece_type) => UserInput::MoveWithPromotion(mv.from, mv.to, piece_type),
+                None => UserInput::Move(mv.from, mv.to),
+            };
+            game.process_input(&input).map_err(|_| invalid_move())?;
+        }
+        if game.final_status.is_none() {
+            game.final_status = save.final_status.filter(GameStatus::is_finished);
+            game.draw_offer = save.draw_offer;
+        }
+        Ok(game)
+    }
+}
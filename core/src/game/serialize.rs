@@ -0,0 +1,39 @@
+use super::{Board, Piece, Position, TOTAL_SQUARES};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use serde::de::{self, Deserializer};
+use serde::ser::Serializer;
+use serde::{Deserialize, Serialize};
+
+/// Squares are written in algebraic notation, e.g. `"e4"`.
+impl Serialize for Position {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Position {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(de::Error::custom)
+    }
+}
+
+/// The board as a sequence of its 64 squares starting at a1, serde only
+/// implements arrays up to 32 elements.
+pub(super) mod board {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(board: &Board, serializer: S) -> Result<S::Ok, S::Error> {
+        board.as_slice().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Board, D::Error> {
+        let squares = Vec::<Option<Piece>>::deserialize(deserializer)?;
+        let len = squares.len();
+        squares.try_into().map_err(|_| {
+            de::Error::invalid_length(len, &format!("{TOTAL_SQUARES} squares").as_str())
+        })
+    }
+}
@@ -0,0 +1,39 @@
+use super::{CloneCounter, Game};
+use alloc::vec;
+use alloc::vec::Vec;
+
+impl Game {
+    /// The current position as a new game without history, captured pieces,
+    /// undo steps and earlier positions for repetitions, so it is cheap to
+    /// clone, e.g. for a bot trying out moves. The computed attacks and legal
+    /// moves are kept.
+    #[must_use]
+    pub fn snapshot(&self) -> Game {
+        Game {
+            turn: self.turn,
+            board: self.board,
+            captured: [Vec::new(), Vec::new()],
+            history: Vec::new(),
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            starting_fen: Some(self.to_fen()),
+            awaiting_promotion: self.awaiting_promotion,
+            variant: self.variant,
+            final_status: self.final_status,
+            draw_offer: None,
+            clock: None,
+            position_keys: vec![self.zobrist_key()],
+            number_of_moves_without_captures_or_pawn_moves: self
+                .number_of_moves_without_captures_or_pawn_moves,
+            fullmove_number: self.fullmove_number,
+            en_passant_square: self.en_passant_square,
+            able_to_long_castle: self.able_to_long_castle,
+            able_to_short_castle: self.able_to_short_castle,
+            hash: self.hash,
+            protected_squares: self.protected_squares.clone(),
+            pieces_attacking_king: self.pieces_attacking_king.clone(),
+            legal_moves: self.legal_moves.clone(),
+            clone_counter: CloneCounter,
+        }
+    }
+}
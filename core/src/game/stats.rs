@@ -0,0 +1,151 @@
+#[cfg(feature = "stats")]
+use core::sync::atomic::{AtomicU64, Ordering};
+#[cfg(feature = "stats")]
+use core::time::Duration;
+
+/// Parts of the move generation that are timed separately.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(super) enum Phase {
+    /// Finding the pieces giving check after every move.
+    Attacks,
+    /// Moves of the pieces by the movement rules.
+    MoveGeneration,
+    /// Removing the moves that leave the own king in check. In antichess this
+    /// also generates moves to look for captures.
+    Legality,
+}
+
+#[cfg(feature = "stats")]
+const PHASE_COUNT: usize = 3;
+
+#[cfg(feature = "stats")]
+static MOVES_GENERATED: AtomicU64 = AtomicU64::new(0);
+#[cfg(feature = "stats")]
+static POSITIONS_GENERATED: AtomicU64 = AtomicU64::new(0);
+#[cfg(feature = "stats")]
+static CLONES: AtomicU64 = AtomicU64::new(0);
+#[cfg(feature = "stats")]
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+#[cfg(feature = "stats")]
+static PHASE_NANOS: [AtomicU64; PHASE_COUNT] =
+    [AtomicU64::new(0), AtomicU64::new(0), AtomicU64::new(0)];
+
+/// Work done since the start of the program or the last [`Stats::reset`],
+/// summed over all games and threads. Only collected with the `stats` feature,
+/// without it the counting compiles to nothing.
+///
+/// ```
+/// use rusty_chess_core::game::{Game, Stats};
+///
+/// Stats::reset();
+/// Game::new().perft(2);
+/// let stats = Stats::get();
+/// assert!(stats.positions_generated >= 21);
+/// ```
+#[cfg(feature = "stats")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// Moves generated by the movement rules, legal or not.
+    pub moves_generated: u64,
+    /// Positions all legal moves were generated for.
+    pub positions_generated: u64,
+    /// Clones of [`Game`](super::Game), e.g. to try out moves.
+    pub clones: u64,
+    /// Move lists that did not fit on the stack, see
+    /// [`MOVE_LIST_CAPACITY`](super::MOVE_LIST_CAPACITY).
+    pub allocations: u64,
+    pub attacks_time: Duration,
+    pub move_generation_time: Duration,
+    pub legality_time: Duration,
+}
+
+#[cfg(feature = "stats")]
+impl Stats {
+    #[must_use]
+    pub fn get() -> Stats {
+        let time = |phase: Phase| {
+            Duration::from_nanos(PHASE_NANOS[phase as usize].load(Ordering::Relaxed))
+        };
+        Stats {
+            moves_generated: MOVES_GENERATED.load(Ordering::Relaxed),
+            positions_generated: POSITIONS_GENERATED.load(Ordering::Relaxed),
+            clones: CLONES.load(Ordering::Relaxed),
+            allocations: ALLOCATIONS.load(Ordering::Relaxed),
+            attacks_time: time(Phase::Attacks),
+            move_generation_time: time(Phase::MoveGeneration),
+            legality_time: time(Phase::Legality),
+        }
+    }
+
+    /// Sets all counters back to zero.
+    pub fn reset() {
+        for counter in [
+            &MOVES_GENERATED,
+            &POSITIONS_GENERATED,
+            &CLONES,
+            &ALLOCATIONS,
+        ]
+        .into_iter()
+        .chain(&PHASE_NANOS)
+        {
+            counter.store(0, Ordering::Relaxed);
+        }
+    }
+}
+
+#[inline]
+pub(super) fn count_moves(_count: usize) {
+    #[cfg(feature = "stats")]
+    MOVES_GENERATED.fetch_add(_count as u64, Ordering::Relaxed);
+}
+
+#[inline]
+pub(super) fn count_position() {
+    #[cfg(feature = "stats")]
+    POSITIONS_GENERATED.fetch_add(1, Ordering::Relaxed);
+}
+
+#[inline]
+pub(super) fn count_allocation() {
+    #[cfg(feature = "stats")]
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Counts the clones of the struct it is a field of.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(super) struct CloneCounter;
+
+impl Clone for CloneCounter {
+    #[inline]
+    fn clone(&self) -> Self {
+        #[cfg(feature = "stats")]
+        CLONES.fetch_add(1, Ordering::Relaxed);
+        CloneCounter
+    }
+}
+
+/// Adds the time until it is dropped to its phase.
+pub(super) struct Timer {
+    #[cfg(feature = "stats")]
+    phase: Phase,
+    #[cfg(feature = "stats")]
+    start: std::time::Instant,
+}
+
+#[inline]
+pub(super) fn timer(_phase: Phase) -> Timer {
+    Timer {
+        #[cfg(feature = "stats")]
+        phase: _phase,
+        #[cfg(feature = "stats")]
+        start: std::time::Instant::now(),
+    }
+}
+
+#[cfg(feature = "stats")]
+impl Drop for Timer {
+    fn drop(&mut self) {
+        let nanos = u64::try_from(self.start.elapsed().as_nanos()).unwrap_or(u64::MAX);
+        PHASE_NANOS[self.phase as usize].fetch_add(nanos, Ordering::Relaxed);
+    }
+}
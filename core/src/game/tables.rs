@@ -0,0 +1,128 @@
+#[cfg(not(feature = "wasm"))]
+use super::TOTAL_SQUARES;
+use super::{Color, Position, BOARD_SIZE, COLOR_COUNT};
+
+const KNIGHT_OFFSETS: [(i8, i8); 8] = [
+    (2, 1),
+    (2, -1),
+    (-2, 1),
+    (-2, -1),
+    (1, 2),
+    (-1, 2),
+    (1, -2),
+    (-1, -2),
+];
+
+const KING_OFFSETS: [(i8, i8); 8] = [
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+    (-1, 0),
+];
+
+/// Squares a knight attacks from every square, indexed by [`Position::as_index`].
+#[cfg(not(feature = "wasm"))]
+static KNIGHT_ATTACKS: [Targets; TOTAL_SQUARES] = attacks(&KNIGHT_OFFSETS);
+
+/// Squares a king attacks from every square, castling not included.
+#[cfg(not(feature = "wasm"))]
+static KING_ATTACKS: [Targets; TOTAL_SQUARES] = attacks(&KING_OFFSETS);
+
+/// Squares a pawn attacks diagonally from every square, indexed by its color
+/// first.
+#[cfg(not(feature = "wasm"))]
+static PAWN_ATTACKS: [[Targets; TOTAL_SQUARES]; COLOR_COUNT] = [
+    attacks(&PAWN_OFFSETS[Color::White as usize]),
+    attacks(&PAWN_OFFSETS[Color::Black as usize]),
+];
+
+const PAWN_OFFSETS: [[(i8, i8); 2]; COLOR_COUNT] = [[(1, 1), (-1, 1)], [(1, -1), (-1, -1)]];
+
+/// Looked up in the tables, with the `wasm` feature computed on every call
+/// instead to keep the binary small.
+#[cfg(not(feature = "wasm"))]
+pub(super) fn knight_attacks(index: usize) -> Targets {
+    KNIGHT_ATTACKS[index]
+}
+
+#[cfg(not(feature = "wasm"))]
+pub(super) fn king_attacks(index: usize) -> Targets {
+    KING_ATTACKS[index]
+}
+
+#[cfg(not(feature = "wasm"))]
+pub(super) fn pawn_attacks(color: Color, index: usize) -> Targets {
+    PAWN_ATTACKS[color as usize][index]
+}
+
+#[cfg(feature = "wasm")]
+pub(super) fn knight_attacks(index: usize) -> Targets {
+    targets(index, &KNIGHT_OFFSETS)
+}
+
+#[cfg(feature = "wasm")]
+pub(super) fn king_attacks(index: usize) -> Targets {
+    targets(index, &KING_OFFSETS)
+}
+
+#[cfg(feature = "wasm")]
+pub(super) fn pawn_attacks(color: Color, index: usize) -> Targets {
+    targets(index, &PAWN_OFFSETS[color as usize])
+}
+
+/// Up to 8 squares on the board as indices, in the order of the offsets they
+/// were computed from.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Targets {
+    squares: [u8; 8],
+    len: u8,
+}
+
+impl Targets {
+    pub(super) fn iter(self) -> impl Iterator<Item = Position> {
+        (0..self.len as usize).map(move |i| {
+            let index = self.squares[i];
+            Position(
+                (b'a' + index % BOARD_SIZE as u8) as char,
+                (b'1' + index / BOARD_SIZE as u8) as char,
+            )
+        })
+    }
+}
+
+#[cfg(not(feature = "wasm"))]
+const fn attacks(offsets: &[(i8, i8)]) -> [Targets; TOTAL_SQUARES] {
+    let mut table = [Targets {
+        squares: [0; 8],
+        len: 0,
+    }; TOTAL_SQUARES];
+    let mut index = 0;
+    while index < TOTAL_SQUARES {
+        table[index] = targets(index, offsets);
+        index += 1;
+    }
+    table
+}
+
+const fn targets(index: usize, offsets: &[(i8, i8)]) -> Targets {
+    let mut targets = Targets {
+        squares: [0; 8],
+        len: 0,
+    };
+    let x = (index % BOARD_SIZE) as i8;
+    let y = (index / BOARD_SIZE) as i8;
+    let mut i = 0;
+    while i < offsets.len() {
+        let (new_x, new_y) = (x + offsets[i].0, y + offsets[i].1);
+        if new_x >= 0 && new_x < BOARD_SIZE as i8 && new_y >= 0 && new_y < BOARD_SIZE as i8 {
+            targets.squares[targets.len as usize] = (new_y as u8) * BOARD_SIZE as u8 + new_x as u8;
+            targets.len += 1;
+        }
+        i += 1;
+    }
+    targets
+}
@@ -0,0 +1,214 @@
+use super::{Annotation, Game, Move, MoveError, PlayedMove, UserInput};
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// A position in a [`GameTree`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeId(usize);
+
+#[derive(Debug, Clone)]
+struct Node {
+    game: Game,
+    /// Annotation of the move that led here.
+    annotation: Annotation,
+    parent: Option<NodeId>,
+    /// The first child continues the main line, the others are variations.
+    children: Vec<NodeId>,
+}
+
+/// A game with variations: every position can be continued with several
+/// moves, the first one is the main line.
+#[derive(Debug, Clone)]
+pub struct GameTree {
+    /// `None` for deleted nodes, so the ids of the others stay valid.
+    nodes: Vec<Option<Node>>,
+}
+
+impl GameTree {
+    /// Tree with `game` in its current position as root.
+    #[must_use]
+    pub fn new(game: Game) -> GameTree {
+        GameTree {
+            nodes: vec![Some(Node {
+                game,
+                annotation: Annotation::default(),
+                parent: None,
+                children: Vec::new(),
+            })],
+        }
+    }
+
+    #[must_use]
+    pub const fn root(&self) -> NodeId {
+        NodeId(0)
+    }
+
+    fn node(&self, id: NodeId) -> Option<&Node> {
+        self.nodes.get(id.0).and_then(Option::as_ref)
+    }
+
+    /// The game in the position of `id`, `None` if the node was deleted.
+    #[must_use]
+    pub fn game(&self, id: NodeId) -> Option<&Game> {
+        self.node(id).map(|node| &node.game)
+    }
+
+    pub(super) fn game_mut(&mut self, id: NodeId) -> Option<&mut Game> {
+        self.nodes
+            .get_mut(id.0)
+            .and_then(Option::as_mut)
+            .map(|node| &mut node.game)
+    }
+
+    /// Annotation of the move that led to `id`, `None` for the root.
+    #[must_use]
+    pub fn annotation(&self, id: NodeId) -> Option<&Annotation> {
+        let node = self.node(id)?;
+        node.parent.map(|_| &node.annotation)
+    }
+
+    pub fn annotation_mut(&mut self, id: NodeId) -> Option<&mut Annotation> {
+        let node = self.nodes.get_mut(id.0).and_then(Option::as_mut)?;
+        node.parent.map(|_| &mut node.annotation)
+    }
+
+    /// The move that led to `id`, `None` for the root.
+    #[must_use]
+    pub fn played_move(&self, id: NodeId) -> Option<&PlayedMove> {
+        self.node(id)?.parent?;
+        self.game(id)?.history().last()
+    }
+
+    #[must_use]
+    pub fn parent(&self, id: NodeId) -> Option<NodeId> {
+        self.node(id)?.parent
+    }
+
+    /// Continuations of `id`, the main line first.
+    #[must_use]
+    pub fn children(&self, id: NodeId) -> &[NodeId] {
+        self.node(id).map_or(&[], |node| &node.children)
+    }
+
+    /// Continuations of `id` besides the main line.
+    #[must_use]
+    pub fn variations(&self, id: NodeId) -> &[NodeId] {
+        self.children(id).get(1..).unwrap_or_default()
+    }
+
+    /// The main line continuation of `id`.
+    #[must_use]
+    pub fn next(&self, id: NodeId) -> Option<NodeId> {
+        self.children(id).first().copied()
+    }
+
+    /// Nodes following the first child from `id` on, starting with `id` itself.
+    pub fn line_from(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        core::iter::successors(self.node(id).map(|_| id), |id| self.next(*id))
+    }
+
+    /// Nodes of the main line starting with the root.
+    pub fn mainline(&self) -> impl Iterator<Item = NodeId> + '_ {
+        self.line_from(self.root())
+    }
+
+    /// Whether `id` lies on the main line of the whole tree.
+    #[must_use]
+    pub fn is_mainline(&self, id: NodeId) -> bool {
+        let mut id = id;
+        while let Some(parent) = self.parent(id) {
+            if self.next(parent) != Some(id) {
+                return false;
+            }
+            id = parent;
+        }
+        id == self.root()
+    }
+
+    /// Plays `mv` in the position of `parent` and returns the resulting node.
+    /// A move that is already a continuation of `parent` is not added again.
+    /// The first move played from a position becomes its main line.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `mv` is not legal in the position of `parent` or is a
+    /// promotion without chosen piece, and [`MoveError::GameOver`] if `parent`
+    /// was deleted.
+    pub fn add_move(&mut self, parent: NodeId, mv: &Move) -> Result<NodeId, MoveError> {
+        if let Some(child) = self.children(parent).iter().find(|child| {
+            self.played_move(**child).is_some_and(|played| {
+                played.mv.from == mv.from
+                    && played.mv.to == mv.to
+                    && played.mv.promotion == mv.promotion
+            })
+        }) {
+            return Ok(*child);
+        }
+        if mv.is_promotion() && mv.promotion.is_none() {
+            return Err(MoveError::PromotionRequired(mv.to));
+        }
+        let mut game = self.game(parent).ok_or(MoveError::GameOver)?.clone();
+        let input = match mv.promotion {
+            Some(piece_type) => UserInput::MoveWithPromotion(mv.from, mv.to, piece_type),
+            None => UserInput::Move(mv.from, mv.to),
+        };
+        game.process_input(&input)?;
+        let id = NodeId(self.nodes.len());
+        self.nodes.push(Some(Node {
+            game,
+            annotation: Annotation::default(),
+            parent: Some(parent),
+            children: Vec::new(),
+        }));
+        if let Some(Some(node)) = self.nodes.get_mut(parent.0) {
+            node.children.push(id);
+        }
+        Ok(id)
+    }
+
+    /// Moves the variation of `id` one place up among its siblings, the first
+    /// place makes it the main line. Returns `false` if it already is.
+    pub fn promote(&mut self, id: NodeId) -> bool {
+        let Some(parent) = self.parent(id) else {
+            return false;
+        };
+        let Some(Some(node)) = self.nodes.get_mut(parent.0) else {
+            return false;
+        };
+        match node.children.iter().position(|child| *child == id) {
+            Some(index) if index > 0 => {
+                node.children.swap(index, index - 1);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Makes the variation of `id` the main line at every branching point
+    /// between it and the root.
+    pub fn promote_to_mainline(&mut self, id: NodeId) {
+        let mut id = id;
+        while let Some(parent) = self.parent(id) {
+            while self.promote(id) {}
+            id = parent;
+        }
+    }
+
+    /// Removes `id` with all positions following it. The root can not be
+    /// deleted. Returns `false` if there is nothing to delete.
+    pub fn delete(&mut self, id: NodeId) -> bool {
+        let Some(parent) = self.parent(id) else {
+            return false;
+        };
+        if let Some(Some(node)) = self.nodes.get_mut(parent.0) {
+            node.children.retain(|child| *child != id);
+        }
+        let mut to_delete = vec![id];
+        while let Some(id) = to_delete.pop() {
+            if let Some(node) = self.nodes.get_mut(id.0).and_then(Option::take) {
+                to_delete.extend(node.children);
+            }
+        }
+        true
+    }
+}
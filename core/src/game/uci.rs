@@ -0,0 +1,56 @@
+use super::{Game, Move, PieceType, Position};
+use alloc::string::String;
+use alloc::vec::Vec;
+
+impl Move {
+    /// Long algebraic notation as used by UCI, e.g. `e2e4`, `e1g1` for white
+    /// short castling or `e7e8q`.
+    #[must_use]
+    pub fn to_uci(&self) -> String {
+        let mut uci = String::with_capacity(5);
+        uci.push(self.from.0);
+        uci.push(self.from.1);
+        uci.push(self.to.0);
+        uci.push(self.to.1);
+        if let Some(piece_type) = self.promotion {
+            uci.push(match piece_type {
+                PieceType::Knight => 'n',
+                PieceType::Bishop => 'b',
+                PieceType::Rook => 'r',
+                _ => 'q',
+            });
+        }
+        uci
+    }
+}
+
+impl Game {
+    /// Parses a move in UCI notation and returns it if it is legal for the side
+    /// to move. Promotions have to name the piece (`e7e8q`), all other moves
+    /// must not.
+    #[must_use]
+    pub fn move_from_uci(&self, uci: &str) -> Option<Move> {
+        let chars: Vec<char> = uci.trim().chars().collect();
+        if chars.len() != 4 && chars.len() != 5 {
+            return None;
+        }
+        let from = Position::new(chars[0], chars[1])?;
+        let to = Position::new(chars[2], chars[3])?;
+
+        let mut mv = self.get_move_if_valid(from, to).ok()?;
+        match (mv.is_promotion(), chars.get(4)) {
+            (false, None) => {}
+            (true, Some(c)) => {
+                mv.promotion = Some(match c {
+                    'q' => PieceType::Queen,
+                    'r' => PieceType::Rook,
+                    'b' => PieceType::Bishop,
+                    'n' => PieceType::Knight,
+                    _ => return None,
+                });
+            }
+            _ => return None,
+        }
+        Some(mv)
+    }
+}
@@ -0,0 +1,114 @@
+use super::{Board, Color, Game, GameStatus, Move, PieceType, Position, UserInput, COLOR_COUNT};
+
+/// State of the game before a move that can not be derived from the move itself.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(super) struct UndoState {
+    pub(super) turn: Color,
+    #[cfg_attr(feature = "serde", serde(with = "super::serialize::board"))]
+    pub(super) board: Board,
+    able_to_long_castle: [bool; COLOR_COUNT],
+    able_to_short_castle: [bool; COLOR_COUNT],
+    number_of_moves_without_captures_or_pawn_moves: u16,
+    fullmove_number: u16,
+    en_passant_square: Option<Position>,
+    hash: u64,
+}
+
+/// A move that was taken back, with the piece chosen for a promotion.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(super) struct RedoMove {
+    from: Position,
+    to: Position,
+    promotion: Option<PieceType>,
+}
+
+impl Game {
+    pub(super) fn undo_state(&self) -> UndoState {
+        UndoState {
+            turn: self.turn,
+            board: self.board,
+            able_to_long_castle: self.able_to_long_castle,
+            able_to_short_castle: self.able_to_short_castle,
+            number_of_moves_without_captures_or_pawn_moves: self
+                .number_of_moves_without_captures_or_pawn_moves,
+            fullmove_number: self.fullmove_number,
+            en_passant_square: self.en_passant_square,
+            hash: self.hash,
+        }
+    }
+
+    /// Takes back the last move and returns it. A pawn that is still waiting for
+    /// its promotion piece goes back as well. The move can be played again with
+    /// [`Game::redo`] until a new move is played.
+    pub fn undo(&mut self) -> Option<Move> {
+        let state = self.undo_stack.pop()?;
+        let mv = self.history.pop()?.mv;
+
+        // a pawn waiting for its promotion did not complete the move yet
+        let promotion = if self.awaiting_promotion.is_some() {
+            None
+        } else {
+            self.position_keys.pop();
+            mv.promotion
+        };
+        self.redo_stack.push(RedoMove {
+            from: mv.from,
+            to: mv.to,
+            promotion,
+        });
+
+        if mv.captured_piece.is_some() {
+            self.captured[mv.piece.color as usize].pop();
+        }
+        self.awaiting_promotion = None;
+        self.final_status = None;
+        self.draw_offer = None;
+
+        self.turn = state.turn;
+        self.board = state.board;
+        self.able_to_long_castle = state.able_to_long_castle;
+        self.able_to_short_castle = state.able_to_short_castle;
+        self.number_of_moves_without_captures_or_pawn_moves =
+            state.number_of_moves_without_captures_or_pawn_moves;
+        self.fullmove_number = state.fullmove_number;
+        self.en_passant_square = state.en_passant_square;
+        self.hash = state.hash;
+
+        self.update_attacks();
+
+        Some(mv)
+    }
+
+    /// Plays the last move taken back with [`Game::undo`] again and returns the
+    /// status of the game afterwards.
+    pub fn redo(&mut self) -> Option<GameStatus> {
+        // replaying the move must not forget the other moves taken back
+        let mut redo_stack = core::mem::take(&mut self.redo_stack);
+        let redo_move = redo_stack.pop()?;
+
+        let input = match redo_move.promotion {
+            Some(piece_type) => {
+                UserInput::MoveWithPromotion(redo_move.from, redo_move.to, piece_type)
+            }
+            None => UserInput::Move(redo_move.from, redo_move.to),
+        };
+        let status = self.process_input(&input).ok();
+
+        self.redo_stack = redo_stack;
+        status
+    }
+
+    /// Whether there is a move that can be taken back.
+    #[must_use]
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    /// Whether there is a move taken back that can be played again.
+    #[must_use]
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+}
@@ -0,0 +1,73 @@
+use super::{Color, Game, MoveList, PieceType, COLOR_COUNT, PROMOTION_PIECE_TYPES};
+use alloc::vec;
+
+/// Rule set the game is played with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Variant {
+    #[default]
+    Standard,
+    /// Also known as giveaway: captures are compulsory, the king is a normal
+    /// piece without check and castling, and the side that loses all its pieces
+    /// or is stalemated wins.
+    Antichess,
+}
+
+/// In antichess pawns may also promote to a king.
+const ANTICHESS_PROMOTION_PIECE_TYPES: [PieceType; 5] = [
+    PieceType::Queen,
+    PieceType::Rook,
+    PieceType::Bishop,
+    PieceType::Knight,
+    PieceType::King,
+];
+
+impl Game {
+    /// New game from the starting position with the rules of `variant`.
+    #[must_use]
+    pub fn with_variant(variant: Variant) -> Game {
+        let mut game = Game::new();
+        game.variant = variant;
+        if variant == Variant::Antichess {
+            game.able_to_long_castle = [false; COLOR_COUNT];
+            game.able_to_short_castle = [false; COLOR_COUNT];
+            game.hash = game.full_hash();
+        }
+        game.update_attacks();
+        game.position_keys = vec![game.zobrist_key()];
+        game
+    }
+
+    #[must_use]
+    pub const fn variant(&self) -> Variant {
+        self.variant
+    }
+
+    /// Pieces a pawn can promote to with the rules of the game.
+    #[must_use]
+    pub fn promotion_piece_types(&self) -> &'static [PieceType] {
+        match self.variant {
+            Variant::Standard => &PROMOTION_PIECE_TYPES,
+            Variant::Antichess => &ANTICHESS_PROMOTION_PIECE_TYPES,
+        }
+    }
+
+    /// Only the captures of `moves` if the side can capture anything.
+    pub(super) fn antichess_legalize(&self, moves: &mut MoveList) {
+        if moves
+            .first()
+            .is_some_and(|mv| self.antichess_capture_available(mv.piece.color))
+        {
+            moves.retain(|mv| mv.captured_piece.is_some());
+        }
+    }
+
+    pub(super) fn antichess_capture_available(&self, color: Color) -> bool {
+        let mut moves = MoveList::new();
+        self.pieces_of(color).any(|(pos, _)| {
+            moves.clear();
+            self.pseudo_legal_moves_to(pos, &mut moves);
+            moves.iter().any(|mv| mv.captured_piece.is_some())
+        })
+    }
+}
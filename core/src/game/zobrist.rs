@@ -0,0 +1,136 @@
+use super::{Color, Game, Piece, PieceType, Position, BOARD_SIZE, COLOR_COUNT, TOTAL_SQUARES};
+
+const PIECE_TYPE_COUNT: usize = 6;
+
+/// Random numbers xored together for the hash of a position.
+struct ZobristKeys {
+    pieces: [[[u64; TOTAL_SQUARES]; PIECE_TYPE_COUNT]; COLOR_COUNT],
+    black_to_move: u64,
+    long_castle: [u64; COLOR_COUNT],
+    short_castle: [u64; COLOR_COUNT],
+    en_passant_file: [u64; BOARD_SIZE],
+}
+
+/// Pseudo random number generator, only used to fill the keys at compile time.
+const fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+const fn generate_keys() -> ZobristKeys {
+    let mut state = 0x5275_7374_7943_6873;
+    let mut keys = ZobristKeys {
+        pieces: [[[0; TOTAL_SQUARES]; PIECE_TYPE_COUNT]; COLOR_COUNT],
+        black_to_move: 0,
+        long_castle: [0; COLOR_COUNT],
+        short_castle: [0; COLOR_COUNT],
+        en_passant_file: [0; BOARD_SIZE],
+    };
+    let mut color = 0;
+    while color < COLOR_COUNT {
+        let mut piece_type = 0;
+        while piece_type < PIECE_TYPE_COUNT {
+            let mut square = 0;
+            while square < TOTAL_SQUARES {
+                keys.pieces[color][piece_type][square] = splitmix64(&mut state);
+                square += 1;
+            }
+            piece_type += 1;
+        }
+        keys.long_castle[color] = splitmix64(&mut state);
+        keys.short_castle[color] = splitmix64(&mut state);
+        color += 1;
+    }
+    keys.black_to_move = splitmix64(&mut state);
+    let mut file = 0;
+    while file < BOARD_SIZE {
+        keys.en_passant_file[file] = splitmix64(&mut state);
+        file += 1;
+    }
+    keys
+}
+
+static KEYS: ZobristKeys = generate_keys();
+
+impl Game {
+    /// Zobrist hash of the position: pieces, side to move, castling rights and
+    /// the en passant file if the side to move has a pawn next to the pawn that
+    /// just moved two squares. Positions that are the same by the repetition rules
+    /// have the same hash. It is updated with every move, so this costs nothing.
+    #[must_use]
+    pub fn zobrist_key(&self) -> u64 {
+        debug_assert_eq!(self.hash, self.full_hash());
+        let mut key = self.hash;
+        if let Some(pos) = self.en_passant_square {
+            if self.en_passant_capture_possible(pos) {
+                key ^= KEYS.en_passant_file[pos.0 as usize - 'a' as usize];
+            }
+        }
+        key
+    }
+
+    /// Same as [`Game::zobrist_key`], e.g. for transposition tables.
+    #[must_use]
+    pub fn hash(&self) -> u64 {
+        self.zobrist_key()
+    }
+
+    /// Hash of the pieces, the side to move and the castling rights computed
+    /// from scratch.
+    pub(super) fn full_hash(&self) -> u64 {
+        let mut key = self.castling_key();
+        for (square, piece) in self.board.iter().enumerate() {
+            if let Some(piece) = piece {
+                key ^= KEYS.pieces[piece.color as usize][piece.piece_type as usize][square];
+            }
+        }
+        if self.turn == Color::Black {
+            key ^= KEYS.black_to_move;
+        }
+        key
+    }
+
+    pub(super) fn castling_key(&self) -> u64 {
+        let mut key = 0;
+        for color in [Color::White, Color::Black] {
+            if self.able_to_long_castle[color as usize] {
+                key ^= KEYS.long_castle[color as usize];
+            }
+            if self.able_to_short_castle[color as usize] {
+                key ^= KEYS.short_castle[color as usize];
+            }
+        }
+        key
+    }
+
+    /// Puts `piece` on `pos` and updates the hash.
+    pub(super) fn set_square(&mut self, pos: Position, piece: Option<Piece>) {
+        let index = pos.as_index();
+        for piece in [self.board[index], piece].into_iter().flatten() {
+            self.hash ^= KEYS.pieces[piece.color as usize][piece.piece_type as usize][index];
+        }
+        self.board[index] = piece;
+    }
+
+    pub(super) fn switch_turn(&mut self) {
+        self.turn = self.turn.invert();
+        self.hash ^= KEYS.black_to_move;
+    }
+
+    /// A pawn of the side to move stands next to the pawn that skipped `en_passant_square`.
+    pub(super) fn en_passant_capture_possible(&self, en_passant_square: Position) -> bool {
+        let direction = if self.turn == Color::White { 1 } else { -1 };
+        [(-1, -direction), (1, -direction)].iter().any(|offset| {
+            en_passant_square
+                .add(*offset)
+                .try_as_index()
+                .and_then(|index| self.board[index])
+                .is_some_and(|piece| {
+                    piece.piece_type == PieceType::Pawn && piece.color == self.turn
+                })
+        })
+    }
+}
@@ -1 +1,17 @@
+#![no_std]
+
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(all(feature = "rayon", target_arch = "wasm32"))]
+compile_error!("the `rayon` feature needs threads, which are not available on wasm");
+
+pub mod analysis;
+pub mod bots;
+pub mod engine;
+pub mod eval;
 pub mod game;
+pub mod player;
+#[cfg(feature = "std")]
+pub mod tournament;
@@ -0,0 +1,120 @@
+use crate::bots;
+use crate::engine::{Engine, SearchLimits};
+use crate::game::{BotRng, Game, Move, Rng, UserInput};
+
+/// One side of a game, a person or a bot. Frontends ask the player of the side
+/// to move for its input and pass it to [`Game::process_input`].
+///
+/// ```
+/// use rusty_chess_core::game::{BotRng, Color, Game, SeedableRng};
+/// use rusty_chess_core::player::{AttackingKing, Player, RandomAggressive};
+///
+/// let mut game = Game::new();
+/// let mut white = AttackingKing::new(BotRng::seed_from_u64(1));
+/// let mut black = RandomAggressive::new(BotRng::seed_from_u64(2));
+/// while !game.status().is_finished() && game.history().len() < 40 {
+///     let player: &mut dyn Player = match game.turn {
+///         Color::White => &mut white,
+///         Color::Black => &mut black,
+///     };
+///     game.process_input(&player.choose_move(&game)).unwrap();
+/// }
+/// ```
+pub trait Player {
+    /// Input for the side to move of `game`. Only asked while the game goes
+    /// on, bots resign if there is no legal move.
+    fn choose_move(&mut self, game: &Game) -> UserInput;
+}
+
+/// A person, who is asked for the input with a callback, e.g. one reading the
+/// command line.
+#[derive(Debug, Clone)]
+pub struct Human<F> {
+    ask: F,
+}
+
+impl<F: FnMut(&Game) -> UserInput> Human<F> {
+    #[must_use]
+    pub const fn new(ask: F) -> Self {
+        Human { ask }
+    }
+}
+
+impl<F: FnMut(&Game) -> UserInput> Player for Human<F> {
+    fn choose_move(&mut self, game: &Game) -> UserInput {
+        (self.ask)(game)
+    }
+}
+
+/// Captures whenever it can, otherwise plays a random move, see
+/// [`bots::randomly_aggressive_move`].
+#[derive(Debug, Clone)]
+pub struct RandomAggressive<R = BotRng> {
+    rng: R,
+}
+
+impl<R: Rng> RandomAggressive<R> {
+    #[must_use]
+    pub const fn new(rng: R) -> Self {
+        RandomAggressive { rng }
+    }
+}
+
+impl<R: Rng> Player for RandomAggressive<R> {
+    fn choose_move(&mut self, game: &Game) -> UserInput {
+        bot_input(bots::randomly_aggressive_move(game, &mut self.rng))
+    }
+}
+
+/// Mates if it can, otherwise gives check, captures or plays a random move, see
+/// [`bots::attacking_king_move`].
+#[derive(Debug, Clone)]
+pub struct AttackingKing<R = BotRng> {
+    rng: R,
+}
+
+impl<R: Rng> AttackingKing<R> {
+    #[must_use]
+    pub const fn new(rng: R) -> Self {
+        AttackingKing { rng }
+    }
+}
+
+impl<R: Rng> Player for AttackingKing<R> {
+    fn choose_move(&mut self, game: &Game) -> UserInput {
+        bot_input(bots::attacking_king_move(game, &mut self.rng))
+    }
+}
+
+/// The [`Engine`] searching every move within the same limits.
+#[derive(Debug, Clone)]
+pub struct EnginePlayer {
+    engine: Engine,
+    limits: SearchLimits,
+}
+
+impl EnginePlayer {
+    #[must_use]
+    pub const fn new(engine: Engine, limits: SearchLimits) -> Self {
+        EnginePlayer { engine, limits }
+    }
+}
+
+impl Player for EnginePlayer {
+    fn choose_move(&mut self, game: &Game) -> UserInput {
+        match self.engine.search(game, &self.limits).best_move {
+            Some(Move {
+                from,
+                to,
+                promotion: Some(piece_type),
+                ..
+            }) => UserInput::MoveWithPromotion(from, to, piece_type),
+            mv => bot_input(mv),
+        }
+    }
+}
+
+/// Bots resign without legal move.
+fn bot_input(mv: Option<Move>) -> UserInput {
+    mv.map_or(UserInput::Resign, bots::bot_input)
+}
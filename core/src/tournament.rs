@@ -0,0 +1,348 @@
+use crate::engine::MatchScore;
+use crate::game::{Clock, Color, Game, GameResult, PgnTags, UserInput};
+use crate::player::Player;
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::time::Duration;
+
+mod crosstable;
+mod sprt;
+
+pub use crosstable::Crosstable;
+pub use sprt::{Sprt, SprtDecision, SprtReport};
+
+/// Time of each side for a game, `base` at the start and `increment` added
+/// after every move. The players are not told their time, a player that uses
+/// more than it has loses the game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeControl {
+    pub base: Duration,
+    pub increment: Duration,
+}
+
+impl TimeControl {
+    #[must_use]
+    pub const fn new(base: Duration, increment: Duration) -> Self {
+        TimeControl { base, increment }
+    }
+}
+
+/// A round-robin between players, people or bots, e.g. to compare bots and
+/// engine settings. In every round each player plays every other player once
+/// with White and once with Black from every opening.
+///
+/// ```
+/// use rusty_chess_core::game::{BotRng, SeedableRng};
+/// use rusty_chess_core::player::{AttackingKing, RandomAggressive};
+/// use rusty_chess_core::tournament::Tournament;
+///
+/// let result = Tournament::new()
+///     .player("Attacking king", AttackingKing::new(BotRng::seed_from_u64(1)))
+///     .player("Random", RandomAggressive::new(BotRng::seed_from_u64(2)))
+///     .max_plies(40)
+///     .run(|_| {});
+/// assert_eq!(result.games.len(), 2);
+/// println!("{}", result.crosstable());
+/// let archive = result.to_pgn();
+/// ```
+pub struct Tournament<'a> {
+    names: Vec<String>,
+    players: Vec<Box<dyn Player + 'a>>,
+    rounds: u32,
+    openings: Vec<Game>,
+    time_control: Option<TimeControl>,
+    max_plies: usize,
+    event: String,
+}
+
+/// A finished game of a [`Tournament`], the players by their index in
+/// [`TournamentResult::names`].
+#[derive(Debug, Clone)]
+pub struct TournamentGame {
+    /// Starts at 1.
+    pub round: u32,
+    pub white: usize,
+    pub black: usize,
+    pub result: GameResult,
+    pub game: Game,
+}
+
+/// The games of a [`Tournament`] in the order they were played.
+#[derive(Debug, Clone)]
+pub struct TournamentResult {
+    pub event: String,
+    /// Names of the players in the order they were added.
+    pub names: Vec<String>,
+    pub games: Vec<TournamentGame>,
+}
+
+impl Default for Tournament<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> Tournament<'a> {
+    /// One round from the starting position without time control, games are
+    /// drawn after 400 plies.
+    #[must_use]
+    pub fn new() -> Self {
+        Tournament {
+            names: Vec::new(),
+            players: Vec::new(),
+            rounds: 1,
+            openings: alloc::vec![Game::new()],
+            time_control: None,
+            max_plies: 400,
+            event: String::from("Tournament"),
+        }
+    }
+
+    #[must_use]
+    pub fn player(mut self, name: impl Into<String>, player: impl Player + 'a) -> Self {
+        self.names.push(name.into());
+        self.players.push(Box::new(player));
+        self
+    }
+
+    #[must_use]
+    pub const fn rounds(mut self, rounds: u32) -> Self {
+        self.rounds = rounds;
+        self
+    }
+
+    /// Positions the games start from, e.g. read from PGN. Their moves are
+    /// part of the archived games. Without any the games start from the
+    /// starting position.
+    #[must_use]
+    pub fn openings(mut self, openings: Vec<Game>) -> Self {
+        self.openings = if openings.is_empty() {
+            alloc::vec![Game::new()]
+        } else {
+            openings
+        };
+        self
+    }
+
+    #[must_use]
+    pub const fn time_control(mut self, time_control: TimeControl) -> Self {
+        self.time_control = Some(time_control);
+        self
+    }
+
+    /// Games still going on after this many plies are drawn by agreement.
+    #[must_use]
+    pub const fn max_plies(mut self, max_plies: usize) -> Self {
+        self.max_plies = max_plies;
+        self
+    }
+
+    /// Name of the event in the PGN archive.
+    #[must_use]
+    pub fn event(mut self, event: impl Into<String>) -> Self {
+        self.event = event.into();
+        self
+    }
+
+    /// Plays all games, `on_game` gets every game when it is finished.
+    pub fn run(self, mut on_game: impl FnMut(&TournamentGame)) -> TournamentResult {
+        self.run_while(|played| {
+            on_game(played);
+            true
+        })
+    }
+
+    /// Like [`Tournament::run`] but stops once `on_game` returns `false`.
+    pub fn run_while(
+        mut self,
+        mut on_game: impl FnMut(&TournamentGame) -> bool,
+    ) -> TournamentResult {
+        let mut games = Vec::new();
+        let count = self.players.len();
+        'rounds: for round in 1..=self.rounds {
+            for first in 0..count {
+                for second in first + 1..count {
+                    for opening in &self.openings {
+                        for (white, black) in [(first, second), (second, first)] {
+                            let (white_player, black_player) =
+                                two_players(&mut self.players, white, black);
+                            let game = play_game(
+                                opening.clone(),
+                                white_player,
+                                black_player,
+                                self.time_control,
+                                self.max_plies,
+                            );
+                            let played = TournamentGame {
+                                round,
+                                white,
+                                black,
+                                result: game.result().unwrap_or(GameResult::Draw),
+                                game,
+                            };
+                            let go_on = on_game(&played);
+                            games.push(played);
+                            if !go_on {
+                                break 'rounds;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        TournamentResult {
+            event: self.event,
+            names: self.names,
+            games,
+        }
+    }
+}
+
+impl Tournament<'_> {
+    /// Plays the first player against the second until `sprt` decides whether
+    /// the first is stronger, at most for all rounds. `on_game` gets the score
+    /// of the first player and the log-likelihood ratio after every game.
+    ///
+    /// ```
+    /// use rusty_chess_core::engine::{Engine, SearchLimits, SkillLevel};
+    /// use rusty_chess_core::player::EnginePlayer;
+    /// use rusty_chess_core::tournament::{Sprt, Tournament};
+    ///
+    /// let limits = SearchLimits::depth(1);
+    /// let weak = Engine::new().skill_level(SkillLevel::MIN);
+    /// let report = Tournament::new()
+    ///     .player("New", EnginePlayer::new(Engine::new(), limits))
+    ///     .player("Old", EnginePlayer::new(weak, limits))
+    ///     .max_plies(16)
+    ///     .run_sprt(&Sprt::new(0.0, 20.0), |score, llr| println!("{score} LLR {llr:.2}"));
+    /// assert_eq!(report.score.games(), 2);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the tournament does not have two players.
+    pub fn run_sprt(self, sprt: &Sprt, mut on_game: impl FnMut(&MatchScore, f64)) -> SprtReport {
+        assert_eq!(self.players.len(), 2, "A SPRT needs two players.");
+        let mut score = MatchScore::default();
+        let result = self.run_while(|played| {
+            let first_color = if played.white == 0 {
+                Color::White
+            } else {
+                Color::Black
+            };
+            score.record(played.result, first_color);
+            on_game(&score, sprt.llr(&score));
+            sprt.decision(&score).is_none()
+        });
+        SprtReport {
+            score,
+            llr: sprt.llr(&score),
+            decision: sprt.decision(&score),
+            result,
+        }
+    }
+}
+
+impl TournamentResult {
+    /// Points of every player against every other one.
+    #[must_use]
+    pub fn crosstable(&self) -> Crosstable {
+        Crosstable::new(self)
+    }
+
+    /// All games as one PGN file, with the names of the players and the round.
+    #[must_use]
+    pub fn to_pgn(&self) -> String {
+        let pgns: Vec<String> = self
+            .games
+            .iter()
+            .map(|played| {
+                played.game.to_pgn_with_tags(&PgnTags {
+                    event: self.event.clone(),
+                    round: played.round.to_string(),
+                    white: self.names[played.white].clone(),
+                    black: self.names[played.black].clone(),
+                    ..PgnTags::default()
+                })
+            })
+            .collect();
+        pgns.join("\n")
+    }
+}
+
+/// Mutable references to two different players.
+fn two_players<'p, 'a>(
+    players: &'p mut [Box<dyn Player + 'a>],
+    white: usize,
+    black: usize,
+) -> (&'p mut dyn Player, &'p mut dyn Player) {
+    if white < black {
+        let (left, right) = players.split_at_mut(black);
+        (left[white].as_mut(), right[0].as_mut())
+    } else {
+        let (left, right) = players.split_at_mut(white);
+        (right[0].as_mut(), left[black].as_mut())
+    }
+}
+
+/// Plays the game to its end. Claimable draws are claimed, games longer than
+/// `max_plies` are drawn by agreement and other draw offers are declined. A
+/// player whose input is not valid resigns.
+fn play_game(
+    mut game: Game,
+    white: &mut dyn Player,
+    black: &mut dyn Player,
+    time_control: Option<TimeControl>,
+    max_plies: usize,
+) -> Game {
+    if let Some(control) = time_control {
+        game.set_clock(Clock::with_increment(control.base, control.increment));
+    }
+    while !game.status().is_finished() {
+        if game.claimable_draw().is_some() {
+            process_valid(&mut game, &UserInput::ClaimDraw);
+            break;
+        }
+        let long_game = game.history().len() >= max_plies;
+        if game.draw_offer() == Some(game.turn.invert()) {
+            if long_game {
+                process_valid(&mut game, &UserInput::AcceptDraw);
+                break;
+            }
+            process_valid(&mut game, &UserInput::DeclineDraw);
+        } else if long_game && game.draw_offer().is_none() {
+            // the other side accepts on its turn
+            process_valid(&mut game, &UserInput::Draw);
+        }
+        // answers and offers take time of the clock as well
+        if game.status().is_finished() {
+            break;
+        }
+        let player: &mut dyn Player = match game.turn {
+            Color::White => &mut *white,
+            Color::Black => &mut *black,
+        };
+        // the clock of the game takes the time of the player
+        let mut input = player.choose_move(&game);
+        if matches!(input, UserInput::Draw) {
+            // the offer comes with a move
+            process_valid(&mut game, &UserInput::Draw);
+            if game.status().is_finished() {
+                break;
+            }
+            input = player.choose_move(&game);
+        }
+        if matches!(input, UserInput::Draw) || game.process_input(&input).is_err() {
+            process_valid(&mut game, &UserInput::Resign);
+        }
+    }
+    game
+}
+
+/// Processes input that is valid in every game that goes on.
+fn process_valid(game: &mut Game, input: &UserInput) {
+    if let Err(e) = game.process_input(input) {
+        unreachable!("{input:?} is not valid: {e}");
+    }
+}
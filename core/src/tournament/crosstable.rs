@@ -0,0 +1,82 @@
+use super::TournamentResult;
+use crate::game::GameResult;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Formatter};
+
+/// Half points of every player against every other one, see
+/// [`TournamentResult::crosstable`]. Shown as table with the best player
+/// first:
+///
+/// ```text
+/// #  Player             1     2     3  Points
+/// 1  Engine             *   2.0   2.0     4.0
+/// 2  Attacking king   0.0     *   1.5     1.5
+/// 3  Random           0.0   0.5     *     0.5
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Crosstable {
+    pub names: Vec<String>,
+    /// `half_points[player][opponent]`, by the index of the players in
+    /// [`Crosstable::names`].
+    pub half_points: Vec<Vec<u32>>,
+}
+
+impl Crosstable {
+    pub(super) fn new(result: &TournamentResult) -> Self {
+        let count = result.names.len();
+        let mut half_points = alloc::vec![alloc::vec![0; count]; count];
+        for played in &result.games {
+            let (white, black) = match played.result {
+                GameResult::WhiteWins => (2, 0),
+                GameResult::BlackWins => (0, 2),
+                GameResult::Draw => (1, 1),
+            };
+            half_points[played.white][played.black] += white;
+            half_points[played.black][played.white] += black;
+        }
+        Crosstable {
+            names: result.names.clone(),
+            half_points,
+        }
+    }
+
+    /// Points of the player over all games, a draw is half a point.
+    #[must_use]
+    pub fn points(&self, player: usize) -> f64 {
+        f64::from(self.half_points[player].iter().sum::<u32>()) / 2.0
+    }
+
+    /// Indices of the players, the one with the most points first.
+    #[must_use]
+    pub fn ranking(&self) -> Vec<usize> {
+        let mut ranking: Vec<usize> = (0..self.names.len()).collect();
+        ranking.sort_by(|a, b| self.points(*b).total_cmp(&self.points(*a)));
+        ranking
+    }
+}
+
+impl fmt::Display for Crosstable {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let ranking = self.ranking();
+        let name_width = self.names.iter().map(String::len).max().unwrap_or(0);
+        write!(f, "#  {:name_width$}", "Player")?;
+        for rank in 1..=ranking.len() {
+            write!(f, "  {rank:>4}")?;
+        }
+        writeln!(f, "  Points")?;
+        for (rank, &player) in ranking.iter().enumerate() {
+            write!(f, "{:<2} {:name_width$}", rank + 1, self.names[player])?;
+            for &opponent in &ranking {
+                if opponent == player {
+                    write!(f, "  {:>4}", "*")?;
+                } else {
+                    let points = f64::from(self.half_points[player][opponent]) / 2.0;
+                    write!(f, "  {points:>4.1}")?;
+                }
+            }
+            writeln!(f, "  {:>6.1}", self.points(player))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,110 @@
+use super::TournamentResult;
+use crate::engine::MatchScore;
+
+/// Sequential probability ratio test of whether the first of two players is at
+/// least `elo1` stronger than the second (the change passes) or at most
+/// `elo0` (it fails). The games go on until the log-likelihood ratio of the
+/// results leaves the [`Sprt::bounds`], so clear differences are decided after
+/// few games. `alpha` and `beta` are the chances to pass a change that is
+/// not better and to fail one that is.
+///
+/// ```
+/// use rusty_chess_core::engine::MatchScore;
+/// use rusty_chess_core::tournament::{Sprt, SprtDecision};
+///
+/// let sprt = Sprt::new(0.0, 10.0);
+/// let score = MatchScore { wins: 700, draws: 600, losses: 500 };
+/// assert!(sprt.llr(&score) > sprt.bounds().1);
+/// assert_eq!(sprt.decision(&score), Some(SprtDecision::Pass));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sprt {
+    pub elo0: f64,
+    pub elo1: f64,
+    pub alpha: f64,
+    pub beta: f64,
+}
+
+/// Outcome of a [`Sprt`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SprtDecision {
+    /// The first player is at least `elo1` stronger.
+    Pass,
+    /// The first player is at most `elo0` stronger.
+    Fail,
+}
+
+/// Outcome of [`Tournament::run_sprt`](super::Tournament::run_sprt).
+#[derive(Debug, Clone)]
+pub struct SprtReport {
+    /// Results of the first player.
+    pub score: MatchScore,
+    pub llr: f64,
+    /// `None` if the rounds ended before the test decided.
+    pub decision: Option<SprtDecision>,
+    pub result: TournamentResult,
+}
+
+impl Sprt {
+    /// A test with error chances of 5%.
+    #[must_use]
+    pub const fn new(elo0: f64, elo1: f64) -> Self {
+        Sprt {
+            elo0,
+            elo1,
+            alpha: 0.05,
+            beta: 0.05,
+        }
+    }
+
+    /// Log-likelihood ratio below which the change fails and above which it
+    /// passes.
+    #[must_use]
+    pub fn bounds(&self) -> (f64, f64) {
+        (
+            (self.beta / (1.0 - self.alpha)).ln(),
+            ((1.0 - self.beta) / self.alpha).ln(),
+        )
+    }
+
+    /// Log-likelihood ratio of `score` for `elo1` against `elo0`, with the
+    /// normal approximation of the results of a game. The variance counts half
+    /// a win, draw and loss more, so that a player winning every game passes
+    /// as well.
+    #[must_use]
+    pub fn llr(&self, score: &MatchScore) -> f64 {
+        let games = f64::from(score.games());
+        if games == 0.0 {
+            return 0.0;
+        }
+        let (wins, draws, losses) = (
+            f64::from(score.wins) + 0.5,
+            f64::from(score.draws) + 0.5,
+            f64::from(score.losses) + 0.5,
+        );
+        let total = wins + draws + losses;
+        let mean = (wins + draws / 2.0) / total;
+        let variance = (wins + draws / 4.0) / total - mean * mean;
+        let (score0, score1) = (expected_score(self.elo0), expected_score(self.elo1));
+        games * (score1 - score0) * (2.0 * score.score() - score0 - score1) / (2.0 * variance)
+    }
+
+    /// The decision once the log-likelihood ratio leaves the bounds.
+    #[must_use]
+    pub fn decision(&self, score: &MatchScore) -> Option<SprtDecision> {
+        let llr = self.llr(score);
+        let (lower, upper) = self.bounds();
+        if llr >= upper {
+            Some(SprtDecision::Pass)
+        } else if llr <= lower {
+            Some(SprtDecision::Fail)
+        } else {
+            None
+        }
+    }
+}
+
+/// Points per game of a player `elo` stronger than the opponent.
+fn expected_score(elo: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf(-elo / 400.0))
+}
@@ -0,0 +1,114 @@
+use rusty_chess_core::analysis::{
+    find_puzzles, review, tactical_motifs, Motif, MoveClass, PuzzleOptions,
+};
+use rusty_chess_core::engine::SearchLimits;
+use rusty_chess_core::game::{Color, Game, Piece, PieceType, Position, UserInput};
+
+fn piece(piece_type: PieceType, color: Color, square: &str) -> (Piece, Position) {
+    let mut chars = square.chars();
+    let pos = Position::new(chars.next().unwrap(), chars.next().unwrap()).unwrap();
+    (Piece::new(piece_type, color), pos)
+}
+
+fn motifs(fen: &str) -> Vec<Motif> {
+    tactical_motifs(&Game::from_fen(fen).unwrap())
+}
+
+#[test]
+fn knight_fork() {
+    let motifs = motifs("8/2k1q3/8/3N4/8/8/8/4K3 b - - 0 1");
+    assert!(motifs.contains(&Motif::Fork {
+        attacker: piece(PieceType::Knight, Color::White, "d5"),
+        targets: vec![
+            piece(PieceType::King, Color::Black, "c7"),
+            piece(PieceType::Queen, Color::Black, "e7"),
+        ],
+    }));
+}
+
+#[test]
+fn pins_and_skewers() {
+    // the knight can not leave the king, the king has to leave the queen
+    let motifs = motifs("4k3/4n3/8/8/4R3/8/8/q2K3B w - - 0 1");
+    assert!(motifs.contains(&Motif::Pin {
+        attacker: piece(PieceType::Rook, Color::White, "e4"),
+        pinned: piece(PieceType::Knight, Color::Black, "e7"),
+        behind: piece(PieceType::King, Color::Black, "e8"),
+    }));
+    assert!(motifs.contains(&Motif::Skewer {
+        attacker: piece(PieceType::Queen, Color::Black, "a1"),
+        front: piece(PieceType::King, Color::White, "d1"),
+        behind: piece(PieceType::Bishop, Color::White, "h1"),
+    }));
+}
+
+#[test]
+fn discovered_attack() {
+    let motifs = motifs("3qk3/8/8/8/8/8/3N4/3RK3 w - - 0 1");
+    assert!(motifs.contains(&Motif::DiscoveredAttack {
+        piece: piece(PieceType::Knight, Color::White, "d2"),
+        slider: piece(PieceType::Rook, Color::White, "d1"),
+        target: piece(PieceType::Queen, Color::Black, "d8"),
+    }));
+}
+
+#[test]
+fn hanging_pieces() {
+    // the knight on c6 is protected, but attacked by a pawn, the one on h5 is
+    // not protected at all
+    let motifs = motifs("4k3/3p4/2n5/1P5n/8/8/8/4K2R w - - 0 1");
+    let hanging: Vec<Position> = motifs
+        .iter()
+        .filter_map(|motif| match motif {
+            Motif::Hanging { piece, .. } => Some(piece.1),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(hanging, [Position('h', '5'), Position('c', '6')]);
+    assert!(tactical_motifs(&Game::new()).is_empty());
+}
+
+#[test]
+fn review_finds_the_blunder() {
+    // the queen takes a protected pawn
+    let game = Game::from_pgn("1. e4 e5 2. Qh5 Nc6 3. Qxe5+ Nxe5").unwrap();
+    let report = review(&game, &SearchLimits::depth(3));
+    assert_eq!(report.moves.len(), 6);
+    assert_eq!(report.moves[4].san, "Qxe5+");
+    assert_eq!(report.moves[4].class, MoveClass::Blunder);
+    assert!(report.moves[5].class <= MoveClass::Good);
+    assert!(report.count(Color::White, MoveClass::Blunder) >= 1);
+    assert!(report.average_loss(Color::White) > report.average_loss(Color::Black));
+
+    assert!(review(&Game::new(), &SearchLimits::depth(1))
+        .moves
+        .is_empty());
+}
+
+#[test]
+fn puzzles_from_a_game() {
+    // Black walks into a knight fork with 1... Qa8
+    let mut game = Game::from_fen("4k3/q7/8/1N6/8/8/8/3RK3 b - - 0 1").unwrap();
+    game.process_input(&UserInput::Move(Position('a', '7'), Position('a', '8')))
+        .unwrap();
+    let options = PuzzleOptions::new(SearchLimits::depth(3));
+    let puzzles = find_puzzles(&game, &options);
+    assert_eq!(puzzles.len(), 1);
+    let puzzle = &puzzles[0];
+    assert_eq!(puzzle.san, "Nc7+");
+    assert_eq!(puzzle.game.turn, Color::White);
+    assert!(puzzle.score >= options.min_score);
+    assert!(puzzle.second_score <= options.max_second_score);
+    assert_eq!(puzzle.mate_in, None);
+    assert_eq!(
+        puzzle.to_epd(),
+        format!(
+            "q3k3/8/8/1N6/8/8/8/3RK3 w - - bm Nc7+; ce {};",
+            puzzle.score
+        )
+    );
+
+    // nothing to win in the opening
+    let game = Game::from_pgn("1. e4 e5 2. Nf3 Nc6").unwrap();
+    assert!(find_puzzles(&game, &PuzzleOptions::new(SearchLimits::depth(2))).is_empty());
+}
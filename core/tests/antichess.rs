@@ -0,0 +1,72 @@
+use rusty_chess_core::game::{
+    Color, Game, GameResult, GameStatus, MoveError, PieceType, Position, UserInput, Variant,
+};
+
+fn play(game: &mut Game, moves: &[&str]) -> GameStatus {
+    let mut status = game.status();
+    for mv in moves {
+        let mut squares = mv.chars();
+        let mut square = || Position(squares.next().unwrap(), squares.next().unwrap());
+        let (from, to) = (square(), square());
+        status = game
+            .process_input(&UserInput::Move(from, to))
+            .unwrap_or_else(|e| panic!("{mv} is not valid: {e}"));
+    }
+    status
+}
+
+#[test]
+fn perft_starting_position() {
+    let game = Game::with_variant(Variant::Antichess);
+    assert_eq!(game.perft(1), 20);
+    assert_eq!(game.perft(2), 400);
+    assert_eq!(game.perft(3), 8_067);
+}
+
+#[test]
+fn capture_is_compulsory() {
+    let mut game = Game::with_variant(Variant::Antichess);
+    play(&mut game, &["e2e3", "b7b5"]);
+    assert_eq!(
+        game.process_input(&UserInput::Move(Position('d', '2'), Position('d', '3'))),
+        Err(MoveError::CaptureRequired)
+    );
+    play(&mut game, &["f1b5"]);
+}
+
+#[test]
+fn king_can_be_captured_and_walk_into_check() {
+    let mut game = Game::from_fen_with_variant("8/8/8/8/8/2k5/8/K7 w - - 0 1", Variant::Antichess)
+        .unwrap();
+    play(&mut game, &["a1b2", "c3b2"]);
+    assert_eq!(game.status(), GameStatus::VariantWin(Color::White));
+    assert_eq!(game.result(), Some(GameResult::WhiteWins));
+}
+
+#[test]
+fn stalemated_side_wins() {
+    let game = Game::from_fen_with_variant("8/8/8/8/8/p7/P7/8 w - - 0 1", Variant::Antichess)
+        .unwrap();
+    assert_eq!(game.status(), GameStatus::VariantWin(Color::White));
+}
+
+#[test]
+fn promotion_to_king() {
+    let promote = UserInput::MoveWithPromotion(Position('a', '7'), Position('a', '8'), PieceType::King);
+    let mut game =
+        Game::from_fen_with_variant("8/P7/8/8/8/8/8/7k w - - 0 1", Variant::Antichess).unwrap();
+    game.process_input(&promote).unwrap();
+    assert!(game.to_fen().starts_with("K7/"));
+
+    let mut game = Game::from_fen("8/P7/8/8/8/8/8/k6K w - - 0 1").unwrap();
+    assert_eq!(game.process_input(&promote), Err(MoveError::InvalidPromotion));
+}
+
+#[test]
+fn no_castling_rights() {
+    assert!(Game::from_fen_with_variant(
+        "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1",
+        Variant::Antichess
+    )
+    .is_err());
+}
@@ -0,0 +1,72 @@
+use rusty_chess_core::game::{Color, Game, Piece, PieceType, Position};
+
+#[test]
+fn attackers_of_square() {
+    // the knight on c6 is pinned to the rook on e8, but still attacks e5
+    let game = Game::from_fen("4r1k1/8/2n5/1B6/4P3/3P1N2/8/4K3 w - - 0 1").unwrap();
+    let mut attackers = game.attackers_of(Position('e', '4'), Color::White);
+    attackers.sort_by_key(|(_, pos)| pos.as_index());
+    assert_eq!(
+        attackers,
+        [(Piece::new(PieceType::Pawn, Color::White), Position('d', '3'))]
+    );
+    let mut attackers = game.attackers_of(Position('e', '5'), Color::Black);
+    attackers.sort_by_key(|(_, pos)| pos.as_index());
+    assert_eq!(
+        attackers,
+        [
+            (Piece::new(PieceType::Knight, Color::Black), Position('c', '6')),
+            (Piece::new(PieceType::Rook, Color::Black), Position('e', '8')),
+        ]
+    );
+    assert_eq!(
+        game.attackers_of(Position('e', '5'), Color::White),
+        [(Piece::new(PieceType::Knight, Color::White), Position('f', '3'))]
+    );
+}
+
+#[test]
+fn pinned_pieces() {
+    let game = Game::from_fen("4k3/8/4r3/8/1b6/2N1B3/8/4K3 w - - 0 1").unwrap();
+    let mut pins = game.pinned_pieces(Color::White);
+    pins.sort_by_key(|pin| pin.pos.as_index());
+    assert_eq!(pins.len(), 2);
+    assert_eq!(pins[0].pos, Position('c', '3'));
+    assert_eq!(pins[0].pinned_by_pos, Position('b', '4'));
+    assert_eq!(
+        pins[0].ray,
+        [Position('d', '2'), Position('c', '3'), Position('b', '4')]
+    );
+    assert_eq!(pins[1].piece, Piece::new(PieceType::Bishop, Color::White));
+    assert_eq!(pins[1].pinned_by, Piece::new(PieceType::Rook, Color::Black));
+    // two pieces in between are no pin
+    let game = Game::from_fen("4k3/8/4r3/4p3/8/4B3/8/4K3 w - - 0 1").unwrap();
+    assert!(game.pinned_pieces(Color::White).is_empty());
+    assert!(game.pinned_pieces(Color::Black).is_empty());
+}
+
+#[test]
+fn check_evasions() {
+    // the rook on e8 checks along the e file, knight and bishop can only
+    // block it and the king steps aside
+    let game = Game::from_fen("4r1k1/8/8/8/8/2N3B1/8/4K3 w - - 0 1").unwrap();
+    let mut moves: Vec<(Position, Position)> = game
+        .get_all_currently_valid_moves()
+        .iter()
+        .map(|mv| (mv.from, mv.to))
+        .collect();
+    moves.sort_by_key(|(from, to)| (from.as_index(), to.as_index()));
+    let mut expected = vec![
+        (Position('c', '3'), Position('e', '2')),
+        (Position('c', '3'), Position('e', '4')),
+        (Position('g', '3'), Position('e', '5')),
+        (Position('e', '1'), Position('d', '1')),
+        (Position('e', '1'), Position('d', '2')),
+        (Position('e', '1'), Position('f', '1')),
+        (Position('e', '1'), Position('f', '2')),
+    ];
+    expected.sort_by_key(|(from, to)| (from.as_index(), to.as_index()));
+    assert_eq!(moves, expected);
+    // moves are plain values without heap allocations
+    assert!(std::mem::size_of::<rusty_chess_core::game::Move>() <= 32);
+}
@@ -0,0 +1,64 @@
+use rusty_chess_core::game::{
+    Color, DecodeError, Game, Piece, PieceType, Position, UserInput, Variant,
+};
+
+fn play(game: &mut Game, moves: &[&str]) {
+    for uci in moves {
+        let mv = game.move_from_uci(uci).unwrap();
+        game.process_input(&UserInput::Move(mv.from, mv.to)).unwrap();
+    }
+}
+
+#[test]
+fn round_trip() {
+    let mut game = Game::new();
+    play(&mut game, &["e2e4", "d7d5", "e4d5", "c7c6", "d5c6", "d8d2"]);
+    let bytes = game.to_bytes();
+    assert_eq!(bytes.len(), 3 + 2 * 6);
+    let decoded = Game::from_bytes(&bytes).unwrap();
+    assert_eq!(decoded.history(), game.history());
+    assert_eq!(decoded.to_fen(), game.to_fen());
+
+    // promotion chosen after the move
+    let mut game = Game::from_fen("4k3/1P6/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    game.process_input(&UserInput::Move(Position('b', '7'), Position('b', '8')))
+        .unwrap();
+    game.process_input(&UserInput::Promotion(
+        Piece::new(PieceType::Rook, Color::White),
+        Position('b', '8'),
+    ))
+    .unwrap();
+    let decoded = Game::from_bytes(&game.to_bytes()).unwrap();
+    assert_eq!(decoded.to_fen(), "1R2k3/8/8/8/8/8/8/4K3 b - - 0 1");
+
+    let antichess = Game::with_variant(Variant::Antichess);
+    let decoded = Game::from_bytes(&antichess.to_bytes()).unwrap();
+    assert_eq!(decoded.variant(), Variant::Antichess);
+}
+
+#[test]
+fn rejects_invalid_bytes() {
+    let mut game = Game::new();
+    play(&mut game, &["e2e4"]);
+    let bytes = game.to_bytes();
+
+    assert_eq!(Game::from_bytes(&[]).unwrap_err(), DecodeError::UnexpectedEnd);
+    assert_eq!(
+        Game::from_bytes(&bytes[..bytes.len() - 1]).unwrap_err(),
+        DecodeError::UnexpectedEnd
+    );
+    assert_eq!(
+        Game::from_bytes(&[9, 0, 0]).unwrap_err(),
+        DecodeError::UnsupportedVersion(9)
+    );
+    assert_eq!(
+        Game::from_bytes(&[1, 7, 0]).unwrap_err(),
+        DecodeError::InvalidVariant(7)
+    );
+    let mut twice = bytes.clone();
+    twice.extend_from_slice(&bytes[3..]);
+    assert_eq!(
+        Game::from_bytes(&twice).unwrap_err(),
+        DecodeError::InvalidMove(1)
+    );
+}
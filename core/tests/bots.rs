@@ -0,0 +1,66 @@
+use rusty_chess_core::bots;
+use rusty_chess_core::game::{BotRng, Game, GameStatus, SeedableRng};
+
+type Bot = fn(&mut Game, &mut BotRng) -> Option<GameStatus>;
+
+fn play_bot_game(bot: Bot, seed: u64, plies: usize) -> Vec<String> {
+    let mut game = Game::new();
+    let mut rng = BotRng::seed_from_u64(seed);
+    for _ in 0..plies {
+        if bot(&mut game, &mut rng).is_none_or(|status| status.is_finished()) {
+            break;
+        }
+    }
+    game.history()
+        .iter()
+        .map(|played| played.mv.to_uci())
+        .collect()
+}
+
+#[test]
+fn same_seed_plays_same_moves() {
+    for bot in [
+        bots::play_attacking_king as Bot,
+        bots::play_randomly_aggressive,
+    ] {
+        assert_eq!(play_bot_game(bot, 7, 40), play_bot_game(bot, 7, 40));
+    }
+    assert_ne!(
+        play_bot_game(bots::play_randomly_aggressive, 7, 40),
+        play_bot_game(bots::play_randomly_aggressive, 8, 40)
+    );
+}
+
+#[test]
+fn attacking_king_checks() {
+    let game = Game::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 0 1").unwrap();
+    let mut rng = BotRng::seed_from_u64(0);
+    let mv = bots::attacking_king_move(&game, &mut rng).unwrap();
+    assert_eq!(mv.to_uci(), "a1a8");
+}
+
+#[test]
+fn randomly_aggressive_captures() {
+    let mut game =
+        Game::from_fen("rnbqkbnr/ppp1pppp/8/3p4/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 2").unwrap();
+    let mut rng = BotRng::seed_from_u64(0);
+    bots::play_randomly_aggressive(&mut game, &mut rng).unwrap();
+    assert_eq!(game.history().last().unwrap().mv.to_uci(), "e4d5");
+}
+
+#[test]
+fn bots_promote_to_a_queen() {
+    let mut game = Game::from_fen("7k/P7/8/8/8/8/8/K7 w - - 0 1").unwrap();
+    let mut rng = BotRng::seed_from_u64(0);
+    let status = bots::play_attacking_king(&mut game, &mut rng).unwrap();
+    assert_eq!(status, GameStatus::Check);
+    assert_eq!(game.to_fen(), "Q6k/8/8/8/8/8/8/K7 b - - 0 1");
+}
+
+#[test]
+fn no_move_after_the_game_is_over() {
+    let mut game = Game::from_fen("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1").unwrap();
+    let mut rng = BotRng::seed_from_u64(0);
+    assert_eq!(bots::play_attacking_king(&mut game, &mut rng), None);
+    assert_eq!(bots::play_randomly_aggressive(&mut game, &mut rng), None);
+}
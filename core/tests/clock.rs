@@ -0,0 +1,122 @@
+use core::time::Duration;
+use rusty_chess_core::game::{
+    Clock, ClockStage, Color, Game, GameStatus, MoveError, Piece, PieceType, Position, UserInput,
+};
+
+fn mv(from: &str, to: &str) -> UserInput {
+    let square = |name: &str| {
+        let mut chars = name.chars();
+        Position(chars.next().unwrap(), chars.next().unwrap())
+    };
+    UserInput::Move(square(from), square(to))
+}
+
+const fn secs(secs: u64) -> Duration {
+    Duration::from_secs(secs)
+}
+
+#[test]
+fn increment_and_stages() {
+    // two moves in a minute, then 10 seconds more for every move
+    let mut clock = Clock::new(vec![
+        ClockStage::new(Some(2), secs(60), Duration::ZERO),
+        ClockStage::new(None, secs(30), secs(10)),
+    ]);
+    assert_eq!(clock.moves_to_go(Color::White), Some(2));
+    assert!(clock.press(Color::White, secs(20)));
+    assert_eq!(clock.remaining(Color::White), secs(40));
+    assert_eq!(clock.moves_to_go(Color::White), Some(1));
+    assert!(clock.press(Color::White, secs(30)));
+    // the second stage adds its time to the rest
+    assert_eq!(clock.remaining(Color::White), secs(40));
+    assert_eq!(clock.moves_to_go(Color::White), None);
+    assert_eq!(clock.stage(Color::White).increment, secs(10));
+    assert!(clock.press(Color::White, secs(5)));
+    assert_eq!(clock.remaining(Color::White), secs(45));
+    assert_eq!(clock.remaining(Color::Black), secs(60));
+    assert_eq!(clock.flagged(), None);
+
+    assert!(!clock.press(Color::Black, secs(60)));
+    assert_eq!(clock.flagged(), Some(Color::Black));
+    assert_eq!(clock.remaining(Color::Black), Duration::ZERO);
+}
+
+#[test]
+fn repeating_stage() {
+    let mut clock = Clock::new(vec![ClockStage::new(Some(2), secs(10), Duration::ZERO)]);
+    for _ in 0..2 {
+        assert!(clock.press(Color::White, secs(1)));
+    }
+    assert_eq!(clock.remaining(Color::White), secs(18));
+    assert_eq!(clock.moves_to_go(Color::White), Some(2));
+}
+
+#[test]
+fn game_with_clock() {
+    let mut game = Game::new();
+    game.set_clock(Clock::with_increment(secs(60), secs(2)));
+    game.process_timed_input(&mv("e2", "e4"), secs(5)).unwrap();
+    // a draw offer does not end the turn
+    game.process_timed_input(&UserInput::Draw, secs(3)).unwrap();
+    game.process_timed_input(&mv("e7", "e5"), secs(1)).unwrap();
+    let clock = game.clock().unwrap();
+    assert_eq!(clock.remaining(Color::White), secs(57));
+    assert_eq!(clock.remaining(Color::Black), secs(58));
+
+    // the time of an illegal move is taken as well
+    let error = game.process_timed_input(&mv("e4", "e6"), secs(50));
+    assert!(error.is_err());
+    assert_eq!(game.clock().unwrap().remaining(Color::White), secs(7));
+    let status = game.process_timed_input(&mv("g1", "f3"), secs(7));
+    assert_eq!(status, Ok(GameStatus::Timeout(Color::Black)));
+    assert_eq!(game.status(), GameStatus::Timeout(Color::Black));
+    assert_eq!(game.history().len(), 2);
+    assert_eq!(
+        game.process_timed_input(&mv("g1", "f3"), Duration::ZERO),
+        Err(MoveError::GameOver)
+    );
+}
+
+#[test]
+fn promotion_completes_the_move() {
+    let mut game = Game::from_fen("8/4P3/8/8/8/k7/8/4K3 w - - 0 1").unwrap();
+    game.set_clock(Clock::with_increment(secs(10), secs(1)));
+    game.process_timed_input(&mv("e7", "e8"), secs(1)).unwrap();
+    assert_eq!(game.clock().unwrap().remaining(Color::White), secs(9));
+    let queen = Piece::new(PieceType::Queen, Color::White);
+    game.process_timed_input(&UserInput::Promotion(queen, Position('e', '8')), secs(1))
+        .unwrap();
+    assert_eq!(game.clock().unwrap().remaining(Color::White), secs(9));
+    assert_eq!(game.turn, Color::Black);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn running_clock() {
+    let mut game = Game::new();
+    game.set_clock(Clock::sudden_death(Duration::from_millis(100)));
+    assert!(game.time_left().unwrap() <= Duration::from_millis(100));
+    game.process_input(&mv("e2", "e4")).unwrap();
+    assert!(game.clock().unwrap().remaining(Color::White) < Duration::from_millis(100));
+    assert_eq!(game.check_flag(), GameStatus::Ongoing);
+
+    std::thread::sleep(Duration::from_millis(120));
+    assert_eq!(game.time_left(), Some(Duration::ZERO));
+    assert_eq!(game.check_flag(), GameStatus::Timeout(Color::White));
+    assert_eq!(game.clock().unwrap().flagged(), Some(Color::Black));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn search_with_almost_no_time() {
+    use rusty_chess_core::engine::{find_mate, Engine, SearchLimits};
+
+    let mut game = Game::from_fen("kbK5/pp6/1P6/8/8/8/8/R7 w - - 0 1").unwrap();
+    game.set_clock(Clock::sudden_death(Duration::from_millis(1)));
+    std::thread::sleep(Duration::from_millis(5));
+    // the search must not run the clock of the game
+    let result = Engine::new().search(&game, &SearchLimits::depth(3));
+    assert_eq!(result.best_move.unwrap().to_uci(), "a1a6");
+    assert_eq!(find_mate(&game, 3).unwrap().len(), 3);
+    assert_eq!(game.status(), GameStatus::Ongoing);
+}
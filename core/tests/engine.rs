@@ -0,0 +1,348 @@
+use core::time::Duration;
+use rusty_chess_core::engine::{
+    find_mate, generate_data, self_play, DataGen, Engine, EngineHandle, MatchScore, SearchLimits,
+    SelfPlay, SkillLevel, MATE_SCORE,
+};
+use rusty_chess_core::game::{Color, Game, GameStatus, PieceType, UserInput};
+
+fn best_move(fen: &str, depth: u8) -> String {
+    let game = Game::from_fen(fen).unwrap();
+    Engine::new()
+        .search(&game, &SearchLimits::depth(depth))
+        .best_move
+        .unwrap()
+        .to_uci()
+}
+
+#[test]
+fn finds_mates() {
+    let game = Game::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1").unwrap();
+    let result = Engine::new().search(&game, &SearchLimits::depth(1));
+    assert_eq!(result.best_move.unwrap().to_uci(), "a1a8");
+    assert_eq!(result.score, MATE_SCORE - 1);
+    assert_eq!(result.mate_in(), Some(1));
+
+    // the quiet rook sacrifice is late in the move order
+    let game = Game::from_fen("kbK5/pp6/1P6/8/8/8/8/R7 w - - 0 1").unwrap();
+    let result = Engine::new().search(&game, &SearchLimits::depth(5));
+    assert_eq!(result.best_move.unwrap().to_uci(), "a1a6");
+    assert_eq!(result.mate_in(), Some(2));
+
+    // black gives the king room or blocks the rook before it mates
+    let game = Game::from_fen("6k1/5ppp/8/8/8/8/r7/R5K1 b - - 0 1").unwrap();
+    let result = Engine::new().search(&game, &SearchLimits::depth(3));
+    assert_eq!(result.mate_in(), None);
+}
+
+#[test]
+fn wins_material() {
+    assert_eq!(best_move("4k3/8/8/3q4/8/8/3R4/4K3 w - - 0 1", 2), "d2d5");
+    // the pawn is protected, taking it loses the queen
+    let game = Game::from_fen("4k3/2p5/3p4/8/8/8/3Q4/4K3 w - - 0 1").unwrap();
+    let result = Engine::new().search(&game, &SearchLimits::depth(2));
+    assert_ne!(result.best_move.unwrap().to_uci(), "d2d6");
+    assert!(result.score > 0);
+}
+
+#[test]
+fn promotes_to_queen() {
+    let game = Game::from_fen("8/4P1k1/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    let result = Engine::new().search(&game, &SearchLimits::depth(2));
+    let mv = result.best_move.unwrap();
+    assert_eq!(mv.to_uci(), "e7e8q");
+    assert_eq!(mv.promotion, Some(PieceType::Queen));
+}
+
+#[test]
+fn no_move_when_the_game_is_over() {
+    let game = Game::from_fen("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1").unwrap();
+    let result = Engine::new().search(&game, &SearchLimits::depth(4));
+    assert_eq!(result.best_move, None);
+    assert_eq!(result.score, 0);
+}
+
+#[test]
+fn plays_on_when_a_draw_can_be_claimed() {
+    // the fifty-move rule allows a claim, but the rook mates
+    let game = Game::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 100 80").unwrap();
+    assert!(game.claimable_draw().is_some());
+    let result = Engine::new().search(&game, &SearchLimits::depth(2));
+    assert_eq!(result.best_move.unwrap().to_uci(), "a1a8");
+    assert_eq!(result.mate_in(), Some(1));
+}
+
+#[test]
+fn stops_at_the_limits() {
+    let game = Game::new();
+    let result = Engine::new().search(&game, &SearchLimits::depth(3));
+    assert_eq!(result.depth, 3);
+    assert!(result.best_move.is_some());
+
+    // the first ply is searched in any case
+    let result = Engine::new().search(&game, &SearchLimits::nodes(1));
+    assert_eq!(result.depth, 1);
+    assert!(result.best_move.is_some());
+
+    let result = Engine::new().search(&game, &SearchLimits::nodes(5_000));
+    assert!(result.nodes < 6_000);
+    assert!(result.depth >= 2);
+
+    let start = std::time::Instant::now();
+    let result = Engine::new().search(&game, &SearchLimits::movetime(Duration::from_millis(200)));
+    assert!(start.elapsed() < Duration::from_secs(1));
+    assert!(result.best_move.is_some());
+}
+
+#[test]
+fn time_management() {
+    let clock = SearchLimits {
+        wtime: Some(Duration::from_secs(60)),
+        btime: Some(Duration::from_millis(300)),
+        winc: Some(Duration::from_secs(2)),
+        ..SearchLimits::default()
+    };
+    // a thirtieth of the clock and half of the increment
+    assert_eq!(
+        clock.time_budget(Color::White),
+        Some(Duration::from_secs(3))
+    );
+    assert_eq!(
+        clock.time_budget(Color::Black),
+        Some(Duration::from_millis(10))
+    );
+    let last_move = SearchLimits {
+        moves_to_go: Some(1),
+        ..clock
+    };
+    // never more than half of the clock
+    assert_eq!(
+        last_move.time_budget(Color::White),
+        Some(Duration::from_secs(30))
+    );
+    let both = SearchLimits {
+        movetime: Some(Duration::from_secs(1)),
+        ..clock
+    };
+    assert_eq!(both.time_budget(Color::White), Some(Duration::from_secs(1)));
+    assert_eq!(SearchLimits::depth(3).time_budget(Color::White), None);
+
+    // Black to move with little time
+    let game = Game::from_pgn("1. e4").unwrap();
+    let start = std::time::Instant::now();
+    let result = Engine::new().search(&game, &clock);
+    assert!(start.elapsed() < Duration::from_millis(500));
+    assert!(result.best_move.is_some());
+}
+
+#[test]
+fn multi_pv_lines() {
+    let game = Game::from_fen("kbK5/pp6/1P6/8/8/8/8/R7 w - - 0 1").unwrap();
+    let result = Engine::new()
+        .multi_pv(3)
+        .search(&game, &SearchLimits::depth(4));
+    assert_eq!(result.lines.len(), 3);
+    assert_eq!(result.lines[0].moves[0], result.best_move.unwrap());
+    assert_eq!(result.lines[0].score, result.score);
+    let uci: Vec<String> = result.lines[0].moves.iter().map(|mv| mv.to_uci()).collect();
+    assert_eq!(uci, ["a1a6", "b7a6", "b6b7"]);
+    assert!(result.lines[1].mate_in().is_none());
+    assert!(result
+        .lines
+        .windows(2)
+        .all(|lines| lines[0].score >= lines[1].score));
+
+    // more lines than legal moves
+    let game = Game::from_fen("k7/8/8/8/8/1r6/7P/K7 w - - 0 1").unwrap();
+    let result = Engine::new()
+        .multi_pv(5)
+        .search(&game, &SearchLimits::depth(2));
+    assert_eq!(result.lines.len(), 3);
+}
+
+#[test]
+fn reports_every_depth() {
+    let game = Game::new();
+    let mut infos = Vec::new();
+    let engine = Engine::new().multi_pv(2);
+    let result = engine.search_with_info(&game, &SearchLimits::depth(4), |info| {
+        assert_eq!(info.pv, info.lines[0].moves.as_slice());
+        assert_eq!(info.lines.len(), 2);
+        infos.push((info.depth, info.nodes, info.pv.to_vec()));
+    });
+    let depths: Vec<u8> = infos.iter().map(|(depth, _, _)| *depth).collect();
+    assert_eq!(depths, [1, 2, 3, 4]);
+    assert!(infos.windows(2).all(|infos| infos[0].1 < infos[1].1));
+    let (_, nodes, pv) = infos.last().unwrap();
+    assert_eq!(*nodes, result.nodes);
+    assert_eq!(*pv, result.lines[0].moves);
+}
+
+#[test]
+fn search_on_another_thread() {
+    let game = Game::from_fen("kbK5/pp6/1P6/8/8/8/8/R7 w - - 0 1").unwrap();
+    let limits = SearchLimits::depth(4);
+    let handle = Engine::new().start_search(&game, limits);
+    assert_eq!(handle.wait(), Engine::new().search(&game, &limits));
+
+    let handle = EngineHandle::start_search(&Game::new(), SearchLimits::default());
+    std::thread::sleep(Duration::from_millis(100));
+    assert!(!handle.is_finished());
+    let result = handle.stop();
+    assert!(result.depth >= 1);
+    assert!(result.best_move.is_some());
+}
+
+#[test]
+fn weaker_skill_levels() {
+    assert_eq!(SkillLevel::new(100), SkillLevel::MAX);
+    assert_eq!(SkillLevel::default(), SkillLevel::MAX);
+    assert_eq!(SkillLevel::MIN.max_depth(), 1);
+
+    let game = Game::new();
+    let weakest = Engine::new().skill_level(SkillLevel::MIN).seed(7);
+    let result = weakest.search(&game, &SearchLimits::default());
+    assert_eq!(result.depth, 1);
+    assert_eq!(result.lines.len(), 1);
+    assert!(game.legal_moves().contains(&result.best_move.unwrap()));
+    // the same seed plays the same move
+    assert_eq!(weakest.search(&game, &SearchLimits::default()), result);
+
+    // mates are not missed on purpose
+    let game = Game::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1").unwrap();
+    let result = weakest.search(&game, &SearchLimits::default());
+    assert_eq!(result.best_move.unwrap().to_uci(), "a1a8");
+}
+
+#[test]
+fn mate_solver() {
+    let game = Game::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1").unwrap();
+    let line = find_mate(&game, 5).unwrap();
+    assert_eq!(line.len(), 1);
+    assert_eq!(line[0].to_uci(), "a1a8");
+
+    // the defence takes the longest line, which still ends in mate
+    let mut game = Game::from_fen("kbK5/pp6/1P6/8/8/8/8/R7 w - - 0 1").unwrap();
+    let line = find_mate(&game, 5).unwrap();
+    assert_eq!(line.len(), 3);
+    for mv in &line {
+        let input = match mv.promotion {
+            Some(piece_type) => UserInput::MoveWithPromotion(mv.from, mv.to, piece_type),
+            None => UserInput::Move(mv.from, mv.to),
+        };
+        game.process_input(&input).unwrap();
+    }
+    assert_eq!(game.status(), GameStatus::Checkmate(Color::White));
+
+    // stalemate is no mate
+    let game = Game::from_fen("k7/8/1Q6/8/8/8/8/7K b - - 0 1").unwrap();
+    assert_eq!(find_mate(&game, 5), None);
+    assert_eq!(find_mate(&Game::new(), 3), None);
+}
+
+#[test]
+fn contempt_avoids_or_seeks_repetitions() {
+    // the knight going back to f3 repeats the position after the first move
+    let repeating = |fen: &str| {
+        let mut game = Game::from_fen(fen).unwrap();
+        for uci in ["g1f3", "e8d8", "f3g1", "d8e8"] {
+            let mv = game.move_from_uci(uci).unwrap();
+            game.process_input(&UserInput::Move(mv.from, mv.to))
+                .unwrap();
+        }
+        game
+    };
+    let limits = SearchLimits::depth(3);
+
+    // a rook down the draw saves the game
+    let worse = repeating("r3k3/8/8/8/8/8/8/4K1N1 w - - 0 1");
+    let result = Engine::new().search(&worse, &limits);
+    assert_eq!(result.best_move.unwrap().to_uci(), "g1f3");
+    assert_eq!(result.score, 0);
+    let result = Engine::new().contempt(1_000).search(&worse, &limits);
+    assert_ne!(result.best_move.unwrap().to_uci(), "g1f3");
+
+    // against a stronger opponent a draw is welcome in an equal position
+    let equal = repeating("4k1n1/8/8/8/8/8/8/4K1N1 w - - 0 1");
+    let result = Engine::new().contempt(-300).search(&equal, &limits);
+    assert_eq!(result.best_move.unwrap().to_uci(), "g1f3");
+    assert_eq!(result.score, 300);
+}
+
+#[test]
+fn self_play_elo() {
+    let score = MatchScore {
+        wins: 60,
+        draws: 20,
+        losses: 20,
+    };
+    assert_eq!(score.to_string(), "+60 =20 -20");
+    assert!((score.score() - 0.7).abs() < 1e-9);
+    assert!((score.draw_rate() - 0.2).abs() < 1e-9);
+    assert!((score.elo_difference() - 147.2).abs() < 0.1);
+    // more games make the estimate more precise
+    let longer = MatchScore {
+        wins: 600,
+        draws: 200,
+        losses: 200,
+    };
+    assert!(longer.elo_error() < score.elo_error() / 3.0);
+    assert_eq!(MatchScore::default().elo_difference(), 0.0);
+    let sweep = MatchScore {
+        wins: 4,
+        ..MatchScore::default()
+    };
+    assert_eq!(sweep.elo_difference(), f64::INFINITY);
+    assert_eq!(sweep.elo_error(), f64::INFINITY);
+
+    let weak = Engine::new().skill_level(SkillLevel::MIN);
+    let options = SelfPlay {
+        max_plies: 16,
+        ..SelfPlay::new(4, SearchLimits::depth(1))
+    };
+    let mut progress = Vec::new();
+    let score = self_play(&Engine::new(), &weak, &options, |score| {
+        progress.push(score.games());
+    });
+    assert_eq!(progress, [1, 2, 3, 4]);
+    assert_eq!(score.games(), 4);
+}
+
+#[test]
+fn self_play_data() {
+    let options = DataGen {
+        noise: 0.5,
+        max_plies: 20,
+        seed: 3,
+        ..DataGen::new(2, SearchLimits::depth(1))
+    };
+    let mut games = Vec::new();
+    generate_data(&Engine::new(), &options, |played| {
+        games.push(played.clone())
+    });
+    assert_eq!(games.len(), 2);
+    for played in &games {
+        assert!(played.game.status().is_finished());
+        assert_eq!(played.game.result(), Some(played.result));
+        // one position for every move after the opening
+        assert_eq!(
+            played.positions.len(),
+            played.game.history().len() - usize::from(options.opening_plies)
+        );
+        let records = played.to_records();
+        assert_eq!(records.lines().count(), played.positions.len());
+        for (line, position) in records.lines().zip(&played.positions) {
+            let fields: Vec<&str> = line.split(" | ").collect();
+            assert_eq!(fields[0], position.fen);
+            assert!(Game::from_fen(fields[0]).is_ok());
+            assert_eq!(fields[1].parse::<i32>().unwrap(), position.score);
+        }
+    }
+
+    // the same seed plays the same games
+    let mut again = Vec::new();
+    generate_data(&Engine::new(), &options, |played| {
+        again.push(played.to_records())
+    });
+    let records: Vec<String> = games.iter().map(|played| played.to_records()).collect();
+    assert_eq!(again, records);
+}
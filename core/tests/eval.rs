@@ -0,0 +1,74 @@
+use rusty_chess_core::eval::{evaluate, evaluate_with, EvalParams, EVAL_PARAM_COUNT};
+use rusty_chess_core::game::{Game, Variant};
+
+fn eval(fen: &str) -> i32 {
+    evaluate(&Game::from_fen(fen).unwrap())
+}
+
+#[test]
+fn mirrored_positions_have_opposite_scores() {
+    let fens = [
+        (
+            "r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3",
+            "rnbqkb1r/pppp1ppp/5n2/4p3/4P3/2N5/PPPP1PPP/R1BQKBNR b KQkq - 2 3",
+        ),
+        (
+            "6k1/5ppp/8/8/3P4/8/5PPP/R5K1 w - - 0 1",
+            "r5k1/5ppp/8/3p4/8/8/5PPP/6K1 b - - 0 1",
+        ),
+    ];
+    for (white, black) in fens {
+        assert_eq!(eval(white), -eval(black), "{white}");
+    }
+}
+
+#[test]
+fn pawn_structure() {
+    let healthy = eval("4k3/pp6/8/8/8/8/PP6/4K3 w - - 0 1");
+    let doubled = eval("4k3/pp6/8/8/8/1P6/1P6/4K3 w - - 0 1");
+    let isolated = eval("4k3/pp6/8/8/8/8/P1P5/4K3 w - - 0 1");
+    assert!(doubled < healthy);
+    assert!(isolated < healthy);
+    // the further a passed pawn is, the better
+    let passed = eval("4k3/8/8/8/P7/8/8/4K3 w - - 0 1");
+    let advanced = eval("4k3/8/P7/8/8/8/8/4K3 w - - 0 1");
+    assert!(advanced > passed);
+    assert!(passed > eval("4k3/p7/8/8/P7/8/8/4K3 w - - 0 1") + 100);
+}
+
+#[test]
+fn king_behind_pawns_is_safer() {
+    let sheltered = eval("rnbq1rk1/ppppbppp/5n2/4p3/4P3/5N2/PPPPBPPP/RNBQ1RK1 w - - 0 1");
+    let exposed = eval("rnbq1rk1/ppppbppp/5n2/4p3/4P3/5NP1/PPPPBP1P/RNBQ1RK1 w - - 0 1");
+    assert!(sheltered > exposed);
+}
+
+#[test]
+fn antichess_counts_material_backwards() {
+    let game =
+        Game::from_fen_with_variant("4k3/8/8/8/8/8/8/4K2Q w - - 0 1", Variant::Antichess).unwrap();
+    assert!(evaluate(&game) < 0);
+}
+
+#[test]
+fn eval_params() {
+    let game =
+        Game::from_fen("r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3").unwrap();
+    assert_eq!(
+        evaluate_with(&game, &EvalParams::default()),
+        evaluate(&game)
+    );
+
+    let values = EvalParams::DEFAULT.to_vec();
+    assert_eq!(values.len(), EVAL_PARAM_COUNT);
+    assert_eq!(EvalParams::from_slice(&values), Some(EvalParams::DEFAULT));
+    assert_eq!(EvalParams::from_slice(&values[1..]), None);
+
+    // an extra pawn is worth as much as the pawn value says
+    let params = EvalParams {
+        pawn: 200,
+        ..EvalParams::DEFAULT
+    };
+    let pawn_up = Game::from_fen("4k3/8/8/8/8/8/P7/4K3 w - - 0 1").unwrap();
+    assert_eq!(evaluate_with(&pawn_up, &params) - evaluate(&pawn_up), 100);
+}
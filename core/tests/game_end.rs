@@ -0,0 +1,161 @@
+use rusty_chess_core::game::{
+    Color, DrawReason, Game, GameStatus, MoveError, Position, TerminationReason, UserInput,
+};
+
+fn mv(from: &str, to: &str) -> UserInput {
+    let square = |name: &str| {
+        let mut chars = name.chars();
+        Position(chars.next().unwrap(), chars.next().unwrap())
+    };
+    UserInput::Move(square(from), square(to))
+}
+
+#[test]
+fn insufficient_material() {
+    let insufficient = GameStatus::Draw(DrawReason::InsufficientMaterial);
+    for fen in [
+        "4k3/8/8/8/8/8/8/4K3 w - - 0 1",
+        "4k3/8/8/8/8/8/8/2B1K3 w - - 0 1",
+        "4k3/8/8/8/8/8/8/1N2K3 b - - 0 1",
+        // both bishops on dark squares
+        "4kb2/8/8/8/8/8/8/2B1K3 w - - 0 1",
+    ] {
+        assert_eq!(Game::from_fen(fen).unwrap().status(), insufficient, "{fen}");
+    }
+    for fen in [
+        // bishops on squares of different colors
+        "2b1k3/8/8/8/8/8/8/2B1K3 w - - 0 1",
+        "4k3/8/8/8/8/8/8/1N2KN2 w - - 0 1",
+    ] {
+        assert_eq!(
+            Game::from_fen(fen).unwrap().status(),
+            GameStatus::Ongoing,
+            "{fen}"
+        );
+    }
+
+    // capturing the last pawn ends the game
+    let mut game = Game::from_fen("4k3/8/8/8/8/8/3p4/4K3 w - - 0 1").unwrap();
+    assert_eq!(game.process_input(&mv("e1", "d2")), Ok(insufficient));
+}
+
+#[test]
+fn dead_position() {
+    // neither king can get through the locked pawns
+    let wall = "1p1p1p1p/pPpPpPpP/P1P1P1P1";
+    let game = Game::from_fen(&format!("4k3/8/8/{wall}/8/4K3 w - - 0 1")).unwrap();
+    assert_eq!(game.status(), GameStatus::Draw(DrawReason::DeadPosition));
+
+    // the white king is behind the wall and can capture the pawn on b5
+    let game = Game::from_fen(&format!("4k3/8/K7/{wall}/8/8 w - - 0 1")).unwrap();
+    assert_eq!(game.status(), GameStatus::Ongoing);
+
+    // the pawn on d5 just moved, so it might still be captured en passant
+    let mut game = Game::from_fen(&format!("4k3/8/8/{wall}/8/4K3 w - d6 0 1")).unwrap();
+    assert_eq!(game.status(), GameStatus::Ongoing);
+    assert_eq!(
+        game.process_input(&mv("e1", "d1")),
+        Ok(GameStatus::Draw(DrawReason::DeadPosition))
+    );
+}
+
+#[test]
+fn draw_offers() {
+    let mut game = Game::new();
+    assert_eq!(
+        game.process_input(&UserInput::AcceptDraw),
+        Err(MoveError::NoDrawOffer)
+    );
+    assert_eq!(
+        game.process_input(&UserInput::DeclineDraw),
+        Err(MoveError::NoDrawOffer)
+    );
+
+    // White can not answer its own offer, Black declines it on its turn
+    game.process_input(&UserInput::Draw).unwrap();
+    assert_eq!(game.draw_offer(), Some(Color::White));
+    assert_eq!(
+        game.process_input(&UserInput::AcceptDraw),
+        Err(MoveError::NoDrawOffer)
+    );
+    assert_eq!(
+        game.process_input(&UserInput::DeclineDraw),
+        Err(MoveError::NoDrawOffer)
+    );
+    game.process_input(&mv("e2", "e4")).unwrap();
+    assert_eq!(game.draw_offer(), Some(Color::White));
+    assert_eq!(
+        game.process_input(&UserInput::DeclineDraw),
+        Ok(GameStatus::Ongoing)
+    );
+    assert_eq!(game.draw_offer(), None);
+    assert_eq!(
+        game.process_input(&UserInput::AcceptDraw),
+        Err(MoveError::NoDrawOffer)
+    );
+
+    // the offer lapses when White moves instead of answering
+    game.process_input(&UserInput::Draw).unwrap();
+    game.process_input(&mv("e7", "e5")).unwrap();
+    assert_eq!(game.draw_offer(), Some(Color::Black));
+    game.process_input(&mv("d2", "d4")).unwrap();
+    assert_eq!(game.draw_offer(), None);
+    assert_eq!(
+        game.process_input(&UserInput::AcceptDraw),
+        Err(MoveError::NoDrawOffer)
+    );
+
+    game.process_input(&UserInput::Draw).unwrap();
+    game.process_input(&mv("d7", "d5")).unwrap();
+    assert_eq!(
+        game.process_input(&UserInput::AcceptDraw),
+        Ok(GameStatus::Draw(DrawReason::Agreement))
+    );
+    assert_eq!(game.status(), GameStatus::Draw(DrawReason::Agreement));
+    assert_eq!(game.draw_offer(), None);
+}
+
+#[test]
+fn no_input_after_the_end() {
+    // fool's mate
+    let mut game = Game::new();
+    for (from, to) in [("f2", "f3"), ("e7", "e5"), ("g2", "g4"), ("d8", "h4")] {
+        game.process_input(&mv(from, to)).unwrap();
+    }
+    assert_eq!(
+        game.termination_reason(),
+        Some(TerminationReason::Checkmate)
+    );
+
+    let mut resigned = Game::new();
+    assert_eq!(
+        resigned.process_input(&UserInput::Resign),
+        Ok(GameStatus::Resignation(Color::Black))
+    );
+    assert_eq!(
+        resigned.termination_reason(),
+        Some(TerminationReason::Resignation)
+    );
+
+    let mut drawn = Game::from_fen("4k3/8/8/8/8/8/3p4/4K3 w - - 0 1").unwrap();
+    drawn.process_input(&mv("e1", "d2")).unwrap();
+    assert_eq!(
+        drawn.termination_reason(),
+        Some(TerminationReason::Draw(DrawReason::InsufficientMaterial))
+    );
+
+    for game in [&mut game, &mut resigned, &mut drawn] {
+        let status = game.status();
+        for input in [
+            mv("g8", "f6"),
+            UserInput::Draw,
+            UserInput::ClaimDraw,
+            UserInput::Resign,
+            UserInput::Timeout,
+        ] {
+            assert_eq!(game.process_input(&input), Err(MoveError::GameOver));
+        }
+        assert_eq!(game.status(), status);
+    }
+    assert_eq!(Game::new().termination_reason(), None);
+}
@@ -0,0 +1,49 @@
+use rusty_chess_core::game::{Color, Game, Handicap, Position, UserInput};
+
+#[test]
+fn handicaps() {
+    for (handicap, fen, turn) in [
+        (
+            Handicap::PawnAndMove,
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPP1PP/RNBQKBNR b KQkq - 0 1",
+            Color::Black,
+        ),
+        (
+            Handicap::KnightOdds,
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/R1BQKBNR w KQkq - 0 1",
+            Color::White,
+        ),
+        (
+            Handicap::RookOdds,
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/1NBQKBNR w Kkq - 0 1",
+            Color::White,
+        ),
+        (
+            Handicap::QueenOdds,
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNB1KBNR w KQkq - 0 1",
+            Color::White,
+        ),
+        (
+            Handicap::FirstMove,
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR b KQkq - 0 1",
+            Color::Black,
+        ),
+    ] {
+        let game = Game::with_handicap(handicap);
+        assert_eq!(game.to_fen(), fen, "{handicap:?}");
+        assert_eq!(game.turn, turn, "{handicap:?}");
+    }
+
+    // without the a1 rook White can only castle short
+    let mut game = Game::with_handicap(Handicap::RookOdds);
+    for uci in [
+        "b1c3", "a7a6", "d2d4", "a6a5", "c1f4", "h7h6", "d1d2", "h6h5",
+    ] {
+        let mv = game.move_from_uci(uci).unwrap();
+        game.process_input(&UserInput::Move(mv.from, mv.to))
+            .unwrap();
+    }
+    assert!(game
+        .process_input(&UserInput::Move(Position('e', '1'), Position('c', '1')))
+        .is_err());
+}
@@ -0,0 +1,123 @@
+#![cfg(feature = "nnue")]
+
+use rusty_chess_core::engine::{Engine, SearchLimits};
+use rusty_chess_core::eval::{Network, NnueError};
+use rusty_chess_core::game::{Game, UserInput};
+use std::sync::Arc;
+
+const INPUTS: usize = 768;
+
+/// Network bytes with `weight(input, neuron)` as weights of the hidden layer
+/// and the given output weights.
+fn network_bytes(
+    hidden: usize,
+    weight: impl Fn(usize, usize) -> i16,
+    output_weights: &[i16],
+) -> Vec<u8> {
+    let mut values: Vec<i16> = (0..INPUTS * hidden)
+        .map(|index| weight(index / hidden, index % hidden))
+        .collect();
+    values.extend(std::iter::repeat_n(0, hidden));
+    values.extend_from_slice(output_weights);
+    values.push(0);
+    let mut bytes = vec![1];
+    bytes.extend_from_slice(&(hidden as u16).to_le_bytes());
+    for value in values {
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+    bytes
+}
+
+/// Counts the own pawns of each side, 100 centipawns each.
+fn pawn_network() -> Network {
+    let bytes = network_bytes(
+        1,
+        |input, _| i16::from(input < 64),
+        // 100 * 255 * 64 / 400
+        &[4_080, -4_080],
+    );
+    Network::from_bytes(&bytes).unwrap()
+}
+
+#[test]
+fn counts_pawns() {
+    let network = pawn_network();
+    assert_eq!(network.hidden_size(), 1);
+    assert_eq!(network.evaluate(&Game::new()), 0);
+    for fen in [
+        "4k3/8/8/8/8/8/P7/4K3 w - - 0 1",
+        "4k3/8/8/8/8/8/P7/4K3 b - - 0 1",
+    ] {
+        assert_eq!(
+            network.evaluate(&Game::from_fen(fen).unwrap()),
+            100,
+            "{fen}"
+        );
+    }
+    assert_eq!(
+        network.evaluate(&Game::from_fen("4k3/pp6/8/8/8/8/P7/4K3 w - - 0 1").unwrap()),
+        -100
+    );
+}
+
+#[test]
+fn incremental_updates_match_a_refresh() {
+    let bytes = network_bytes(
+        4,
+        |input, neuron| ((input * 31 + neuron * 17) % 23) as i16 - 11,
+        &[5, -3, 7, 2, -4, 6, 1, -8],
+    );
+    let network = Network::from_bytes(&bytes).unwrap();
+    assert_eq!(network.to_bytes(), bytes);
+    // castling, en passant, a capture with promotion
+    let mut game = Game::from_fen("r3k2r/1P6/8/8/5p2/8/4P3/R3K2R w KQkq - 0 1").unwrap();
+    let mut accumulator = network.accumulator(&game);
+    for uci in ["e1g1", "e8g8", "e2e4", "f4e3", "b7a8q"] {
+        let before = game.clone();
+        let mv = game.move_from_uci(uci).unwrap();
+        let input = match mv.promotion {
+            Some(piece_type) => UserInput::MoveWithPromotion(mv.from, mv.to, piece_type),
+            None => UserInput::Move(mv.from, mv.to),
+        };
+        game.process_input(&input).unwrap();
+        network.update(&mut accumulator, &before, &game);
+        assert_eq!(accumulator, network.accumulator(&game), "{uci}");
+        assert_eq!(
+            network.evaluate_accumulator(&accumulator, game.turn),
+            network.evaluate(&game)
+        );
+    }
+}
+
+#[test]
+fn engine_searches_with_the_network() {
+    let engine = Engine::new().network(Arc::new(pawn_network()));
+    let game = Game::from_fen("4k3/8/8/3p4/4P3/8/8/4K3 w - - 0 1").unwrap();
+    let result = engine.search(&game, &SearchLimits::depth(2));
+    assert_eq!(result.best_move.unwrap().to_uci(), "e4d5");
+    assert_eq!(result.score, 100);
+}
+
+#[test]
+fn invalid_networks() {
+    let bytes = pawn_network().to_bytes();
+    assert_eq!(Network::from_bytes(&[]), Err(NnueError::UnexpectedEnd));
+    assert_eq!(
+        Network::from_bytes(&[2, 1, 0]),
+        Err(NnueError::UnsupportedVersion(2))
+    );
+    assert_eq!(
+        Network::from_bytes(&[1, 0, 0]),
+        Err(NnueError::EmptyHiddenLayer)
+    );
+    assert_eq!(
+        Network::from_bytes(&bytes[..bytes.len() - 1]),
+        Err(NnueError::UnexpectedEnd)
+    );
+    let mut longer = bytes;
+    longer.push(0);
+    assert_eq!(
+        Network::from_bytes(&longer),
+        Err(NnueError::TrailingBytes(1))
+    );
+}
@@ -0,0 +1,216 @@
+//! Node counts of the standard perft positions, see
+//! <https://www.chessprogramming.org/Perft_Results>.
+//!
+//! The deeper counts are slow and ignored by default, run them with
+//! `cargo test --release -- --ignored`.
+
+use rusty_chess_core::game::{Game, Move, MoveList, Position, STARTING_POSITION_FEN};
+
+const KIWIPETE_FEN: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+const POSITION_3_FEN: &str = "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1";
+const POSITION_4_FEN: &str = "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1";
+const POSITION_5_FEN: &str = "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8";
+const POSITION_6_FEN: &str =
+    "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10";
+
+fn perft(fen: &str, depth: u8) -> u64 {
+    Game::from_fen(fen).unwrap().perft(depth)
+}
+
+#[test]
+fn perft_starting_position() {
+    assert_eq!(Game::new().perft(0), 1);
+    assert_eq!(perft(STARTING_POSITION_FEN, 1), 20);
+    assert_eq!(perft(STARTING_POSITION_FEN, 2), 400);
+}
+
+#[test]
+#[ignore]
+fn perft_starting_position_deep() {
+    assert_eq!(perft(STARTING_POSITION_FEN, 3), 8_902);
+    assert_eq!(perft(STARTING_POSITION_FEN, 4), 197_281);
+}
+
+#[test]
+fn perft_kiwipete() {
+    assert_eq!(perft(KIWIPETE_FEN, 1), 48);
+    assert_eq!(perft(KIWIPETE_FEN, 2), 2_039);
+}
+
+#[test]
+#[ignore]
+fn perft_kiwipete_deep() {
+    assert_eq!(perft(KIWIPETE_FEN, 3), 97_862);
+}
+
+#[test]
+fn perft_position_3() {
+    assert_eq!(perft(POSITION_3_FEN, 1), 14);
+    assert_eq!(perft(POSITION_3_FEN, 2), 191);
+    assert_eq!(perft(POSITION_3_FEN, 3), 2_812);
+}
+
+#[test]
+#[ignore]
+fn perft_position_3_deep() {
+    assert_eq!(perft(POSITION_3_FEN, 4), 43_238);
+}
+
+#[test]
+fn perft_position_4() {
+    assert_eq!(perft(POSITION_4_FEN, 1), 6);
+    assert_eq!(perft(POSITION_4_FEN, 2), 264);
+}
+
+#[test]
+#[ignore]
+fn perft_position_4_deep() {
+    assert_eq!(perft(POSITION_4_FEN, 3), 9_467);
+}
+
+#[test]
+fn perft_position_5() {
+    assert_eq!(perft(POSITION_5_FEN, 1), 44);
+    assert_eq!(perft(POSITION_5_FEN, 2), 1_486);
+}
+
+#[test]
+#[ignore]
+fn perft_position_5_deep() {
+    assert_eq!(perft(POSITION_5_FEN, 3), 62_379);
+}
+
+#[test]
+fn perft_position_6() {
+    assert_eq!(perft(POSITION_6_FEN, 1), 46);
+    assert_eq!(perft(POSITION_6_FEN, 2), 2_079);
+}
+
+#[test]
+fn perft_divide_sums_up_to_perft() {
+    let game = Game::from_fen(POSITION_4_FEN).unwrap();
+    let divide = game.perft_divide(2);
+    assert_eq!(divide.len(), 6);
+    assert_eq!(divide.iter().map(|(_, nodes)| nodes).sum::<u64>(), 264);
+
+    // every promotion piece is a separate entry
+    let game = Game::from_fen(POSITION_5_FEN).unwrap();
+    let promotions: Vec<String> = game
+        .perft_divide(1)
+        .into_iter()
+        .filter(|(mv, _)| mv.promotion.is_some())
+        .map(|(mv, _)| mv.to_uci())
+        .collect();
+    assert_eq!(promotions, ["d7c8b", "d7c8n", "d7c8q", "d7c8r"]);
+}
+
+#[test]
+fn legal_move_iterators_match_move_lists() {
+    let game = Game::from_fen(KIWIPETE_FEN).unwrap();
+    let mut expected: Vec<String> = game
+        .get_all_currently_valid_moves()
+        .iter()
+        .map(|mv| mv.to_uci())
+        .collect();
+    expected.sort();
+    let mut iterated: Vec<String> = game.all_legal_moves_iter().map(|mv| mv.to_uci()).collect();
+    iterated.sort();
+    assert_eq!(iterated, expected);
+
+    let mut buffer = Vec::new();
+    game.legal_moves_into(&mut buffer);
+    game.legal_moves_into(&mut buffer);
+    assert_eq!(buffer.len(), 48);
+
+    let from = Position('e', '1');
+    assert_eq!(
+        game.legal_moves_iter(from).count(),
+        game.get_valid_moves(from).len()
+    );
+}
+
+#[test]
+fn move_list_buffers_match_move_lists() {
+    let game = Game::from_fen(KIWIPETE_FEN).unwrap();
+    let mut moves = MoveList::new();
+    game.legal_moves_to(&mut moves);
+    game.legal_moves_to(&mut moves);
+    let mut buffered: Vec<String> = moves.iter().map(|mv| mv.to_uci()).collect();
+    buffered.sort();
+    let mut expected: Vec<String> = game.legal_moves().iter().map(|mv| mv.to_uci()).collect();
+    expected.sort();
+    assert_eq!(buffered, expected);
+    assert!(!moves.spilled());
+
+    moves.clear();
+    game.pseudo_legal_moves_to(Position('e', '1'), &mut moves);
+    game.pseudo_legal_moves_to(Position('d', '2'), &mut moves);
+    let expected: Vec<Move> = game
+        .pseudo_legal_moves(Position('e', '1'))
+        .into_iter()
+        .chain(game.pseudo_legal_moves(Position('d', '2')))
+        .collect();
+    assert_eq!(Vec::from(moves), expected);
+}
+
+#[test]
+fn legalize_pseudo_legal_moves() {
+    // the rook gives check and the knight can not block on f1 as it is pinned
+    let game = Game::from_fen("4k3/8/8/8/1b6/8/3N4/4K2r w - - 0 1").unwrap();
+    let knight = Position('d', '2');
+    assert_eq!(game.pseudo_legal_moves(knight).len(), 6);
+    assert!(game.legalize(game.pseudo_legal_moves(knight)).is_empty());
+
+    let king_moves = game.pseudo_legal_moves(Position('e', '1'));
+    let mut legal: Vec<String> = king_moves
+        .iter()
+        .filter(|mv| game.is_legal(mv))
+        .map(|mv| mv.to_uci())
+        .collect();
+    legal.sort();
+    assert_eq!(legal, ["e1e2", "e1f2"]);
+    assert_eq!(game.legalize(king_moves).len(), 2);
+}
+
+#[test]
+fn capture_moves_are_legal_captures_and_promotions() {
+    for fen in [KIWIPETE_FEN, POSITION_4_FEN, POSITION_5_FEN] {
+        let game = Game::from_fen(fen).unwrap();
+        let mut expected: Vec<String> = game
+            .get_all_currently_valid_moves()
+            .iter()
+            .filter(|mv| mv.captured_piece.is_some() || mv.is_promotion())
+            .map(|mv| mv.to_uci())
+            .collect();
+        expected.sort();
+        let mut captures: Vec<String> = game
+            .capture_moves(game.turn)
+            .iter()
+            .map(|mv| mv.to_uci())
+            .collect();
+        captures.sort();
+        assert_eq!(captures, expected);
+    }
+}
+
+#[test]
+fn evasion_moves() {
+    let uci = |moves: Vec<rusty_chess_core::game::Move>| {
+        let mut moves: Vec<String> = moves.iter().map(|mv| mv.to_uci()).collect();
+        moves.sort();
+        moves
+    };
+    assert!(Game::new().evasion_moves().is_empty());
+    // the knight can block or the king can step aside
+    let game = Game::from_fen("4k3/8/8/8/1B6/8/3N4/r3K3 w - - 0 1").unwrap();
+    assert_eq!(
+        uci(game.evasion_moves()),
+        ["d2b1", "e1e2", "e1f2"]
+    );
+    // the checking pawn can be captured en passant
+    let game = Game::from_fen("8/8/8/2k5/3Pp3/8/8/4K3 b - d3 0 1").unwrap();
+    assert!(uci(game.evasion_moves()).contains(&"e4d3".to_string()));
+    // double check: only the king can move
+    let game = Game::from_fen("4k3/8/8/8/8/3n4/8/r3K2R w - - 0 1").unwrap();
+    assert_eq!(uci(game.evasion_moves()), ["e1d2", "e1e2"]);
+}
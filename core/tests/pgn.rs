@@ -0,0 +1,104 @@
+use rusty_chess_core::game::{
+    Annotation, Color, Game, GameStatus, GameTree, PgnError, Position, UserInput,
+};
+
+const ANNOTATED: &str = r#"[Event "Casual \"blitz\""]
+[Site "?"]
+
+1.e4 e5!? {Central} 2. Nf3 (2. f4 exf4 $6 (2... d5) 3. Nf3) 2... Nc6 $1 ; developing
+3. Bb5 a6 1-0"#;
+
+fn movetext(pgn: &str) -> &str {
+    pgn.split("\n\n").nth(1).unwrap().trim_end()
+}
+
+#[test]
+fn import_annotations() {
+    let game = Game::from_pgn(ANNOTATED).unwrap();
+    let sans: Vec<&str> = game.history().iter().map(|p| p.san.as_str()).collect();
+    assert_eq!(sans, ["e4", "e5", "Nf3", "Nc6", "Bb5", "a6"]);
+    assert_eq!(
+        game.history()[1].annotation,
+        Annotation {
+            comment: Some(String::from("Central")),
+            nags: vec![5],
+        }
+    );
+    assert_eq!(game.history()[3].annotation.comment.as_deref(), Some("developing"));
+    assert_eq!(game.status(), GameStatus::Resignation(Color::White));
+    assert_eq!(
+        movetext(&game.to_pgn()),
+        "1. e4 e5 $5 {Central} 2. Nf3 Nc6 $1 {developing} 3. Bb5 a6 1-0"
+    );
+}
+
+#[test]
+fn variations_round_trip() {
+    let tree = GameTree::from_pgn(ANNOTATED).unwrap();
+    let pgn = tree.to_pgn();
+    assert_eq!(
+        movetext(&pgn),
+        "1. e4 e5 $5 {Central} 2. Nf3 (2. f4 exf4 $6 (2... d5) 3. Nf3) 2... Nc6 $1\n\
+         {developing} 3. Bb5 a6 1-0"
+    );
+    assert_eq!(GameTree::from_pgn(&pgn).unwrap().to_pgn(), pgn);
+}
+
+#[test]
+fn export_annotations() {
+    let mut game = Game::new();
+    for (from, to) in [("e2", "e4"), ("c7", "c5")] {
+        let (from, to): (Position, Position) = (from.parse().unwrap(), to.parse().unwrap());
+        game.process_input(&UserInput::Move(from, to)).unwrap();
+    }
+    game.annotation_mut(0).unwrap().comment = Some(String::from("best by test"));
+    game.annotation_mut(1).unwrap().nags.push(1);
+    assert!(game.annotation_mut(2).is_none());
+    let pgn = game.to_pgn();
+    assert_eq!(movetext(&pgn), "1. e4 {best by test} 1... c5 $1 *");
+    assert_eq!(Game::from_pgn(&pgn).unwrap().history(), game.history());
+}
+
+#[test]
+fn invalid_pgn() {
+    assert_eq!(
+        Game::from_pgn("1. e4 e4").unwrap_err(),
+        PgnError::IllegalMove(String::from("e4"))
+    );
+    assert_eq!(
+        Game::from_pgn("1. e4 {open").unwrap_err(),
+        PgnError::UnterminatedComment
+    );
+    assert_eq!(
+        Game::from_pgn("1. e4 (1. d4").unwrap_err(),
+        PgnError::UnbalancedVariation
+    );
+    assert_eq!(
+        Game::from_pgn("(1. d4)").unwrap_err(),
+        PgnError::UnbalancedVariation
+    );
+    assert!(matches!(
+        Game::from_pgn("[Event \"?\"\n1. e4"),
+        Err(PgnError::InvalidTag(_))
+    ));
+    assert!(matches!(
+        Game::from_pgn("[Variant \"Atomic\"]\n1. e4"),
+        Err(PgnError::UnknownVariant(_))
+    ));
+}
+
+#[test]
+fn san_parsing() {
+    let game = Game::from_fen("r3k3/1P6/8/8/8/8/8/R3K2R w KQq - 0 1").unwrap();
+    for (san, uci) in [
+        ("O-O", "e1g1"),
+        ("0-0-0+", "e1c1"),
+        ("bxa8=N", "b7a8n"),
+        ("b8Q+", "b7b8q"),
+        ("Rb1", "a1b1"),
+    ] {
+        assert_eq!(game.move_from_san(san), game.move_from_uci(uci), "{san}");
+    }
+    assert_eq!(game.move_from_san("b8"), None);
+    assert_eq!(game.move_from_san("Rc8"), None);
+}
@@ -0,0 +1,65 @@
+use rusty_chess_core::engine::{Engine, SearchLimits};
+use rusty_chess_core::game::{BotRng, Game, PieceType, Position, SeedableRng, UserInput};
+use rusty_chess_core::player::{AttackingKing, EnginePlayer, Human, Player, RandomAggressive};
+
+fn pos(square: &str) -> Position {
+    let mut chars = square.chars();
+    Position::new(chars.next().unwrap(), chars.next().unwrap()).unwrap()
+}
+
+#[test]
+fn human_answers_with_the_callback() {
+    let mut human = Human::new(|_: &Game| UserInput::Move(pos("e2"), pos("e4")));
+    assert_eq!(
+        human.choose_move(&Game::new()),
+        UserInput::Move(pos("e2"), pos("e4"))
+    );
+}
+
+#[test]
+fn random_aggressive_captures() {
+    let game =
+        Game::from_fen("rnbqkbnr/ppp1pppp/8/3p4/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 2").unwrap();
+    let mut player = RandomAggressive::new(BotRng::seed_from_u64(3));
+    assert_eq!(
+        player.choose_move(&game),
+        UserInput::Move(pos("e4"), pos("d5"))
+    );
+}
+
+#[test]
+fn attacking_king_mates() {
+    let game = Game::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1").unwrap();
+    let mut player = AttackingKing::new(BotRng::seed_from_u64(3));
+    assert_eq!(
+        player.choose_move(&game),
+        UserInput::Move(pos("a1"), pos("a8"))
+    );
+}
+
+#[test]
+fn bots_promote_to_a_queen() {
+    let game = Game::from_fen("7k/P7/8/8/8/8/8/K7 w - - 0 1").unwrap();
+    let mut player = AttackingKing::new(BotRng::seed_from_u64(3));
+    assert_eq!(
+        player.choose_move(&game),
+        UserInput::MoveWithPromotion(pos("a7"), pos("a8"), PieceType::Queen)
+    );
+}
+
+#[test]
+fn bots_resign_without_moves() {
+    let game = Game::from_fen("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1").unwrap();
+    let mut player = RandomAggressive::new(BotRng::seed_from_u64(3));
+    assert_eq!(player.choose_move(&game), UserInput::Resign);
+}
+
+#[test]
+fn engine_plays_its_best_move() {
+    let game = Game::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1").unwrap();
+    let mut player = EnginePlayer::new(Engine::new(), SearchLimits::depth(2));
+    assert_eq!(
+        player.choose_move(&game),
+        UserInput::Move(pos("a1"), pos("a8"))
+    );
+}
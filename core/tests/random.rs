@@ -0,0 +1,29 @@
+use rusty_chess_core::game::{BotRng, Game, PieceType, SeedableRng, UserInput};
+
+fn play_random_game(seed: u64, plies: usize) -> Vec<String> {
+    let mut game = Game::new();
+    let mut rng = BotRng::seed_from_u64(seed);
+    let mut moves = Vec::new();
+    for _ in 0..plies {
+        let Some(mv) = game.random_move(&mut rng) else {
+            break;
+        };
+        assert!(game.legal_moves().contains(&mv));
+        let input = if mv.is_promotion() {
+            UserInput::MoveWithPromotion(mv.from, mv.to, PieceType::Queen)
+        } else {
+            UserInput::Move(mv.from, mv.to)
+        };
+        if game.process_input(&input).is_err() {
+            break;
+        }
+        moves.push(mv.to_uci());
+    }
+    moves
+}
+
+#[test]
+fn same_seed_plays_same_moves() {
+    assert_eq!(play_random_game(7, 40), play_random_game(7, 40));
+    assert_ne!(play_random_game(7, 40), play_random_game(8, 40));
+}
@@ -0,0 +1,40 @@
+#![cfg(feature = "render")]
+
+use rusty_chess_core::game::{Game, Position, SvgRenderer, Theme, UserInput};
+
+#[test]
+fn svg() {
+    let mut game = Game::new();
+    game.process_input(&UserInput::Move(Position('e', '2'), Position('e', '4')))
+        .unwrap();
+    let svg = SvgRenderer::new().size(200).render(&game);
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"200\""));
+    assert!(svg.ends_with("</svg>"));
+    assert_eq!(svg.matches("<rect").count(), 64 + 2);
+    assert_eq!(svg.matches("\u{265F}</text>").count(), 16);
+    assert!(svg.contains(&Theme::brown().light_square));
+
+    let plain = SvgRenderer::new()
+        .last_move(false)
+        .coordinates(false)
+        .theme(Theme::green())
+        .render(&game);
+    assert_eq!(plain.matches("<rect").count(), 64);
+    assert_eq!(plain.matches("<text").count(), 32);
+    assert!(plain.contains(&Theme::green().dark_square));
+
+    // the white king is drawn at the top when flipped
+    let flipped = SvgRenderer::new().flipped(true).render(&Game::new());
+    let king = flipped.split("<text x=\"157\" y=\"37\"").nth(1).unwrap();
+    let king = king.split("</text>").next().unwrap();
+    assert!(king.contains("fill=\"#ffffff\""));
+    assert!(king.ends_with('\u{265A}'));
+}
+
+#[cfg(feature = "png")]
+#[test]
+fn png() {
+    let png = SvgRenderer::new().size(64).render_png(&Game::new()).unwrap();
+    assert!(png.starts_with(b"\x89PNG"));
+    assert!(SvgRenderer::new().size(0).render_png(&Game::new()).is_err());
+}
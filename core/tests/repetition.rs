@@ -0,0 +1,201 @@
+use rusty_chess_core::game::{
+    DrawReason, Game, GameStatus, MoveError, PieceType, Position, UserInput,
+};
+
+fn play(game: &mut Game, moves: &[&str]) -> GameStatus {
+    let mut status = game.status();
+    for mv in moves {
+        let mut squares = mv.chars();
+        let mut square = || Position(squares.next().unwrap(), squares.next().unwrap());
+        let (from, to) = (square(), square());
+        status = game
+            .process_input(&UserInput::Move(from, to))
+            .unwrap_or_else(|e| panic!("{mv} is not valid: {e}"));
+    }
+    status
+}
+
+const KNIGHT_SHUFFLE: [&str; 4] = ["g1f3", "g8f6", "f3g1", "f6g8"];
+
+#[test]
+fn threefold_repetition() {
+    let mut game = Game::new();
+    play(&mut game, &KNIGHT_SHUFFLE);
+    assert_eq!(game.claimable_draw(), None);
+    // the starting position occurs for the third time
+    play(&mut game, &KNIGHT_SHUFFLE);
+    assert_eq!(game.claimable_draw(), Some(DrawReason::ThreefoldRepetition));
+}
+
+#[test]
+fn repetition_of_other_position_than_the_first() {
+    let mut game = Game::new();
+    play(&mut game, &["e2e4", "e7e5"]);
+    play(&mut game, &KNIGHT_SHUFFLE);
+    play(&mut game, &KNIGHT_SHUFFLE[..3]);
+    assert_eq!(game.claimable_draw(), None);
+    play(&mut game, &KNIGHT_SHUFFLE[3..]);
+    assert_eq!(game.claimable_draw(), Some(DrawReason::ThreefoldRepetition));
+}
+
+#[test]
+fn repetition_needs_identical_castling_rights() {
+    let mut game = Game::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+    let rook_shuffle = ["a1b1", "a8b8", "b1a1", "b8a8"];
+    play(&mut game, &rook_shuffle);
+    // same pieces as in the first position, but without the long castling rights
+    play(&mut game, &rook_shuffle);
+    assert_eq!(game.claimable_draw(), None);
+    play(&mut game, &rook_shuffle);
+    assert_eq!(game.claimable_draw(), Some(DrawReason::ThreefoldRepetition));
+}
+
+#[test]
+fn repetition_needs_identical_en_passant_rights() {
+    let mut game = Game::from_fen("4k3/8/8/8/3p4/8/4P3/4K3 w - - 0 1").unwrap();
+    // black could capture en passant after the double step
+    play(&mut game, &["e2e4"]);
+    let king_shuffle = ["e8d8", "e1d1", "d8e8", "d1e1"];
+    play(&mut game, &king_shuffle);
+    play(&mut game, &king_shuffle);
+    assert_eq!(game.claimable_draw(), None);
+    play(&mut game, &king_shuffle);
+    assert_eq!(game.claimable_draw(), Some(DrawReason::ThreefoldRepetition));
+}
+
+#[test]
+fn en_passant_square_without_capture_does_not_matter() {
+    let mut game = Game::from_fen("4k3/8/8/8/8/8/4P3/4K3 w - - 0 1").unwrap();
+    play(&mut game, &["e2e4"]);
+    let king_shuffle = ["e8d8", "e1d1", "d8e8", "d1e1"];
+    play(&mut game, &king_shuffle);
+    play(&mut game, &king_shuffle);
+    assert_eq!(game.claimable_draw(), Some(DrawReason::ThreefoldRepetition));
+}
+
+#[test]
+fn undo_forgets_repetition() {
+    let mut game = Game::new();
+    play(&mut game, &KNIGHT_SHUFFLE);
+    play(&mut game, &KNIGHT_SHUFFLE);
+    game.undo();
+    assert_eq!(game.claimable_draw(), None);
+    play(&mut game, &KNIGHT_SHUFFLE[3..]);
+    assert_eq!(game.claimable_draw(), Some(DrawReason::ThreefoldRepetition));
+    game.undo();
+    game.undo();
+    game.undo();
+    play(&mut game, &["g8f6", "f3g1"]);
+    assert_eq!(game.claimable_draw(), None);
+}
+
+#[test]
+fn claim_threefold_repetition() {
+    let mut game = Game::new();
+    play(&mut game, &KNIGHT_SHUFFLE);
+    assert_eq!(
+        game.process_input(&UserInput::ClaimDraw),
+        Err(MoveError::NoDrawToClaim)
+    );
+    assert_eq!(play(&mut game, &KNIGHT_SHUFFLE), GameStatus::Ongoing);
+    assert_eq!(
+        game.process_input(&UserInput::ClaimDraw),
+        Ok(GameStatus::Draw(DrawReason::ThreefoldRepetition))
+    );
+    assert_eq!(
+        game.status(),
+        GameStatus::Draw(DrawReason::ThreefoldRepetition)
+    );
+}
+
+#[test]
+fn fivefold_repetition_ends_the_game() {
+    let mut game = Game::new();
+    for _ in 0..3 {
+        play(&mut game, &KNIGHT_SHUFFLE);
+    }
+    assert_eq!(play(&mut game, &KNIGHT_SHUFFLE[..3]), GameStatus::Ongoing);
+    assert_eq!(
+        play(&mut game, &KNIGHT_SHUFFLE[3..]),
+        GameStatus::Draw(DrawReason::FivefoldRepetition)
+    );
+}
+
+#[test]
+fn fifty_and_seventy_five_move_rules() {
+    let mut game = Game::from_fen("4k3/8/8/8/8/8/4P3/R3K3 w - - 99 80").unwrap();
+    assert_eq!(game.claimable_draw(), None);
+    play(&mut game, &["a1a2"]);
+    assert_eq!(game.claimable_draw(), Some(DrawReason::FiftyMoveRule));
+
+    let mut game = Game::from_fen("4k3/8/8/8/8/8/4P3/R3K3 w - - 149 80").unwrap();
+    assert_eq!(
+        play(&mut game, &["a1a2"]),
+        GameStatus::Draw(DrawReason::SeventyFiveMoveRule)
+    );
+    // a pawn move resets the count
+    let mut game = Game::from_fen("4k3/8/8/8/8/8/4P3/R3K3 w - - 149 80").unwrap();
+    assert_eq!(play(&mut game, &["e2e3"]), GameStatus::Ongoing);
+}
+
+#[test]
+fn position_keys_of_transpositions() {
+    let mut game = Game::new();
+    play(&mut game, &["g1f3", "g8f6", "b1c3"]);
+    let mut transposed = Game::new();
+    play(&mut transposed, &["b1c3", "g8f6", "g1f3"]);
+    assert_eq!(game.position_key(), transposed.position_key());
+    assert_eq!(game.zobrist_key(), transposed.zobrist_key());
+
+    // an en passant square nobody can capture on does not matter
+    let mut game = Game::new();
+    play(&mut game, &["e2e4"]);
+    let mut transposed =
+        Game::from_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1").unwrap();
+    assert_eq!(game.position_key(), transposed.position_key());
+    play(&mut transposed, &["g8f6"]);
+    assert_ne!(game.position_key(), transposed.position_key());
+}
+
+#[test]
+fn hash_follows_moves_and_undo() {
+    let mut game = Game::new();
+    let start = game.hash();
+    // castling, en passant and a promotion change the hash of several squares
+    play(
+        &mut game,
+        &["e2e4", "d7d5", "e4e5", "f7f5", "e5f6", "g8h6", "f6g7", "e8f7", "g1f3", "d8d6"],
+    );
+    game.process_input(&UserInput::MoveWithPromotion(
+        Position('g', '7'),
+        Position('h', '8'),
+        PieceType::Queen,
+    ))
+    .unwrap();
+    play(&mut game, &["b8c6", "f1e2", "c8d7", "e1g1"]);
+    let fen = Game::from_fen(&game.to_fen()).unwrap();
+    assert_eq!(game.hash(), fen.hash());
+
+    while game.undo().is_some() {}
+    assert_eq!(game.hash(), start);
+}
+
+#[test]
+fn snapshot_forgets_how_the_position_was_reached() {
+    let mut game = Game::new();
+    play(&mut game, &["e2e4", "e7e5"]);
+    play(&mut game, &KNIGHT_SHUFFLE);
+    let mut snapshot = game.snapshot();
+    assert!(snapshot.history().is_empty());
+    assert!(!snapshot.can_undo());
+    assert_eq!(snapshot.to_fen(), game.to_fen());
+    assert_eq!(snapshot.legal_moves(), game.legal_moves());
+
+    // the repetitions before the snapshot do not count
+    play(&mut game, &KNIGHT_SHUFFLE);
+    play(&mut snapshot, &KNIGHT_SHUFFLE);
+    assert_eq!(game.claimable_draw(), Some(DrawReason::ThreefoldRepetition));
+    assert_eq!(snapshot.claimable_draw(), None);
+    // replays start from the snapshot
+    assert!(snapshot.to_pgn().contains("[FEN "));
+}
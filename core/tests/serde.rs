@@ -0,0 +1,89 @@
+#![cfg(feature = "serde")]
+
+use rusty_chess_core::game::{
+    Color, Game, GameStatus, LoadError, Move, Position, UserInput, Variant, SAVE_FORMAT_VERSION,
+};
+
+#[test]
+fn game_round_trip() {
+    let mut game = Game::new();
+    for uci in ["e2e4", "d7d5", "e4d5", "g8f6"] {
+        let mv = game.move_from_uci(uci).unwrap();
+        game.process_input(&UserInput::Move(mv.from, mv.to)).unwrap();
+    }
+    game.undo();
+
+    let json = serde_json::to_string(&game).unwrap();
+    let mut loaded: Game = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded.to_fen(), game.to_fen());
+    assert_eq!(loaded.history(), game.history());
+    assert_eq!(loaded.status(), game.status());
+    assert_eq!(
+        loaded.get_all_currently_valid_moves(),
+        game.get_all_currently_valid_moves()
+    );
+
+    loaded.redo();
+    game.redo();
+    assert_eq!(loaded.to_fen(), game.to_fen());
+    while loaded.undo().is_some() {}
+    assert_eq!(loaded.to_fen(), Game::new().to_fen());
+}
+
+#[test]
+fn positions_are_algebraic() {
+    assert_eq!(serde_json::to_string(&Position('e', '4')).unwrap(), "\"e4\"");
+    assert!(serde_json::from_str::<Position>("\"i9\"").is_err());
+
+    let mv = Game::new().move_from_uci("g1f3").unwrap();
+    let json = serde_json::to_string(&mv).unwrap();
+    assert_eq!(serde_json::from_str::<Move>(&json).unwrap(), mv);
+
+    let status = GameStatus::AwaitingPromotion(Position('a', '8'));
+    let json = serde_json::to_string(&status).unwrap();
+    assert_eq!(serde_json::from_str::<GameStatus>(&json).unwrap(), status);
+}
+
+#[test]
+fn board_needs_all_squares() {
+    let mut json = serde_json::to_value(Game::new()).unwrap();
+    json["board"].as_array_mut().unwrap().pop();
+    assert!(serde_json::from_value::<Game>(json).is_err());
+}
+
+#[test]
+fn save_game_round_trip() {
+    let mut game = Game::from_fen("4k3/1P6/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    let mv = game.move_from_uci("b7b8q").unwrap();
+    game.process_input(&UserInput::MoveWithPromotion(mv.from, mv.to, mv.promotion.unwrap()))
+        .unwrap();
+    game.process_input(&UserInput::Resign).unwrap();
+
+    let loaded = Game::load_json(&game.save_json()).unwrap();
+    assert_eq!(loaded.to_fen(), game.to_fen());
+    assert_eq!(loaded.history(), game.history());
+    assert_eq!(loaded.status(), GameStatus::Resignation(Color::White));
+
+    let antichess = Game::with_variant(Variant::Antichess);
+    let loaded = Game::load_json(&antichess.save_json()).unwrap();
+    assert_eq!(loaded.variant(), Variant::Antichess);
+    assert_eq!(loaded.to_fen(), antichess.to_fen());
+}
+
+#[test]
+fn save_game_versions() {
+    let json = r#"{"version":1,"moves":["e2e4","e7e5"],"comment":"from the future"}"#;
+    let game = Game::load_json(json).unwrap();
+    assert_eq!(game.history().len(), 2);
+
+    let newer = format!(r#"{{"version":{}}}"#, SAVE_FORMAT_VERSION + 1);
+    assert_eq!(
+        Game::load_json(&newer).unwrap_err(),
+        LoadError::UnsupportedVersion(SAVE_FORMAT_VERSION + 1)
+    );
+    assert_eq!(
+        Game::load_json(r#"{"version":1,"moves":["e2e4","e2e4"]}"#).unwrap_err(),
+        LoadError::InvalidMove(1, "e2e4".to_string())
+    );
+    assert!(matches!(Game::load_json("{}"), Err(LoadError::InvalidJson(_))));
+}
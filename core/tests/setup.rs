@@ -0,0 +1,192 @@
+use rusty_chess_core::game::{
+    BoardBuilder, Color, Game, Piece, PieceType, Position, SetupError, UserInput, Variant,
+};
+
+fn kings() -> BoardBuilder {
+    BoardBuilder::new()
+        .piece(Position('e', '1'), Piece::new(PieceType::King, Color::White))
+        .piece(Position('e', '8'), Piece::new(PieceType::King, Color::Black))
+}
+
+#[test]
+fn builds_position() {
+    let game = kings()
+        .piece(Position('d', '5'), Piece::new(PieceType::Pawn, Color::Black))
+        .piece(Position('e', '5'), Piece::new(PieceType::Pawn, Color::White))
+        .turn(Color::White)
+        .en_passant_square(Some(Position('d', '6')))
+        .halfmove_clock(0)
+        .fullmove_number(12)
+        .build()
+        .unwrap();
+    assert_eq!(game.to_fen(), "4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 12");
+}
+
+#[test]
+fn needs_one_king_per_side() {
+    assert_eq!(
+        BoardBuilder::new()
+            .piece(Position('e', '1'), Piece::new(PieceType::King, Color::White))
+            .build()
+            .unwrap_err(),
+        SetupError::WrongNumberOfKings(Color::Black, 0)
+    );
+    assert_eq!(
+        kings()
+            .piece(Position('a', '1'), Piece::new(PieceType::King, Color::White))
+            .build()
+            .unwrap_err(),
+        SetupError::WrongNumberOfKings(Color::White, 2)
+    );
+    assert!(BoardBuilder::new()
+        .piece(Position('a', '2'), Piece::new(PieceType::Pawn, Color::White))
+        .variant(Variant::Antichess)
+        .build()
+        .is_ok());
+}
+
+#[test]
+fn rejects_invalid_positions() {
+    let pawn = Piece::new(PieceType::Pawn, Color::White);
+    assert_eq!(
+        kings().piece(Position('a', '8'), pawn).build().unwrap_err(),
+        SetupError::PawnOnBackRank(Position('a', '8'))
+    );
+    assert_eq!(
+        kings().piece(Position('i', '2'), pawn).build().unwrap_err(),
+        SetupError::InvalidSquare(Position('i', '2'))
+    );
+    assert_eq!(
+        kings().castling(Color::Black, true, false).build().unwrap_err(),
+        SetupError::InvalidCastlingRights(Color::Black)
+    );
+    assert_eq!(
+        kings()
+            .en_passant_square(Some(Position('d', '6')))
+            .build()
+            .unwrap_err(),
+        SetupError::InvalidEnPassantSquare(Position('d', '6'))
+    );
+    // black is in check but white is to move
+    assert_eq!(
+        kings()
+            .piece(Position('e', '4'), Piece::new(PieceType::Rook, Color::White))
+            .build()
+            .unwrap_err(),
+        SetupError::OpponentInCheck
+    );
+}
+
+#[test]
+fn validate_lists_all_problems() {
+    let game = Game::from_fen("4k2P/8/8/8/4R3/8/8/4r3 w - - 0 1").unwrap();
+    assert_eq!(
+        game.validate(),
+        vec![
+            SetupError::WrongNumberOfKings(Color::White, 0),
+            SetupError::PawnOnBackRank(Position('h', '8')),
+            SetupError::OpponentInCheck,
+        ]
+    );
+    let game = Game::from_fen("4k3/8/8/8/8/8/4R3/r3K3 w - - 0 1").unwrap();
+    assert_eq!(game.validate(), vec![SetupError::BothKingsInCheck]);
+    assert!(Game::new().validate().is_empty());
+}
+
+#[test]
+fn castling_rights() {
+    let mut game = Game::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w Kq - 0 1").unwrap();
+    assert!(game.can_castle_short(Color::White));
+    assert!(!game.can_castle_long(Color::White));
+    assert!(!game.can_castle_short(Color::Black));
+    assert!(game.can_castle_long(Color::Black));
+
+    game.set_castling_rights(Color::White, true, true).unwrap();
+    assert_eq!(game.to_fen(), "r3k2r/8/8/8/8/8/8/R3K2R w KQq - 0 1");
+    assert!(game.validate().is_empty());
+
+    let mut game = Game::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 0 1").unwrap();
+    assert_eq!(
+        game.set_castling_rights(Color::White, true, false),
+        Err(SetupError::InvalidCastlingRights(Color::White))
+    );
+    assert!(!game.can_castle_short(Color::White));
+}
+
+#[test]
+fn en_passant_square() {
+    let mut game = Game::new();
+    assert_eq!(game.en_passant_square(), None);
+    game.process_input(&UserInput::Move(Position('e', '2'), Position('e', '4')))
+        .unwrap();
+    assert_eq!(game.en_passant_square(), Some(Position('e', '3')));
+    game.process_input(&UserInput::Move(Position('g', '8'), Position('f', '6')))
+        .unwrap();
+    assert_eq!(game.en_passant_square(), None);
+}
+
+#[test]
+fn move_counters() {
+    let mut game = Game::from_fen("4k3/8/8/8/8/8/4P3/R3K3 b - - 120 40").unwrap();
+    assert_eq!(game.halfmove_clock(), 120);
+    assert_eq!(game.fullmove_number(), 40);
+    game.process_input(&UserInput::Move(Position('e', '8'), Position('d', '8')))
+        .unwrap();
+    assert_eq!((game.halfmove_clock(), game.fullmove_number()), (121, 41));
+    game.process_input(&UserInput::Move(Position('e', '2'), Position('e', '4')))
+        .unwrap();
+    assert_eq!((game.halfmove_clock(), game.fullmove_number()), (0, 41));
+}
+
+#[test]
+fn history() {
+    let mut game = Game::from_fen("4k3/P7/8/8/8/8/8/4K3 w - - 3 20").unwrap();
+    game.process_input(&UserInput::Move(Position('e', '1'), Position('d', '2')))
+        .unwrap();
+    game.process_input(&UserInput::Move(Position('e', '8'), Position('d', '7')))
+        .unwrap();
+    game.process_input(&UserInput::MoveWithPromotion(
+        Position('a', '7'),
+        Position('a', '8'),
+        PieceType::Queen,
+    ))
+    .unwrap();
+    let history = game.history();
+    let sans: Vec<&str> = history.iter().map(|played| played.san.as_str()).collect();
+    assert_eq!(sans, ["Kd2", "Kd7", "a8=Q"]);
+    assert_eq!(history[0].halfmove_clock, 4);
+    assert_eq!(history[1].fullmove_number, 21);
+    assert_eq!(history[2].mv.promotion, Some(PieceType::Queen));
+    assert_eq!(history[2].key, game.zobrist_key());
+    assert_eq!(history[2].halfmove_clock, 0);
+}
+
+#[test]
+fn replay() {
+    let mut game = Game::new();
+    for (from, to) in [("e2", "e4"), ("e7", "e5"), ("g1", "f3")] {
+        let (from, to) = (from.parse().unwrap(), to.parse().unwrap());
+        game.process_input(&UserInput::Move(from, to)).unwrap();
+    }
+    let mut replay = game.replay();
+    assert_eq!(replay.len(), 3);
+    assert_eq!(replay.board(), &Game::new().board);
+    assert!(!replay.back());
+    assert!(replay.forward());
+    assert_eq!(replay.turn(), Color::Black);
+    assert_eq!(replay.last_move().unwrap().san, "e4");
+    assert!(!replay.goto(4));
+    assert!(replay.goto(3));
+    assert_eq!(replay.board(), &game.board);
+    assert!(!replay.forward());
+
+    let fen = game.to_fen();
+    assert!(game.goto_ply(1));
+    assert_eq!(
+        game.to_fen(),
+        "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1"
+    );
+    assert!(game.goto_ply(3));
+    assert_eq!(game.to_fen(), fen);
+    assert!(!game.goto_ply(4));
+}
@@ -0,0 +1,21 @@
+#![cfg(feature = "stats")]
+
+use rusty_chess_core::game::{Game, Stats};
+
+// the counters are global, so there is only one test that resets them
+#[test]
+fn counts_the_work_of_a_perft() {
+    let game = Game::new();
+    Stats::reset();
+    assert_eq!(game.perft(2), 400);
+    let stats = Stats::get();
+    // the start position and the 20 positions after the first move
+    assert_eq!(stats.positions_generated, 21);
+    assert_eq!(stats.clones, 20);
+    assert!(stats.moves_generated >= 400);
+    assert_eq!(stats.allocations, 0);
+    assert!(stats.move_generation_time > std::time::Duration::ZERO);
+
+    Stats::reset();
+    assert_eq!(Stats::get(), Stats::default());
+}
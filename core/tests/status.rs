@@ -0,0 +1,186 @@
+use rusty_chess_core::game::{
+    BoardFormatter, Color, Game, MoveError, ParsePositionError, Piece, PieceType, Position,
+    UserInput,
+};
+
+#[test]
+fn predicates() {
+    let mut game = Game::new();
+    for (from, to) in [("f2", "f3"), ("e7", "e5"), ("g2", "g4")] {
+        assert!(!game.is_check());
+        let mut squares = from.chars().chain(to.chars());
+        let mut square = || Position(squares.next().unwrap(), squares.next().unwrap());
+        let (from, to) = (square(), square());
+        game.process_input(&UserInput::Move(from, to)).unwrap();
+    }
+    game.process_input(&UserInput::Move(Position('d', '8'), Position('h', '4')))
+        .unwrap();
+    assert!(game.is_check());
+    assert!(game.is_checkmate());
+    assert!(!game.is_stalemate());
+    assert!(!game.is_draw());
+
+    let game = Game::from_fen("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1").unwrap();
+    assert!(!game.is_check());
+    assert!(game.is_stalemate());
+    assert!(game.is_draw());
+    assert!(Game::from_fen("8/8/8/8/8/8/8/K6k w - - 0 1").unwrap().is_draw());
+}
+
+#[test]
+fn valid_moves_only_for_the_side_to_move() {
+    let game = Game::new();
+    assert_eq!(game.get_valid_moves_for_turn(Position('g', '1')).len(), 2);
+    assert!(game.get_valid_moves_for_turn(Position('g', '8')).is_empty());
+    assert_eq!(game.get_valid_moves(Position('g', '8')).len(), 2);
+    assert!(game.get_valid_moves_for_turn(Position('e', '4')).is_empty());
+}
+
+#[test]
+fn legal_moves_follow_the_position() {
+    let mut game = Game::new();
+    assert_eq!(game.legal_moves().len(), 20);
+    assert_eq!(game.get_valid_moves(Position('e', '2')).len(), 2);
+    game.process_input(&UserInput::Move(Position('e', '2'), Position('e', '4')))
+        .unwrap();
+    assert_eq!(game.legal_moves().len(), 20);
+    assert!(game.get_valid_moves_for_turn(Position('e', '4')).is_empty());
+    assert_eq!(game.get_valid_moves(Position('f', '1')).len(), 5);
+    game.process_input(&UserInput::Move(Position('e', '7'), Position('e', '5')))
+        .unwrap();
+    assert_eq!(game.legal_moves().len(), 29);
+    game.undo();
+    assert_eq!(game.legal_moves(), game.get_all_currently_valid_moves());
+    assert!(game.legal_moves().iter().all(|mv| mv.piece.color == Color::Black));
+}
+
+#[test]
+fn move_classification() {
+    let game = Game::from_fen("r3k3/1P6/8/3pP3/8/8/8/R3K2R w KQq d6 0 1").unwrap();
+    let find = |from: Position, to: Position| {
+        game.get_valid_moves(from)
+            .into_iter()
+            .find(|mv| mv.to == to)
+            .unwrap()
+    };
+    let en_passant = find(Position('e', '5'), Position('d', '6'));
+    assert!(en_passant.is_en_passant() && en_passant.is_capture());
+    let castle = find(Position('e', '1'), Position('c', '1'));
+    assert!(castle.is_castle() && !castle.is_capture());
+    assert!(!find(Position('e', '1'), Position('d', '1')).is_castle());
+    let promotion = find(Position('b', '7'), Position('a', '8'));
+    assert!(promotion.is_promotion() && promotion.is_capture());
+    // the new queen on a8 checks the king
+    assert!(promotion.gives_check(&game));
+    assert!(find(Position('h', '1'), Position('h', '8')).gives_check(&game));
+    assert!(!find(Position('h', '1'), Position('h', '7')).gives_check(&game));
+}
+
+#[test]
+fn material() {
+    let game = Game::new();
+    let white = game.material(Color::White);
+    assert_eq!((white.pawns, white.knights, white.queens), (8, 2, 1));
+    assert_eq!(white.value(), 8 + 2 * 3 + 2 * 3 + 2 * 5 + 8);
+    assert_eq!(game.material_balance(), 0);
+    let game = Game::from_fen("4k3/pp6/8/8/8/8/8/3QK3 b - - 0 1").unwrap();
+    assert_eq!(game.material_balance(), 8 - 2);
+    assert_eq!(game.material(Color::Black).pawns, 2);
+}
+
+#[test]
+fn mobility() {
+    let game = Game::new();
+    assert_eq!(game.mobility(Color::White), 20);
+    assert_eq!(game.mobility(Color::Black), 20);
+    assert_eq!(game.piece_mobility(Position('b', '1')), 2);
+    assert_eq!(game.piece_mobility(Position('a', '1')), 0);
+    assert_eq!(game.piece_mobility(Position('e', '4')), 0);
+}
+
+#[test]
+fn pieces() {
+    let game = Game::from_fen("4k3/8/8/8/8/8/3P4/4K3 w - - 0 1").unwrap();
+    let pieces: Vec<(Position, Piece)> = game.pieces().collect();
+    assert_eq!(
+        pieces,
+        [
+            (Position('e', '1'), Piece::new(PieceType::King, Color::White)),
+            (Position('d', '2'), Piece::new(PieceType::Pawn, Color::White)),
+            (Position('e', '8'), Piece::new(PieceType::King, Color::Black)),
+        ]
+    );
+    assert_eq!(game.pieces_of(Color::White).count(), 2);
+    assert_eq!(Game::new().pieces_of(Color::Black).count(), 16);
+}
+
+#[test]
+fn position_parsing() {
+    assert_eq!("e4".parse(), Ok(Position('e', '4')));
+    assert_eq!(Position('h', '8').to_string(), "h8");
+    for invalid in ["", "e", "e9", "i1", "E4", "e44"] {
+        assert_eq!(
+            invalid.parse::<Position>(),
+            Err(ParsePositionError(invalid.to_string()))
+        );
+    }
+    assert_eq!(Position::new('a', '1'), Some(Position('a', '1')));
+    assert_eq!(Position::new('a', '0'), None);
+}
+
+#[test]
+fn move_descriptions() {
+    let game = Game::from_fen("r3k3/1P6/8/3pP3/8/8/8/R3K1N1 w Qq d6 0 1").unwrap();
+    for (uci, description) in [
+        ("g1f3", "Knight from g1 to f3"),
+        ("e5d6", "Pawn from e5 to d6, capturing a pawn en passant"),
+        ("b7a8q", "Pawn from b7 to a8, capturing a rook, promoting to a queen, check"),
+        ("e1c1", "Castling queenside"),
+        ("a1a8", "Rook from a1 to a8, capturing a rook, check"),
+    ] {
+        let mv = game.move_from_uci(uci).unwrap();
+        assert_eq!(mv.describe(&game), description);
+    }
+    let game = Game::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1").unwrap();
+    let mv = game.move_from_uci("a1a8").unwrap();
+    assert_eq!(mv.describe(&game), "Rook from a1 to a8, checkmate");
+}
+
+#[test]
+fn board_formatting() {
+    let mut game = Game::new();
+    game.process_input(&UserInput::Move(Position('g', '1'), Position('f', '3')))
+        .unwrap();
+    assert_eq!(
+        game.to_string(),
+        BoardFormatter::new().format(&game),
+        "Display uses the default formatter"
+    );
+    let board = BoardFormatter::new()
+        .unicode(false)
+        .flipped(true)
+        .last_move(true)
+        .format(&game);
+    let lines: Vec<&str> = board.lines().collect();
+    assert_eq!(lines[1], "1 | R |  *| B | K | Q | B | N | R | ");
+    assert_eq!(lines[5], "3 |   |   | N*|   |   |   |   |   | ");
+    assert_eq!(lines[15], "8 | r | n | b | k | q | b | n | r | ");
+    assert_eq!(lines[17], "    h   g   f   e   d   c   b   a   ");
+    let board = BoardFormatter::new().coordinates(false).format(&game);
+    assert_eq!(board.lines().count(), 17);
+    assert!(board.lines().all(|line| line.starts_with('|') || line.starts_with('-')));
+}
+
+#[test]
+fn null_move() {
+    let mut game = Game::from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1").unwrap();
+    game.make_null_move().unwrap();
+    assert_eq!(game.turn, Color::Black);
+    assert_eq!(game.to_fen(), "4k3/8/8/3pP3/8/8/8/4K3 b - - 1 1");
+    assert!(game.history().is_empty());
+    game.make_null_move().unwrap();
+    assert_eq!(game.to_fen(), "4k3/8/8/3pP3/8/8/8/4K3 w - - 2 2");
+
+    let mut game = Game::from_fen("4k3/8/8/8/8/8/8/4K2r w - - 0 1").unwrap();
+    assert_eq!(game.make_null_move(), Err(MoveError::LeavesKingInCheck));
+}
@@ -0,0 +1,137 @@
+use core::time::Duration;
+use rusty_chess_core::engine::{Engine, MatchScore, SearchLimits};
+use rusty_chess_core::game::{BotRng, Color, Game, GameStatus, SeedableRng, UserInput};
+use rusty_chess_core::player::{AttackingKing, EnginePlayer, Human, RandomAggressive};
+use rusty_chess_core::tournament::{Crosstable, Sprt, SprtDecision, TimeControl, Tournament};
+
+#[test]
+fn round_robin() {
+    let openings = vec![
+        Game::from_pgn("1. e4 e5").unwrap(),
+        Game::from_pgn("1. d4 d5").unwrap(),
+    ];
+    let mut finished = 0;
+    let result = Tournament::new()
+        .player(
+            "Engine",
+            EnginePlayer::new(Engine::new(), SearchLimits::depth(1)),
+        )
+        .player(
+            "Attacking king",
+            AttackingKing::new(BotRng::seed_from_u64(1)),
+        )
+        .player("Random", RandomAggressive::new(BotRng::seed_from_u64(2)))
+        .openings(openings)
+        .max_plies(30)
+        .event("Test")
+        .run(|_| finished += 1);
+    // three pairs, two openings, both colors
+    assert_eq!(finished, 12);
+    assert_eq!(result.games.len(), 12);
+    assert!(result
+        .games
+        .iter()
+        .all(|played| played.game.status().is_finished() && played.round == 1));
+
+    let table = result.crosstable();
+    for player in 0..3 {
+        assert_eq!(table.half_points[player][player], 0);
+        for opponent in 0..3 {
+            if opponent != player {
+                assert_eq!(
+                    table.half_points[player][opponent] + table.half_points[opponent][player],
+                    8
+                );
+            }
+        }
+    }
+    let total: f64 = (0..3).map(|player| table.points(player)).sum();
+    assert_eq!(total, 12.0);
+
+    let pgn = result.to_pgn();
+    assert_eq!(pgn.matches("[Event \"Test\"]").count(), 12);
+    assert_eq!(pgn.matches("[White \"Engine\"]").count(), 4);
+    assert_eq!(pgn.matches("1. d4 d5").count(), 6);
+    assert!(!pgn.contains("[Result \"*\"]"));
+}
+
+#[test]
+fn slow_players_lose_on_time() {
+    let slow = Human::new(|game: &Game| {
+        std::thread::sleep(Duration::from_millis(30));
+        let mv = game.legal_moves()[0];
+        UserInput::Move(mv.from, mv.to)
+    });
+    let result = Tournament::new()
+        .player("Slow", slow)
+        .player("Random", RandomAggressive::new(BotRng::seed_from_u64(1)))
+        .time_control(TimeControl::new(Duration::from_millis(50), Duration::ZERO))
+        .run(|_| {});
+    for played in &result.games {
+        let winner = if played.white == 1 {
+            Color::White
+        } else {
+            Color::Black
+        };
+        assert_eq!(played.game.status(), GameStatus::Timeout(winner));
+    }
+    assert_eq!(result.crosstable().points(1), 2.0);
+}
+
+#[test]
+fn crosstable_table() {
+    let table = Crosstable {
+        names: vec![
+            String::from("Attacking king"),
+            String::from("Engine"),
+            String::from("Random"),
+        ],
+        half_points: vec![vec![0, 0, 3], vec![4, 0, 4], vec![1, 0, 0]],
+    };
+    assert_eq!(table.ranking(), [1, 0, 2]);
+    assert_eq!(
+        table.to_string(),
+        "\
+#  Player             1     2     3  Points
+1  Engine             *   2.0   2.0     4.0
+2  Attacking king   0.0     *   1.5     1.5
+3  Random           0.0   0.5     *     0.5
+"
+    );
+}
+
+#[test]
+fn sprt() {
+    let sprt = Sprt::new(0.0, 10.0);
+    let (lower, upper) = sprt.bounds();
+    assert!((lower + 2.944).abs() < 1e-3 && (upper - 2.944).abs() < 1e-3);
+    assert_eq!(sprt.llr(&MatchScore::default()), 0.0);
+    let even = MatchScore {
+        wins: 500,
+        draws: 800,
+        losses: 500,
+    };
+    assert!(sprt.llr(&even) < 0.0);
+    assert_eq!(sprt.decision(&even), None);
+    let worse = MatchScore {
+        wins: 400,
+        draws: 800,
+        losses: 600,
+    };
+    assert_eq!(sprt.decision(&worse), Some(SprtDecision::Fail));
+
+    // the engine beats a random mover in a few games
+    let mut llrs = Vec::new();
+    let report = Tournament::new()
+        .player(
+            "Engine",
+            EnginePlayer::new(Engine::new(), SearchLimits::depth(1)),
+        )
+        .player("Random", RandomAggressive::new(BotRng::seed_from_u64(1)))
+        .rounds(50)
+        .run_sprt(&Sprt::new(0.0, 200.0), |_, llr| llrs.push(llr));
+    assert_eq!(report.decision, Some(SprtDecision::Pass));
+    assert!(report.llr >= upper);
+    assert_eq!(llrs.len(), report.result.games.len());
+    assert!(report.score.games() < 100);
+}
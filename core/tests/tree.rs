@@ -0,0 +1,59 @@
+use rusty_chess_core::game::{Game, GameTree, MoveError, NodeId};
+
+fn add(tree: &mut GameTree, parent: NodeId, uci: &str) -> NodeId {
+    let mv = tree.game(parent).unwrap().move_from_uci(uci).unwrap();
+    tree.add_move(parent, &mv).unwrap()
+}
+
+fn sans(tree: &GameTree, nodes: impl Iterator<Item = NodeId>) -> Vec<String> {
+    nodes
+        .filter_map(|id| tree.played_move(id).map(|played| played.san.clone()))
+        .collect()
+}
+
+#[test]
+fn variations() {
+    let mut tree = GameTree::new(Game::new());
+    let root = tree.root();
+    let e4 = add(&mut tree, root, "e2e4");
+    let e5 = add(&mut tree, e4, "e7e5");
+    let c5 = add(&mut tree, e4, "c7c5");
+    let nf3 = add(&mut tree, c5, "g1f3");
+    let d4 = add(&mut tree, root, "d2d4");
+
+    assert_eq!(add(&mut tree, root, "e2e4"), e4);
+    assert_eq!(sans(&tree, tree.mainline()), ["e4", "e5"]);
+    assert_eq!(tree.variations(root), [d4]);
+    assert_eq!(tree.parent(nf3), Some(c5));
+    assert!(tree.is_mainline(e5));
+    assert!(!tree.is_mainline(nf3));
+
+    tree.promote_to_mainline(nf3);
+    assert_eq!(sans(&tree, tree.mainline()), ["e4", "c5", "Nf3"]);
+    assert_eq!(tree.variations(e4), [e5]);
+    assert!(!tree.promote(c5));
+
+    assert!(tree.delete(c5));
+    assert_eq!(sans(&tree, tree.mainline()), ["e4", "e5"]);
+    assert!(tree.game(nf3).is_none());
+    assert!(!tree.delete(root));
+}
+
+#[test]
+fn invalid_moves() {
+    let mut tree = GameTree::new(Game::from_fen("4k3/1P6/8/8/8/8/8/4K3 w - - 0 1").unwrap());
+    let root = tree.root();
+    let mut mv = tree.game(root).unwrap().move_from_uci("b7b8q").unwrap();
+    mv.promotion = None;
+    assert_eq!(
+        tree.add_move(root, &mv),
+        Err(MoveError::PromotionRequired(mv.to))
+    );
+    let king_move = tree.game(root).unwrap().move_from_uci("e1e2").unwrap();
+    let e2 = tree.add_move(root, &king_move).unwrap();
+    assert_eq!(
+        tree.add_move(e2, &king_move),
+        Err(MoveError::NoPieceAtSquare(king_move.from))
+    );
+    assert!(tree.children(e2).is_empty());
+}
@@ -1,18 +1,26 @@
-use rand::Rng;
 use raylib::prelude::*;
+use rusty_chess_core::bots;
+use rusty_chess_core::engine::{Engine, EngineHandle, SearchLimits, SkillLevel};
+use rusty_chess_core::game::BotRng;
 use rusty_chess_core::game::Color as ChessColor;
 use rusty_chess_core::game::Game;
+use rusty_chess_core::game::GameStatus;
+use rusty_chess_core::game::MoveError;
 use rusty_chess_core::game::Piece;
 use rusty_chess_core::game::PieceType;
+use rusty_chess_core::game::Position;
+use rusty_chess_core::game::SeedableRng;
 use rusty_chess_core::game::UserInput;
-use rusty_chess_core::game::UserOutput;
 use rusty_chess_core::game::BOARD_SIZE;
 use std::path::Path;
 use std::thread::available_parallelism;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 const WINDOW_SIZE: i32 = 640;
 const RECT_SIZE: i32 = WINDOW_SIZE / BOARD_SIZE as i32;
 const TITLE: &str = "Rusty Chess";
+/// Time the engine thinks about its moves.
+const ENGINE_MOVETIME: Duration = Duration::from_secs(1);
 
 const CRATE_PATH: &str = env!("CARGO_MANIFEST_DIR");
 
@@ -31,6 +39,14 @@ const QUEEN_W: &str = "queen-w.png";
 const ROOK_B: &str = "rook-b.png";
 const ROOK_W: &str = "rook-w.png";
 
+/// Who plays Black against the player, switched with the B key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Opponent {
+    Engine,
+    /// The attacking king bot, which plays at once.
+    Bot,
+}
+
 struct Assets {
     bishop_b: Texture2D,
     bishop_w: Texture2D,
@@ -144,57 +160,37 @@ fn draw_board(d: &mut RaylibDrawHandle) {
     }
 }
 
-#[allow(dead_code)]
-fn play_attacking_king(game: &mut Game) -> Option<UserOutput> {
-    let possible_moves = game.get_all_currently_valid_moves();
-    if possible_moves.is_empty() {
-        eprintln!(
-            "Something went wrong. No possible moves found. Function was probably called after check mate or stale mate."
-        );
-        return Some(UserOutput::InvalidMove);
-    }
-
-    let move_to_play = possible_moves
+/// Plays a move and promotes to a queen if a pawn reaches the last rank.
+fn play_move(game: &mut Game, from: Position, to: Position) -> Result<GameStatus, MoveError> {
+    let promotion = game
+        .get_valid_moves(from)
         .iter()
-        .find(|mv| {
-            let mut game = game.clone();
-            match game.process_input(&UserInput::Move(mv.from, mv.to)) {
-                Some(UserOutput::CheckMate) => true,
-                _ => game.check(game.turn.invert()),
-            }
-        })
-        .unwrap_or_else(
-            || match possible_moves.iter().find(|mv| mv.captured_piece.is_some()) {
-                Some(mv) => mv,
-                None => {
-                    let rng = &mut rand::thread_rng();
-                    let random_index = rng.gen_range(0..possible_moves.len());
-                    &possible_moves[random_index]
-                }
-            },
-        );
-
-    game.process_input(&UserInput::Move(move_to_play.from, move_to_play.to))
+        .any(|mv| mv.to == to && mv.is_promotion());
+    if promotion {
+        game.process_input(&UserInput::MoveWithPromotion(from, to, PieceType::Queen))
+    } else {
+        game.process_input(&UserInput::Move(from, to))
+    }
 }
 
-#[allow(dead_code)]
-fn play_randomly_aggressive(game: &mut Game) -> Option<UserOutput> {
-    let possible_moves = game.get_all_currently_valid_moves();
-    if possible_moves.is_empty() {
-        panic!(
-            "Something went wrong. No possible moves found. Function was probably called after check mate or stale mate."
-        );
+/// Starts the engine on the position and plays its move once it is done. The
+/// search runs on its own thread, so the board is drawn in the meantime.
+fn play_engine_move(
+    game: &mut Game,
+    engine: &Engine,
+    search: &mut Option<EngineHandle>,
+) -> Option<GameStatus> {
+    let handle = search
+        .get_or_insert_with(|| engine.start_search(game, SearchLimits::movetime(ENGINE_MOVETIME)));
+    if !handle.is_finished() {
+        return None;
     }
-    let move_to_play = match possible_moves.iter().find(|mv| mv.captured_piece.is_some()) {
-        Some(mv) => mv,
-        None => {
-            let rng = &mut rand::thread_rng();
-            let random_index = rng.gen_range(0..possible_moves.len());
-            &possible_moves[random_index]
-        }
+    let mv = search.take()?.wait().best_move?;
+    let input = match mv.promotion {
+        Some(piece_type) => UserInput::MoveWithPromotion(mv.from, mv.to, piece_type),
+        None => UserInput::Move(mv.from, mv.to),
     };
-
-    game.process_input(&UserInput::Move(move_to_play.from, move_to_play.to))
+    game.process_input(&input).ok()
 }
 
 #[inline]
@@ -221,12 +217,25 @@ fn draw_pieces(
     selected_piece: Option<&SelectedPiece>,
     d: &mut RaylibDrawHandle,
 ) {
+    if game.is_check() {
+        let king = Piece::new(PieceType::King, game.turn);
+        if let Some((pos, _)) = game.pieces().find(|(_, piece)| *piece == king) {
+            let (x, y) = game_index_to_coord(pos.as_index());
+            d.draw_rectangle(
+                x,
+                y,
+                RECT_SIZE,
+                RECT_SIZE,
+                Color::from_hex("FF0000").unwrap().alpha(0.5),
+            );
+        }
+    }
     if let Some(selected_piece) = selected_piece {
         let pos = selected_piece
             .game_index
             .try_into()
             .expect("Invalid game index");
-        let possible_moves = game.get_valid_moves(pos);
+        let possible_moves = game.get_valid_moves_for_turn(pos);
         for mv in possible_moves {
             let (x, y) = game_index_to_coord(mv.to.as_index());
             let color = if mv.captured_piece.is_some() {
@@ -278,20 +287,20 @@ fn draw_pieces(
 fn draw(
     game: &Game,
     assets: &Assets,
-    user_output: Option<&UserOutput>,
+    status: GameStatus,
+    opponent_text: &str,
     selected_piece: Option<&SelectedPiece>,
     rl: &mut RaylibHandle,
     thread: &RaylibThread,
 ) {
-    let mut text = "";
-    if let Some(user_output) = user_output {
-        text = match user_output {
-            UserOutput::CheckMate => "Checkmate!",
-            UserOutput::StaleMate => "Stalemate!",
-            UserOutput::Draw => "Draw!",
-            UserOutput::InvalidMove => "Invalid move!",
-            UserOutput::Promotion(_) => "Promotion!",
-        };
+    let text = match status {
+        GameStatus::Checkmate(_) => "Checkmate!",
+        GameStatus::Resignation(_) => "Resigned!",
+        GameStatus::Timeout(_) => "Time is up!",
+        GameStatus::VariantWin(_) => "Won!",
+        GameStatus::Stalemate => "Stalemate!",
+        GameStatus::Draw(_) => "Draw!",
+        _ => "",
     };
     let font_size = 60;
     let text_x = WINDOW_SIZE / 2 - rl.measure_text(text, font_size) / 2;
@@ -305,21 +314,37 @@ fn draw(
     d.clear_background(Color::WHITE);
     draw_board(&mut d);
     draw_pieces(game, assets, selected_piece, &mut d);
+    d.draw_text(opponent_text, 4, 4, 20, Color::DARKGRAY);
     if !text.is_empty() {
         d.draw_text(text, text_x, text_y, font_size, Color::RED);
         d.draw_text(text2, text2_x, text2_y, font_size, Color::RED);
     }
 }
 
+/// The opponent in the top left corner with the keys to change it.
+fn opponent_text(opponent: Opponent, skill: SkillLevel) -> String {
+    match opponent {
+        Opponent::Engine => format!("Level {} (Up/Down), B for the bot", skill.level()),
+        Opponent::Bot => String::from("Bot, B for the engine"),
+    }
+}
+
 fn update_game(
     game: &mut Game,
     selected_piece: &mut Option<SelectedPiece>,
     rl: &mut RaylibHandle,
-) -> Option<UserOutput> {
+    opponent: Opponent,
+    engine: &Engine,
+    search: &mut Option<EngineHandle>,
+    rng: &mut BotRng,
+) -> Option<GameStatus> {
     if game.turn == ChessColor::White {
         update_selected_piece(game, selected_piece, rl)
     } else {
-        play_attacking_king(game)
+        match opponent {
+            Opponent::Engine => play_engine_move(game, engine, search),
+            Opponent::Bot => bots::play_attacking_king(game, rng),
+        }
     }
 }
 
@@ -327,8 +352,8 @@ fn update_selected_piece(
     game: &mut Game,
     selected_piece: &mut Option<SelectedPiece>,
     rl: &mut RaylibHandle,
-) -> Option<UserOutput> {
-    let mut user_output = None;
+) -> Option<GameStatus> {
+    let mut status = None;
     if rl.is_mouse_button_down(MouseButton::MOUSE_BUTTON_LEFT) {
         let mouse_pos = rl.get_mouse_position();
         let x = mouse_pos.x as i32;
@@ -360,13 +385,12 @@ fn update_selected_piece(
         if let Ok(to) = coord_to_game_index(x, y).try_into() {
             let game_index = selected_piece.as_ref().unwrap().game_index;
             let from = game_index.try_into().expect("Invalid game index");
-            let user_input = UserInput::Move(from, to);
-            match game.process_input(&user_input) {
-                Some(UserOutput::InvalidMove) => {
-                    println!("Invalid move");
+            match play_move(game, from, to) {
+                Err(e) => {
+                    println!("Invalid move: {e}");
                 }
-                o => {
-                    user_output = o;
+                Ok(s) => {
+                    status = Some(s);
                 }
             }
         }
@@ -374,7 +398,7 @@ fn update_selected_piece(
     } else {
         *selected_piece = None;
     }
-    user_output
+    status
 }
 
 fn main() {
@@ -394,28 +418,79 @@ fn main() {
     let assets = Assets::new(&mut rl, &thread);
 
     let mut game = Game::new();
+    let mut opponent = Opponent::Engine;
+    let mut skill = SkillLevel::MAX;
+    // the same seed makes the bot and the weaker engine levels play the same
+    // moves again
+    let seed = std::env::var("RUSTY_CHESS_SEED")
+        .ok()
+        .and_then(|seed| seed.parse().ok())
+        .unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |time| time.as_secs())
+        });
+    println!("Bot seed: {seed}");
+    let mut rng = BotRng::seed_from_u64(seed);
+    // the search of the engine while it is its turn
+    let mut search = None;
 
     rl.set_target_fps(60);
     rl.show_cursor();
-    let mut finished = false;
-    let mut user_output = None;
+    let mut status = GameStatus::Ongoing;
     let mut selected_piece = None;
     while !rl.window_should_close() {
         if rl.is_key_pressed(KeyboardKey::KEY_R) {
+            search = None;
             game = Game::new();
-            finished = false;
-            user_output = None;
+            status = GameStatus::Ongoing;
+        }
+        if rl.is_key_pressed(KeyboardKey::KEY_B) {
+            search = None;
+            opponent = match opponent {
+                Opponent::Engine => Opponent::Bot,
+                Opponent::Bot => Opponent::Engine,
+            };
+        }
+        if rl.is_key_pressed(KeyboardKey::KEY_UP) {
+            skill = SkillLevel::new(skill.level() + 1);
+        }
+        if rl.is_key_pressed(KeyboardKey::KEY_DOWN) {
+            skill = SkillLevel::new(skill.level().saturating_sub(1));
+        }
+        if rl.is_key_pressed(KeyboardKey::KEY_C) {
+            if let Ok(new_status) = game.process_input(&UserInput::ClaimDraw) {
+                status = new_status;
+            }
+        }
+        if rl.is_key_pressed(KeyboardKey::KEY_U) {
+            // take back the bot's answer as well, so that it is the player's turn again
+            search = None;
+            game.undo();
+            if game.turn != ChessColor::White {
+                game.undo();
+            }
+            status = game.status();
+            selected_piece = None;
         }
-        if !finished {
-            user_output = update_game(&mut game, &mut selected_piece, &mut rl);
-            if user_output.is_some() {
-                finished = true;
+        if !status.is_finished() {
+            if let Some(new_status) = update_game(
+                &mut game,
+                &mut selected_piece,
+                &mut rl,
+                opponent,
+                &Engine::new().skill_level(skill).seed(seed),
+                &mut search,
+                &mut rng,
+            ) {
+                status = new_status;
             }
         }
         draw(
             &game,
             &assets,
-            user_output.as_ref(),
+            status,
+            &opponent_text(opponent, skill),
             selected_piece.as_ref(),
             &mut rl,
             &thread,
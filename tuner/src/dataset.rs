@@ -0,0 +1,78 @@
+use rusty_chess_core::game::{Game, GameResult};
+
+/// A quiet position with the result of its game for White: 1 for a win, 0.5
+/// for a draw and 0 for a loss.
+pub struct Sample {
+    pub game: Game,
+    pub result: f64,
+}
+
+/// Positions of EPD lines labeled with the result, either as `c9 "1-0"`
+/// opcode or as `[1.0]`, `[0.5]` and `[0.0]` after the position. Lines that
+/// can not be read are skipped.
+pub fn from_epd(text: &str) -> Vec<Sample> {
+    text.lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 5 {
+                return None;
+            }
+            let (position, operations) = fields.split_at(4);
+            let label = operations
+                .iter()
+                .position(|field| *field == "c9")
+                .and_then(|index| operations.get(index + 1))
+                .or_else(|| operations.iter().find(|field| field.starts_with('[')))?;
+            let result = parse_result(label)?;
+            let game = Game::from_fen(&position.join(" ")).ok()?;
+            is_quiet(&game).then_some(Sample { game, result })
+        })
+        .collect()
+}
+
+/// The quiet positions of the games of a PGN file after the first
+/// `skip_plies` plies, labeled with the result of their game. Unfinished
+/// games and games that can not be read are skipped.
+pub fn from_pgn(text: &str, skip_plies: usize) -> Vec<Sample> {
+    Game::from_pgn_games(text)
+        .into_iter()
+        .filter_map(Result::ok)
+        .flat_map(|game| {
+            let result = match game.result() {
+                Some(GameResult::WhiteWins) => 1.0,
+                Some(GameResult::BlackWins) => 0.0,
+                Some(GameResult::Draw) => 0.5,
+                None => return Vec::new(),
+            };
+            let mut position = game;
+            position.goto_ply(0);
+            let mut samples = Vec::new();
+            let mut ply = 0;
+            while position.redo().is_some() {
+                ply += 1;
+                if ply > skip_plies && is_quiet(&position) {
+                    samples.push(Sample {
+                        game: position.clone(),
+                        result,
+                    });
+                }
+            }
+            samples
+        })
+        .collect()
+}
+
+/// The static evaluation is only meaningful if nothing can be taken and the
+/// side to move is not in check.
+fn is_quiet(game: &Game) -> bool {
+    !game.status().is_finished() && !game.is_check() && game.capture_moves(game.turn).is_empty()
+}
+
+fn parse_result(label: &str) -> Option<f64> {
+    match label.trim_matches(|c| matches!(c, '"' | '[' | ']' | ';')) {
+        "1-0" | "1.0" | "1" => Some(1.0),
+        "0-1" | "0.0" | "0" => Some(0.0),
+        "1/2-1/2" | "0.5" => Some(0.5),
+        _ => None,
+    }
+}
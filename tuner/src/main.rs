@@ -0,0 +1,63 @@
+mod dataset;
+mod texel;
+
+use std::process::exit;
+
+/// Opening moves are mostly book moves and say little about the result.
+const DEFAULT_SKIP_PLIES: usize = 16;
+const DEFAULT_PASSES: usize = 100;
+
+fn main() {
+    let usage = || {
+        eprintln!("Usage: rusty-chess-tuner [--passes N] [--skip-plies N] FILE.epd|FILE.pgn...");
+        exit(1)
+    };
+    let mut passes = DEFAULT_PASSES;
+    let mut skip_plies = DEFAULT_SKIP_PLIES;
+    let mut files = Vec::new();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--passes" => {
+                let Some(value) = args.next().and_then(|value| value.parse().ok()) else {
+                    usage()
+                };
+                passes = value;
+            }
+            "--skip-plies" => {
+                let Some(value) = args.next().and_then(|value| value.parse().ok()) else {
+                    usage()
+                };
+                skip_plies = value;
+            }
+            _ if arg.starts_with("--") => usage(),
+            _ => files.push(arg),
+        }
+    }
+    if files.is_empty() {
+        usage();
+    }
+
+    let mut samples = Vec::new();
+    for file in &files {
+        let text = std::fs::read_to_string(file).unwrap_or_else(|err| {
+            eprintln!("Could not read \"{file}\": {err}");
+            exit(1)
+        });
+        if file.ends_with(".pgn") {
+            samples.extend(dataset::from_pgn(&text, skip_plies));
+        } else {
+            samples.extend(dataset::from_epd(&text));
+        }
+    }
+    if samples.is_empty() {
+        eprintln!("No quiet labeled positions found.");
+        exit(1)
+    }
+    eprintln!("{} quiet positions", samples.len());
+
+    let k = texel::fit_k(&samples);
+    eprintln!("K = {k:.3}");
+    let params = texel::tune(&samples, k, passes);
+    println!("{params:#?}");
+}
@@ -0,0 +1,69 @@
+use crate::dataset::Sample;
+use rayon::prelude::*;
+use rusty_chess_core::eval::{evaluate_with, EvalParams};
+
+/// Expected result for White of a position evaluated at `score` centipawns.
+fn win_probability(score: i32, k: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf(-k * f64::from(score) / 400.0))
+}
+
+/// Mean squared difference between the results of the samples and the ones
+/// their evaluation with `params` predicts.
+pub fn mean_error(samples: &[Sample], params: &EvalParams, k: f64) -> f64 {
+    let sum: f64 = samples
+        .par_iter()
+        .map(|sample| {
+            let error = sample.result - win_probability(evaluate_with(&sample.game, params), k);
+            error * error
+        })
+        .sum();
+    sum / samples.len() as f64
+}
+
+/// The scaling constant of the scores that fits the samples best with the
+/// default weights, so that tuning changes the weights and not the scale.
+pub fn fit_k(samples: &[Sample]) -> f64 {
+    let params = EvalParams::DEFAULT;
+    let mut best = 1.0;
+    for step in [0.1, 0.01, 0.001] {
+        let start = best;
+        best = (-10..=10)
+            .map(|offset| f64::from(offset).mul_add(step, start))
+            .filter(|k| *k > 0.0)
+            .map(|k| (k, mean_error(samples, &params, k)))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map_or(start, |(k, _)| k);
+    }
+    best
+}
+
+/// Texel tuning: every weight is moved by one centipawn up or down as long as
+/// it lowers the error, for at most `passes` passes over all weights.
+pub fn tune(samples: &[Sample], k: f64, passes: usize) -> EvalParams {
+    let mut values = EvalParams::DEFAULT.to_vec();
+    let error = |values: &[i32]| {
+        let params = EvalParams::from_slice(values).expect("Tuning keeps the number of weights.");
+        mean_error(samples, &params, k)
+    };
+    let mut best_error = error(&values);
+    for pass in 1..=passes {
+        let mut improved = false;
+        for index in 0..values.len() {
+            for delta in [1, -1] {
+                values[index] += delta;
+                let new_error = error(&values);
+                if new_error < best_error {
+                    best_error = new_error;
+                    improved = true;
+                    break;
+                }
+                values[index] -= delta;
+            }
+        }
+        eprintln!("Pass {pass}: error {best_error:.6}");
+        if !improved {
+            break;
+        }
+    }
+    EvalParams::from_slice(&values).expect("Tuning keeps the number of weights.")
+}
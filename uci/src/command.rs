@@ -0,0 +1,250 @@
+use rusty_chess_core::engine::SearchLimits;
+use rusty_chess_core::game::{Game, UserInput};
+use std::time::Duration;
+
+/// A command of the GUI, unknown commands are ignored as the protocol asks.
+pub enum Command {
+    Uci,
+    IsReady,
+    UciNewGame,
+    SetOption {
+        name: String,
+        value: String,
+    },
+    /// The position, or only its base position if `illegal_move` could not be
+    /// played.
+    Position {
+        game: Box<Game>,
+        illegal_move: Option<String>,
+    },
+    Go(Go),
+    Stop,
+    Quit,
+}
+
+/// Limits of a `go` command.
+#[derive(Default)]
+pub struct Go {
+    pub limits: SearchLimits,
+    /// Search until `stop`, also if the limits are reached before.
+    pub infinite: bool,
+}
+
+/// Parses a line of the GUI, `Err` for known commands with invalid arguments.
+pub fn parse(line: &str) -> Result<Option<Command>, String> {
+    let mut tokens = line.split_whitespace();
+    let command = match tokens.next() {
+        Some("uci") => Command::Uci,
+        Some("isready") => Command::IsReady,
+        Some("ucinewgame") => Command::UciNewGame,
+        Some("setoption") => parse_option(tokens)?,
+        Some("position") => parse_position(tokens)?,
+        Some("go") => Command::Go(parse_go(tokens)?),
+        Some("stop") => Command::Stop,
+        Some("quit") => Command::Quit,
+        _ => return Ok(None),
+    };
+    Ok(Some(command))
+}
+
+/// `name <name> value <value>`, both may contain spaces.
+fn parse_option<'a>(tokens: impl Iterator<Item = &'a str>) -> Result<Command, String> {
+    let mut name = Vec::new();
+    let mut value = Vec::new();
+    let mut in_value = false;
+    for token in tokens {
+        match token {
+            "name" if !in_value && name.is_empty() => {}
+            "value" if !in_value => in_value = true,
+            _ if in_value => value.push(token),
+            _ => name.push(token),
+        }
+    }
+    if name.is_empty() {
+        return Err(String::from("setoption needs a name"));
+    }
+    Ok(Command::SetOption {
+        name: name.join(" "),
+        value: value.join(" "),
+    })
+}
+
+/// `startpos` or `fen <fen>`, followed by `moves` and the moves in UCI notation.
+fn parse_position<'a>(mut tokens: impl Iterator<Item = &'a str>) -> Result<Command, String> {
+    let base = match tokens.next() {
+        Some("startpos") => {
+            let moves = tokens.next();
+            if moves.is_some_and(|token| token != "moves") {
+                return Err(format!("expected moves, got {}", moves.unwrap_or_default()));
+            }
+            Game::new()
+        }
+        Some("fen") => {
+            let fen: Vec<&str> = tokens
+                .by_ref()
+                .take_while(|token| *token != "moves")
+                .collect();
+            Game::from_fen(&fen.join(" ")).map_err(|e| e.to_string())?
+        }
+        _ => return Err(String::from("position needs startpos or fen")),
+    };
+    let mut game = base.clone();
+    for uci in tokens {
+        let played = game.move_from_uci(uci).is_some_and(|mv| {
+            let input = match mv.promotion {
+                Some(piece_type) => UserInput::MoveWithPromotion(mv.from, mv.to, piece_type),
+                None => UserInput::Move(mv.from, mv.to),
+            };
+            game.process_input(&input).is_ok()
+        });
+        if !played {
+            return Ok(Command::Position {
+                game: Box::new(base),
+                illegal_move: Some(uci.to_string()),
+            });
+        }
+    }
+    Ok(Command::Position {
+        game: Box::new(game),
+        illegal_move: None,
+    })
+}
+
+fn parse_go<'a>(mut tokens: impl Iterator<Item = &'a str>) -> Result<Go, String> {
+    let mut go = Go::default();
+    while let Some(token) = tokens.next() {
+        if token == "infinite" {
+            go.infinite = true;
+            continue;
+        }
+        // the rest of the parameters have a number
+        let number = |value: Option<&str>| -> Result<u64, String> {
+            value
+                .and_then(|value| value.parse().ok())
+                .ok_or_else(|| format!("{token} needs a number"))
+        };
+        // some GUIs send a negative time once it ran out
+        let millis = |value: Option<&str>| -> Result<Duration, String> {
+            value
+                .and_then(|value| value.parse::<i64>().ok())
+                .map(|millis| Duration::from_millis(millis.max(0).unsigned_abs()))
+                .ok_or_else(|| format!("{token} needs a number"))
+        };
+        match token {
+            "depth" => {
+                go.limits.depth = Some(u8::try_from(number(tokens.next())?).unwrap_or(u8::MAX));
+            }
+            "nodes" => go.limits.nodes = Some(number(tokens.next())?),
+            "movetime" => go.limits.movetime = Some(millis(tokens.next())?),
+            "wtime" => go.limits.wtime = Some(millis(tokens.next())?),
+            "btime" => go.limits.btime = Some(millis(tokens.next())?),
+            "winc" => go.limits.winc = Some(millis(tokens.next())?),
+            "binc" => go.limits.binc = Some(millis(tokens.next())?),
+            "movestogo" => {
+                go.limits.moves_to_go =
+                    Some(u32::try_from(number(tokens.next())?).unwrap_or(u32::MAX));
+            }
+            // ponder, searchmoves and mate are not supported
+            _ => {}
+        }
+    }
+    Ok(go)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The FEN of a `position` command and its illegal move.
+    fn position(line: &str) -> (String, Option<String>) {
+        match parse(line) {
+            Ok(Some(Command::Position { game, illegal_move })) => (game.to_fen(), illegal_move),
+            _ => panic!("{line} is not a position"),
+        }
+    }
+
+    fn parse_go_line(line: &str) -> Go {
+        match parse(line) {
+            Ok(Some(Command::Go(go))) => go,
+            _ => panic!("{line} is not a go command"),
+        }
+    }
+
+    #[test]
+    fn startpos_with_moves() {
+        assert_eq!(position("position startpos"), (Game::new().to_fen(), None));
+        assert_eq!(
+            position("position startpos moves e2e4 e7e5 g1f3"),
+            (
+                String::from("rnbqkbnr/pppp1ppp/8/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2"),
+                None
+            )
+        );
+    }
+
+    #[test]
+    fn fen_with_moves() {
+        assert_eq!(
+            position("position fen 8/4P1k1/8/8/8/8/8/4K3 w - - 0 1 moves e7e8q g7h6"),
+            (String::from("4Q3/8/7k/8/8/8/8/4K3 w - - 1 2"), None)
+        );
+        assert_eq!(
+            position("position fen 4k3/8/8/8/8/8/8/4K3 b - - 0 1"),
+            (String::from("4k3/8/8/8/8/8/8/4K3 b - - 0 1"), None)
+        );
+    }
+
+    #[test]
+    fn illegal_moves_give_the_base_position() {
+        assert_eq!(
+            position("position startpos moves e2e4 e2e4 e7e5"),
+            (Game::new().to_fen(), Some(String::from("e2e4")))
+        );
+        assert_eq!(
+            position("position fen 4k3/8/8/8/8/8/4P3/4K3 w - - 0 1 moves e1d1 zz"),
+            (
+                String::from("4k3/8/8/8/8/8/4P3/4K3 w - - 0 1"),
+                Some(String::from("zz"))
+            )
+        );
+    }
+
+    #[test]
+    fn go_limits() {
+        let go =
+            parse_go_line("go wtime 60000 btime -20 winc 1000 binc 500 movestogo 12 depth 300");
+        assert_eq!(go.limits.wtime, Some(Duration::from_secs(60)));
+        // a negative time is none left
+        assert_eq!(go.limits.btime, Some(Duration::ZERO));
+        assert_eq!(go.limits.winc, Some(Duration::from_secs(1)));
+        assert_eq!(go.limits.binc, Some(Duration::from_millis(500)));
+        assert_eq!(go.limits.moves_to_go, Some(12));
+        assert_eq!(go.limits.depth, Some(u8::MAX));
+        assert!(!go.infinite);
+
+        let go = parse_go_line("go infinite");
+        assert!(go.infinite);
+        assert_eq!(go.limits, SearchLimits::default());
+        let go = parse_go_line("go movetime 250 nodes 1000 ponder");
+        assert_eq!(go.limits.movetime, Some(Duration::from_millis(250)));
+        assert_eq!(go.limits.nodes, Some(1000));
+    }
+
+    #[test]
+    fn malformed_input() {
+        for line in [
+            "position",
+            "position startpos e2e4",
+            "position fen not a fen",
+            "go depth",
+            "go wtime soon",
+            "go movestogo -1",
+            "setoption value 1",
+        ] {
+            assert!(parse(line).is_err(), "{line}");
+        }
+        for line in ["", "   ", "debug on", "ponderhit"] {
+            assert!(matches!(parse(line), Ok(None)), "{line}");
+        }
+    }
+}
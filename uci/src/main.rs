@@ -0,0 +1,269 @@
+mod command;
+
+use command::{Command, Go};
+use rusty_chess_core::engine::{Engine, EngineHandle, SearchInfo, SearchResult, SkillLevel};
+#[cfg(feature = "nnue")]
+use rusty_chess_core::eval::Network;
+use rusty_chess_core::game::Game;
+use std::io::{self, BufRead};
+use std::sync::mpsc::{self, RecvTimeoutError};
+#[cfg(feature = "nnue")]
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+/// How often a running search is checked for its end while waiting for input.
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+const MAX_MULTI_PV: usize = 16;
+const MAX_CONTEMPT: i32 = 1_000;
+
+/// The options the GUI can set with `setoption`.
+struct Options {
+    multi_pv: usize,
+    skill_level: u8,
+    contempt: i32,
+    #[cfg(feature = "nnue")]
+    network: Option<Arc<Network>>,
+}
+
+impl Options {
+    fn print() {
+        println!("option name MultiPV type spin default 1 min 1 max {MAX_MULTI_PV}");
+        println!(
+            "option name Skill Level type spin default {} min {} max {}",
+            SkillLevel::MAX.level(),
+            SkillLevel::MIN.level(),
+            SkillLevel::MAX.level()
+        );
+        println!("option name Contempt type spin default 0 min -{MAX_CONTEMPT} max {MAX_CONTEMPT}");
+        #[cfg(feature = "nnue")]
+        println!("option name EvalFile type string default <empty>");
+    }
+
+    fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+        let number = || {
+            value
+                .parse::<i64>()
+                .map_err(|_| format!("{name} needs a number, got {value}"))
+        };
+        match name.to_lowercase().as_str() {
+            "multipv" => {
+                self.multi_pv =
+                    usize::try_from(number()?.clamp(1, MAX_MULTI_PV as i64)).unwrap_or(1);
+            }
+            "skill level" => {
+                let level = number()?.clamp(0, i64::from(SkillLevel::MAX.level()));
+                self.skill_level = u8::try_from(level).unwrap_or(SkillLevel::MAX.level());
+            }
+            "contempt" => {
+                let contempt = number()?.clamp(-i64::from(MAX_CONTEMPT), i64::from(MAX_CONTEMPT));
+                self.contempt = i32::try_from(contempt).unwrap_or(0);
+            }
+            #[cfg(feature = "nnue")]
+            "evalfile" => {
+                self.network = if value.is_empty() || value == "<empty>" {
+                    None
+                } else {
+                    let bytes = std::fs::read(value).map_err(|e| format!("{value}: {e}"))?;
+                    let network =
+                        Network::from_bytes(&bytes).map_err(|e| format!("{value}: {e}"))?;
+                    Some(Arc::new(network))
+                };
+            }
+            _ => return Err(format!("unknown option {name}")),
+        }
+        Ok(())
+    }
+
+    fn engine(&self) -> Engine {
+        let seed = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |time| time.as_secs());
+        let engine = Engine::new()
+            .multi_pv(self.multi_pv)
+            .skill_level(SkillLevel::new(self.skill_level))
+            .contempt(self.contempt)
+            .seed(seed);
+        #[cfg(feature = "nnue")]
+        if let Some(network) = &self.network {
+            return engine.network(Arc::clone(network));
+        }
+        engine
+    }
+}
+
+/// A running search, `infinite` ones only end with `stop`.
+struct Search {
+    handle: EngineHandle,
+    infinite: bool,
+}
+
+struct Uci {
+    game: Game,
+    options: Options,
+    search: Option<Search>,
+}
+
+impl Uci {
+    /// Handles a line of the GUI, `false` on `quit`.
+    fn handle(&mut self, line: &str) -> bool {
+        let command = match command::parse(line) {
+            Ok(Some(command)) => command,
+            Ok(None) => return true,
+            Err(e) => {
+                println!("info string {e}");
+                return true;
+            }
+        };
+        match command {
+            Command::Uci => {
+                println!("id name rusty-chess {}", env!("CARGO_PKG_VERSION"));
+                println!("id author {}", env!("CARGO_PKG_AUTHORS"));
+                Options::print();
+                println!("uciok");
+            }
+            Command::IsReady => println!("readyok"),
+            Command::UciNewGame => {
+                self.stop();
+                self.game = Game::new();
+            }
+            Command::SetOption { name, value } => {
+                if let Err(e) = self.options.set(&name, &value) {
+                    println!("info string {e}");
+                }
+            }
+            Command::Position { game, illegal_move } => {
+                if let Some(uci) = illegal_move {
+                    println!("info string illegal move {uci}, ignoring the moves");
+                }
+                self.game = *game;
+            }
+            Command::Go(go) => {
+                self.stop();
+                self.go(&go);
+            }
+            Command::Stop => self.stop(),
+            Command::Quit => {
+                self.stop();
+                return false;
+            }
+        }
+        true
+    }
+
+    fn go(&mut self, go: &Go) {
+        let multi_pv = self.options.multi_pv;
+        let handle =
+            self.options
+                .engine()
+                .start_search_with_info(&self.game, go.limits, move |info| {
+                    print_info(info, multi_pv)
+                });
+        self.search = Some(Search {
+            handle,
+            infinite: go.infinite,
+        });
+    }
+
+    /// Prints the best move of a search that reached its limits.
+    fn poll(&mut self) {
+        if self
+            .search
+            .as_ref()
+            .is_some_and(|search| !search.infinite && search.handle.is_finished())
+        {
+            self.stop();
+        }
+    }
+
+    /// Stops the running search and prints its best move.
+    fn stop(&mut self) {
+        if let Some(search) = self.search.take() {
+            print_best_move(&search.handle.stop());
+        }
+    }
+
+    /// Waits for a running search that ends on its own, e.g. when the input
+    /// ended after `go depth 5`.
+    fn finish(&mut self) {
+        if let Some(search) = self.search.take() {
+            let result = if search.infinite {
+                search.handle.stop()
+            } else {
+                search.handle.wait()
+            };
+            print_best_move(&result);
+        }
+    }
+}
+
+fn print_info(info: &SearchInfo, multi_pv: usize) {
+    for (index, line) in info.lines.iter().enumerate() {
+        let score = match line.mate_in() {
+            Some(moves) => format!("mate {moves}"),
+            None => format!("cp {}", line.score),
+        };
+        let pv: Vec<String> = line.moves.iter().map(|mv| mv.to_uci()).collect();
+        let multi_pv = if multi_pv > 1 {
+            format!(" multipv {}", index + 1)
+        } else {
+            String::new()
+        };
+        println!(
+            "info depth {}{multi_pv} score {score} nodes {} nps {} time {} pv {}",
+            info.depth,
+            info.nodes,
+            info.nps,
+            info.time.as_millis(),
+            pv.join(" ")
+        );
+    }
+}
+
+fn print_best_move(result: &SearchResult) {
+    match result.best_move {
+        Some(mv) => println!("bestmove {}", mv.to_uci()),
+        // the position is already over
+        None => println!("bestmove 0000"),
+    }
+}
+
+fn main() {
+    // the input is read on its own thread, so that the end of a search is
+    // noticed while waiting for the next command
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        for line in io::stdin().lock().lines() {
+            let Ok(line) = line else { break };
+            if sender.send(line).is_err() {
+                break;
+            }
+        }
+    });
+    let mut uci = Uci {
+        game: Game::new(),
+        options: Options {
+            multi_pv: 1,
+            skill_level: SkillLevel::MAX.level(),
+            contempt: 0,
+            #[cfg(feature = "nnue")]
+            network: None,
+        },
+        search: None,
+    };
+    loop {
+        match receiver.recv_timeout(POLL_INTERVAL) {
+            Ok(line) => {
+                if !uci.handle(&line) {
+                    break;
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => {
+                uci.finish();
+                break;
+            }
+        }
+        uci.poll();
+    }
+}
@@ -264,6 +264,25 @@ playPauseButton.addEventListener("click", (event) => {
   }
 });
 
+// step through the moves while paused
+document.addEventListener("keydown", (event) => {
+  if (!isPaused()) {
+    return;
+  }
+  if (event.key === "u") {
+    chessGame.undo();
+    finished = false;
+  } else if (event.key === "r") {
+    chessGame.redo();
+  } else {
+    return;
+  }
+  boardPtr = chessGame.get_game_board();
+  board = new Uint8Array(memory.buffer, boardPtr, 64);
+  drawGrid();
+  drawBoard();
+});
+
 const pre = document.getElementById("rusty-chess-wasm-pre");
 let previous_timestamp = -Infinity;
 function renderLoop() {
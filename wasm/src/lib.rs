@@ -4,7 +4,11 @@ mod utils;
 use utils::set_panic_hook;
 use wasm_bindgen::prelude::*;
 
-use rusty_chess_core::game::{Color, Game, PieceType, UserInput, UserOutput};
+use rusty_chess_core::bots;
+use rusty_chess_core::game::{
+    BotRng, Color, Game, GameStatus, MoveError, PieceType, SeedableRng, UserInput,
+};
+use std::fmt;
 
 // Canvas in wasm
 // https://rustwasm.github.io/wasm-bindgen/examples/2d-canvas.html
@@ -13,6 +17,7 @@ use rusty_chess_core::game::{Color, Game, PieceType, UserInput, UserOutput};
 pub struct ChessGame {
     game: Game,
     game_board: [Piece; 64],
+    rng: BotRng,
 }
 
 #[wasm_bindgen]
@@ -41,62 +46,63 @@ extern "C" {
 
 #[wasm_bindgen]
 #[derive(Debug)]
-pub struct UserOutputWrapper(UserOutput);
+pub struct GameStatusWrapper(Result<GameStatus, MoveError>);
 
 #[wasm_bindgen]
 #[derive(Debug)]
 pub struct PositionWrapper(pub char, pub char);
 
 #[wasm_bindgen]
-impl UserOutputWrapper {
+impl GameStatusWrapper {
     pub fn is_check_mate(&self) -> bool {
-        match self.0 {
-            UserOutput::CheckMate => true,
-            _ => false,
-        }
+        matches!(self.0, Ok(GameStatus::Checkmate(_)))
     }
 
     pub fn is_stale_mate(&self) -> bool {
-        match self.0 {
-            UserOutput::StaleMate => true,
-            _ => false,
-        }
+        matches!(self.0, Ok(GameStatus::Stalemate))
     }
     pub fn is_invalid_move(&self) -> bool {
-        match self.0 {
-            UserOutput::InvalidMove => true,
-            _ => false,
-        }
+        self.0.is_err()
     }
     pub fn is_promotion(&self) -> bool {
-        match self.0 {
-            UserOutput::Promotion(_) => true,
-            _ => false,
-        }
+        matches!(self.0, Ok(GameStatus::AwaitingPromotion(_)))
     }
     pub fn promotion_pos(&self) -> Option<PositionWrapper> {
         match self.0 {
-            UserOutput::Promotion(pos) => Some(PositionWrapper(pos.0, pos.1)),
+            Ok(GameStatus::AwaitingPromotion(pos)) => Some(PositionWrapper(pos.0, pos.1)),
             _ => None,
         }
     }
     pub fn is_draw(&self) -> bool {
-        match self.0 {
-            UserOutput::Draw => true,
-            _ => false,
-        }
+        matches!(self.0, Ok(GameStatus::Draw(_)))
+    }
+    pub fn is_check(&self) -> bool {
+        matches!(self.0, Ok(GameStatus::Check))
     }
 
-    pub fn to_string(&self) -> String {
+    #[wasm_bindgen(js_name = to_string)]
+    pub fn to_js_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl fmt::Display for GameStatusWrapper {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self.0 {
-            UserOutput::CheckMate => "CheckMate".to_string(),
-            UserOutput::StaleMate => "StaleMate".to_string(),
-            UserOutput::InvalidMove => "InvalidMove".to_string(),
-            UserOutput::Promotion(pos) => format!("Promotion ({},{})", pos.0, pos.1),
-            UserOutput::Draw => "Draw".to_string(),
+            Ok(GameStatus::Ongoing) => write!(f, "Ongoing"),
+            Ok(GameStatus::Check) => write!(f, "Check"),
+            Ok(GameStatus::Checkmate(winner)) => write!(f, "CheckMate ({winner:?} wins)"),
+            Ok(GameStatus::Resignation(winner)) => write!(f, "Resignation ({winner:?} wins)"),
+            Ok(GameStatus::Timeout(winner)) => write!(f, "Timeout ({winner:?} wins)"),
+            Ok(GameStatus::VariantWin(winner)) => write!(f, "VariantWin ({winner:?} wins)"),
+            Ok(GameStatus::Stalemate) => write!(f, "StaleMate"),
+            Ok(GameStatus::Draw(reason)) => write!(f, "Draw ({reason:?})"),
+            Ok(GameStatus::AwaitingPromotion(pos)) => write!(f, "Promotion ({},{})", pos.0, pos.1),
+            Err(ref e) => write!(f, "InvalidMove ({e})"),
         }
     }
 }
+
 impl ChessGame {
     fn update_game_board(&mut self) {
         for (i, piece) in self.game.board.iter().enumerate() {
@@ -122,6 +128,17 @@ impl ChessGame {
         }
     }
 
+    /// `status` is `None` if the bot had no move to play.
+    fn bot_played(&mut self, status: Option<GameStatus>) -> Option<GameStatusWrapper> {
+        let Some(status) = status else {
+            console_log!("Something went wrong. Function was probably called after check mate or stale mate.");
+            return None;
+        };
+        self.update_game_board();
+        console_log!("{}", self.game);
+        Some(GameStatusWrapper(Ok(status)))
+    }
+
     #[allow(dead_code)]
     fn get_index(row: char, col: char) -> usize {
         let row = row as usize - '1' as usize;
@@ -130,6 +147,12 @@ impl ChessGame {
     }
 }
 
+impl Default for ChessGame {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[wasm_bindgen]
 impl ChessGame {
     pub fn new() -> ChessGame {
@@ -137,7 +160,13 @@ impl ChessGame {
         let game = Game::new();
         let game_board = [Piece::Empty; 64];
 
-        let mut chess_game = ChessGame { game, game_board };
+        let seed = (js_sys::Math::random() * u64::MAX as f64) as u64;
+
+        let mut chess_game = ChessGame {
+            game,
+            game_board,
+            rng: BotRng::seed_from_u64(seed),
+        };
         chess_game.update_game_board();
         console_log!("{}", chess_game.game);
         chess_game
@@ -149,74 +178,76 @@ impl ChessGame {
         from2: char,
         to1: char,
         to2: char,
-    ) -> Option<UserOutputWrapper> {
-        let user_output = self
+    ) -> GameStatusWrapper {
+        let status = self
             .game
-            .process_input(&UserInput::Move((from1, from2).into(), (to1, to2).into()))
-            .map(|x| UserOutputWrapper(x));
+            .process_input(&UserInput::Move((from1, from2).into(), (to1, to2).into()));
         self.update_game_board();
-        user_output
+        GameStatusWrapper(status)
     }
 
-    pub fn play_attacking_king(&mut self) -> Option<UserOutputWrapper> {
-        let possible_moves = self.game.get_all_currently_valid_moves();
-        if possible_moves.is_empty() {
-            console_log!("Something went wrong. Function was probably called after check mate or stale mate.");
-            return None;
-        }
+    pub fn play_attacking_king(&mut self) -> Option<GameStatusWrapper> {
+        let status = bots::play_attacking_king(&mut self.game, &mut self.rng);
+        self.bot_played(status)
+    }
 
-        let move_to_play = possible_moves
-            .iter()
-            .find(|mv| {
-                let mut game = self.game.clone();
-                match game.process_input(&UserInput::Move(mv.from, mv.to)) {
-                    Some(UserOutput::CheckMate) => true,
-                    _ => game.check(self.game.turn.invert()),
-                }
-            })
-            .unwrap_or_else(|| {
-                match possible_moves.iter().find(|mv| mv.captured_piece.is_some()) {
-                    Some(mv) => mv,
-                    None => {
-                        let random_index =
-                            (js_sys::Math::random() * (possible_moves.len() as f64 - 1.0)) as usize;
-                        &possible_moves[random_index]
-                    }
-                }
-            });
-
-        let user_output = self
-            .game
-            .process_input(&UserInput::Move(move_to_play.from, move_to_play.to))
-            .map(|x| UserOutputWrapper(x));
-        self.update_game_board();
-        console_log!("{}", self.game);
-        user_output
+    pub fn play_randomly_aggressive(&mut self) -> Option<GameStatusWrapper> {
+        let status = bots::play_randomly_aggressive(&mut self.game, &mut self.rng);
+        self.bot_played(status)
     }
 
-    pub fn play_randomly_aggressive(&mut self) -> Option<UserOutputWrapper> {
-        let possible_moves = self.game.get_all_currently_valid_moves();
-        if possible_moves.is_empty() {
-            console_log!("Something went wrong. Function was probably called after check mate or stale mate.");
-            return None;
-        }
-        let move_to_play = match possible_moves.iter().find(|mv| mv.captured_piece.is_some()) {
-            Some(mv) => mv,
-            None => {
-                let random_index =
-                    (js_sys::Math::random() * (possible_moves.len() as f64 - 1.0)) as usize;
-                &possible_moves[random_index]
-            }
-        };
-        console_log!("{move_to_play}");
+    /// Makes the bots play the same moves again for the same seed.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rng = BotRng::seed_from_u64(seed);
+    }
 
-        let user_output = self
-            .game
-            .process_input(&UserInput::Move(move_to_play.from, move_to_play.to))
-            .map(|x| UserOutputWrapper(x));
+    pub fn claim_draw(&mut self) -> GameStatusWrapper {
+        GameStatusWrapper(self.game.process_input(&UserInput::ClaimDraw))
+    }
+
+    pub fn undo(&mut self) -> bool {
+        let undone = self.game.undo().is_some();
         self.update_game_board();
-        console_log!("{}", self.game);
-        user_output
+        undone
+    }
+
+    pub fn redo(&mut self) -> Option<GameStatusWrapper> {
+        let status = self.game.redo()?;
+        self.update_game_board();
+        Some(GameStatusWrapper(Ok(status)))
+    }
+
+    pub fn material_balance(&self) -> i32 {
+        self.game.material_balance()
+    }
+
+    pub fn halfmove_clock(&self) -> u16 {
+        self.game.halfmove_clock()
+    }
+
+    pub fn fullmove_number(&self) -> u16 {
+        self.game.fullmove_number()
+    }
+
+    /// Compact encoding of the game, e.g. for shareable links.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.game.to_bytes()
+    }
+
+    /// Replaces the game with one encoded by `to_bytes`, returns `false` and
+    /// keeps the current game if the bytes are invalid.
+    pub fn load_bytes(&mut self, bytes: &[u8]) -> bool {
+        match Game::from_bytes(bytes) {
+            Ok(game) => {
+                self.game = game;
+                self.update_game_board();
+                true
+            }
+            Err(e) => {
+                console_log!("Could not load game: {e}");
+                false
+            }
+        }
     }
 
     pub fn get_game_board(&self) -> *const Piece {